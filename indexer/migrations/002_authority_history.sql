@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS authority_history (
+    id SERIAL PRIMARY KEY,
+    schedule_address TEXT NOT NULL,
+    action TEXT NOT NULL,
+    authority TEXT NOT NULL,
+    pending_authority TEXT,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (tx_signature, schedule_address, action)
+);
+
+CREATE INDEX IF NOT EXISTS idx_authority_history_schedule ON authority_history(schedule_address);
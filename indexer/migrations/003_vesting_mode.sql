@@ -0,0 +1,5 @@
+-- 0 = step, 1 = linear, existing schedules were all created in step mode
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS vesting_mode SMALLINT NOT NULL DEFAULT 0;
+
+-- vested share of the allocation at the claim's block time, in BPS
+ALTER TABLE claims ADD COLUMN IF NOT EXISTS vested_bps BIGINT;
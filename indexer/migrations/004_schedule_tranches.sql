@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS schedule_tranches (
+    schedule_address TEXT NOT NULL,
+    tranche_index SMALLINT NOT NULL,
+    offset_seconds BIGINT NOT NULL,
+    cumulative_bps INTEGER NOT NULL,
+    PRIMARY KEY (schedule_address, tranche_index)
+);
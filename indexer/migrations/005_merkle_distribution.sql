@@ -0,0 +1,6 @@
+-- hex encoded, NULL for schedules without merkle recipients
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS merkle_root TEXT;
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS merkle_total BIGINT NOT NULL DEFAULT 0;
+
+-- participants created by their own first claim with a merkle proof
+ALTER TABLE participants ADD COLUMN IF NOT EXISTS from_merkle_leaf BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,19 @@
+-- set on the old participant row once its grant moved to a new wallet
+ALTER TABLE participants ADD COLUMN IF NOT EXISTS transferred_to TEXT;
+
+CREATE TABLE IF NOT EXISTS beneficiary_transfers (
+    id SERIAL PRIMARY KEY,
+    schedule_address TEXT NOT NULL,
+    old_participant_pda TEXT NOT NULL,
+    old_wallet TEXT NOT NULL,
+    new_participant_pda TEXT NOT NULL,
+    new_wallet TEXT NOT NULL,
+    signer TEXT NOT NULL,
+    recovery BOOLEAN NOT NULL,
+    tx_signature TEXT NOT NULL UNIQUE,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_beneficiary_transfers_old ON beneficiary_transfers(old_participant_pda);
+CREATE INDEX IF NOT EXISTS idx_beneficiary_transfers_new ON beneficiary_transfers(new_participant_pda);
@@ -0,0 +1,6 @@
+-- signer of a CrankClaim, NULL when the participant claimed themselves
+ALTER TABLE claims ADD COLUMN IF NOT EXISTS keeper TEXT;
+
+-- a keeper pushes many claims in one transaction, so a signature alone no longer identifies a claim
+ALTER TABLE claims DROP CONSTRAINT IF EXISTS claims_tx_signature_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_claims_tx_participant ON claims(tx_signature, participant_pda);
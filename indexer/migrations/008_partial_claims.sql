@@ -0,0 +1,2 @@
+-- amount asked for in Claim, NULL when the claim took everything available; claimed_amount is what actually left the vault
+ALTER TABLE claims ADD COLUMN IF NOT EXISTS requested_amount BIGINT;
@@ -0,0 +1,14 @@
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS paused BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS pause_events (
+    id SERIAL PRIMARY KEY,
+    schedule_address TEXT NOT NULL,
+    action TEXT NOT NULL,
+    authority TEXT NOT NULL,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (tx_signature, schedule_address, action)
+);
+
+CREATE INDEX IF NOT EXISTS idx_pause_events_schedule ON pause_events(schedule_address);
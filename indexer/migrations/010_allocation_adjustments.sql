@@ -0,0 +1,15 @@
+-- previous_amount is taken from the participant row, so it is NULL if the participant was added before the indexer's start slot
+CREATE TABLE IF NOT EXISTS allocation_adjustments (
+    id SERIAL PRIMARY KEY,
+    participant_pda TEXT NOT NULL,
+    schedule_address TEXT NOT NULL,
+    previous_amount BIGINT,
+    new_amount BIGINT NOT NULL,
+    authority TEXT NOT NULL,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (tx_signature, participant_pda)
+);
+
+CREATE INDEX IF NOT EXISTS idx_allocation_adjustments_participant ON allocation_adjustments(participant_pda);
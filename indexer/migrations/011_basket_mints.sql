@@ -0,0 +1,40 @@
+-- extra mints a schedule pays out on the same timeline as its primary mint
+CREATE TABLE IF NOT EXISTS schedule_basket_mints (
+    schedule_address TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    vault TEXT NOT NULL,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (schedule_address, mint)
+);
+
+-- a participant's allocation of each basket mint, the primary mint stays in participants
+CREATE TABLE IF NOT EXISTS participant_basket_allocations (
+    participant_pda TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    schedule_address TEXT NOT NULL,
+    allocated_amount BIGINT NOT NULL,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (participant_pda, mint)
+);
+
+-- one claim row per mint paid out, rows from before baskets get the schedule's primary mint
+ALTER TABLE claims ADD COLUMN IF NOT EXISTS mint TEXT;
+UPDATE claims SET mint = schedules.mint
+FROM schedules
+WHERE claims.mint IS NULL AND claims.schedule_address = schedules.schedule_address;
+
+-- widen 007's index under the same name, so rerunning 007 at startup leaves it alone
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM pg_indexes
+        WHERE indexname = 'idx_claims_tx_participant' AND indexdef NOT LIKE '%mint%'
+    ) THEN
+        DROP INDEX idx_claims_tx_participant;
+    END IF;
+END $$;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_claims_tx_participant ON claims(tx_signature, participant_pda, mint);
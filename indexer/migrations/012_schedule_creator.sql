@@ -0,0 +1,5 @@
+-- key namespacing the schedule pda, the authority that signed Initialize, unlike authority it never rotates
+-- NULL for schedules created under the legacy global ["schedule", seed] address
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS creator TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_schedules_creator ON schedules(creator);
@@ -0,0 +1,2 @@
+-- on-chain clock at the claim, taken from the program's Claimed event; NULL for claims indexed before events existed
+ALTER TABLE claims ADD COLUMN IF NOT EXISTS claimed_at BIGINT;
@@ -0,0 +1,18 @@
+-- funder pays allocations and receives refunds, pauser may pause besides the authority
+-- NULL for schedules indexed before the program had roles, pauser is also NULL when unset
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS funder TEXT;
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS pauser TEXT;
+
+CREATE TABLE IF NOT EXISTS role_changes (
+    id SERIAL PRIMARY KEY,
+    schedule_address TEXT NOT NULL,
+    role TEXT NOT NULL,
+    signer TEXT NOT NULL,
+    holder TEXT,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (tx_signature, schedule_address, role)
+);
+
+CREATE INDEX IF NOT EXISTS idx_role_changes_schedule ON role_changes(schedule_address);
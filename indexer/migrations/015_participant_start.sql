@@ -0,0 +1,2 @@
+-- participant's own start from AddParticipant, the schedule's durations run from it; NULL follows the schedule's start
+ALTER TABLE participants ADD COLUMN IF NOT EXISTS start_timestamp BIGINT;
@@ -0,0 +1,18 @@
+-- one row per Accelerate, accelerated_bps is the participant's running total after it
+CREATE TABLE IF NOT EXISTS accelerations (
+    id SERIAL PRIMARY KEY,
+    participant_pda TEXT NOT NULL,
+    schedule_address TEXT NOT NULL,
+    added_bps INTEGER NOT NULL,
+    accelerated_bps INTEGER NOT NULL,
+    authority TEXT NOT NULL,
+    accelerated_at BIGINT NOT NULL,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (tx_signature, participant_pda)
+);
+
+CREATE INDEX IF NOT EXISTS idx_accelerations_participant ON accelerations(participant_pda);
+
+ALTER TABLE participants ADD COLUMN IF NOT EXISTS accelerated_bps INTEGER NOT NULL DEFAULT 0;
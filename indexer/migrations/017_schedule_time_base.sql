@@ -0,0 +1,2 @@
+-- 0 = unix timestamp, 1 = slot, 2 = epoch; existing schedules all count in unix timestamps
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS time_base SMALLINT NOT NULL DEFAULT 0;
@@ -0,0 +1,25 @@
+-- one row per mint a Revoke cut down, the primary mint first
+CREATE TABLE IF NOT EXISTS revocations (
+    id SERIAL PRIMARY KEY,
+    participant_pda TEXT NOT NULL,
+    schedule_address TEXT NOT NULL,
+    mint TEXT NOT NULL,
+    clawback_amount BIGINT NOT NULL,
+    allocated_amount BIGINT NOT NULL,
+    terminated_at BIGINT NOT NULL,
+    tx_signature TEXT NOT NULL,
+    slot BIGINT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (tx_signature, participant_pda, mint)
+);
+
+CREATE INDEX IF NOT EXISTS idx_revocations_participant ON revocations(participant_pda);
+
+-- in the schedule's time base, NULL until revoked
+ALTER TABLE participants ADD COLUMN IF NOT EXISTS terminated_at BIGINT;
+
+-- NULL while the account is open
+ALTER TABLE participants ADD COLUMN IF NOT EXISTS closed_tx_signature TEXT;
+ALTER TABLE participants ADD COLUMN IF NOT EXISTS closed_slot BIGINT;
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS closed_tx_signature TEXT;
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS closed_slot BIGINT;
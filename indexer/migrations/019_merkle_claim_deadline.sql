@@ -0,0 +1,2 @@
+-- in the schedule's time base, NULL without a merkle root; unclaimed leaves go back to the funder after it
+ALTER TABLE schedules ADD COLUMN IF NOT EXISTS merkle_claim_deadline BIGINT;
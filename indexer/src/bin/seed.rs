@@ -2,8 +2,13 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use solana_client::rpc_client::RpcClient;
 use solana_commitment_config::CommitmentConfig;
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    Initialize, InitializeAccounts, InitializeInstructionData, TimeBase, VestingMode,
+};
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::Instruction,
     native_token::LAMPORTS_PER_SOL,
     program_pack::Pack,
     pubkey::Pubkey,
@@ -13,11 +18,13 @@ use solana_sdk::{
 };
 use solana_system_interface::instruction as system_instruction;
 
-const PROGRAM_ID: Pubkey = solana_sdk::pubkey!("FwnGeaANDtRZHA1xXzjyTjr5mmEZtXBSKuA3umcRPiWG");
 const SYSTEM_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("11111111111111111111111111111111");
 
-fn get_schedule_pda(seed: u64) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"schedule", &seed.to_le_bytes()], &PROGRAM_ID)
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
 }
 
 fn get_participant_pda(wallet: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
@@ -36,34 +43,39 @@ fn build_initialize_ix(
     schedule: &Pubkey,
     mint: &Pubkey,
     vault: &Pubkey,
-    start_timestamp: u64,
-    cliff_duration: u64,
-    step_duration: u64,
-    total_duration: u64,
+    start_timestamp: i64,
+    cliff_duration: i64,
+    step_duration: i64,
+    total_duration: i64,
     seed: u64,
     bump: u8,
 ) -> Instruction {
-    let mut data = vec![0u8];
-    data.extend_from_slice(&start_timestamp.to_le_bytes());
-    data.extend_from_slice(&cliff_duration.to_le_bytes());
-    data.extend_from_slice(&step_duration.to_le_bytes());
-    data.extend_from_slice(&total_duration.to_le_bytes());
-    data.extend_from_slice(&seed.to_le_bytes());
-    data.push(bump);
-
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*authority, true),
-            AccountMeta::new(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    let data = InitializeInstructionData {
+        start_timestamp,
+        cliff_duration,
+        step_duration,
+        total_duration,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
         ],
-        data,
-    }
+        &data.to_bytes(),
+    )
 }
 
 fn build_add_participant_ix(
@@ -76,24 +88,25 @@ fn build_add_participant_ix(
     mint: &Pubkey,
     allocation: u64,
 ) -> Instruction {
-    let mut data = vec![1u8];
-    data.extend_from_slice(&allocation.to_le_bytes());
-
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*authority, true),
-            AccountMeta::new(*authority_ata, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(*participant_wallet, false),
-            AccountMeta::new(*vested_participant_pda, false),
-            AccountMeta::new_readonly(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
         ],
-        data,
-    }
+        &data.to_bytes(),
+    )
 }
 
 fn build_claim_ix(
@@ -104,21 +117,22 @@ fn build_claim_ix(
     schedule: &Pubkey,
     mint: &Pubkey,
 ) -> Instruction {
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*participant_wallet, true),
-            AccountMeta::new(*vested_participant, false),
-            AccountMeta::new(*participant_ata, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
         ],
-        data: vec![2u8],
-    }
+        &[],
+    )
 }
 
 fn send_and_confirm(
@@ -207,12 +221,12 @@ fn main() {
         .unwrap()
         .as_secs();
     let seed: u64 = now; // unique per run
-    let start_timestamp = now + 5; // 5s in the future
-    let cliff_duration: u64 = 5;
-    let step_duration: u64 = 5;
-    let total_duration: u64 = 30;
+    let start_timestamp = now as i64 + 5; // 5s in the future
+    let cliff_duration: i64 = 5;
+    let step_duration: i64 = 5;
+    let total_duration: i64 = 30;
 
-    let (schedule, bump) = get_schedule_pda(seed);
+    let (schedule, bump) = get_schedule_pda(&payer.pubkey(), &mint.pubkey(), seed);
     let vault = get_ata(&schedule, &mint.pubkey());
 
     let init_ix = build_initialize_ix(
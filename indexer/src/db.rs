@@ -6,11 +6,34 @@ pub async fn init_pool(database_url: &str) -> PgPool {
         .expect("failed to connect to database")
 }
 
+const MIGRATIONS: &[&str] = &[
+    include_str!("../migrations/001_init.sql"),
+    include_str!("../migrations/002_authority_history.sql"),
+    include_str!("../migrations/003_vesting_mode.sql"),
+    include_str!("../migrations/004_schedule_tranches.sql"),
+    include_str!("../migrations/005_merkle_distribution.sql"),
+    include_str!("../migrations/006_beneficiary_transfers.sql"),
+    include_str!("../migrations/007_keeper_claims.sql"),
+    include_str!("../migrations/008_partial_claims.sql"),
+    include_str!("../migrations/009_pause.sql"),
+    include_str!("../migrations/010_allocation_adjustments.sql"),
+    include_str!("../migrations/011_basket_mints.sql"),
+    include_str!("../migrations/012_schedule_creator.sql"),
+    include_str!("../migrations/013_claim_timestamps.sql"),
+    include_str!("../migrations/014_schedule_roles.sql"),
+    include_str!("../migrations/015_participant_start.sql"),
+    include_str!("../migrations/016_accelerations.sql"),
+    include_str!("../migrations/017_schedule_time_base.sql"),
+    include_str!("../migrations/018_revocations_and_closures.sql"),
+    include_str!("../migrations/019_merkle_claim_deadline.sql"),
+];
+
 pub async fn run_migrations(pool: &PgPool) {
-    let sql = include_str!("../migrations/001_init.sql");
-    sqlx::raw_sql(sql)
-        .execute(pool)
-        .await
-        .expect("failed to run migrations");
+    for sql in MIGRATIONS {
+        sqlx::raw_sql(sql)
+            .execute(pool)
+            .await
+            .expect("failed to run migrations");
+    }
     log::info!("migrations applied");
 }
@@ -0,0 +1,99 @@
+use carbon_core::{borsh, deserialize::CarbonDeserialize};
+use multi_token_vesting::{
+    AccountMigrated, AllocationAdjusted, AuthorityAccepted, AuthorityProposed,
+    AuthorityTransferCancelled, BasketAllocationAdded, BasketMintAdded, BeneficiaryTransferred,
+    Claimed, Event, FunderUpdated, MerkleRootSet, ParticipantAccelerated, ParticipantAdded,
+    ParticipantClosed, ParticipantRevoked, PauseUpdated, PauserUpdated, ScheduleClosed,
+    ScheduleCreated, EVENT_TAG,
+};
+
+/// All events the vesting program logs with `sol_log_data`, one per state change.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VestingEvent {
+    ScheduleCreated(ScheduleCreated),
+    ParticipantAdded(ParticipantAdded),
+    Claimed(Claimed),
+    ParticipantRevoked(ParticipantRevoked),
+    AuthorityProposed(AuthorityProposed),
+    AuthorityAccepted(AuthorityAccepted),
+    AuthorityTransferCancelled(AuthorityTransferCancelled),
+    ParticipantClosed(ParticipantClosed),
+    ScheduleClosed(ScheduleClosed),
+    MerkleRootSet(MerkleRootSet),
+    BeneficiaryTransferred(BeneficiaryTransferred),
+    PauseUpdated(PauseUpdated),
+    AllocationAdjusted(AllocationAdjusted),
+    BasketMintAdded(BasketMintAdded),
+    BasketAllocationAdded(BasketAllocationAdded),
+    FunderUpdated(FunderUpdated),
+    PauserUpdated(PauserUpdated),
+    AccountMigrated(AccountMigrated),
+    ParticipantAccelerated(ParticipantAccelerated),
+}
+
+impl VestingEvent {
+    /// Decodes the bytes of one `Program data:` log, `None` for anything that isn't a known event.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let kind = *data.get(EVENT_TAG.len())?;
+        match kind {
+            ScheduleCreated::KIND => ScheduleCreated::from_bytes(data).map(Self::ScheduleCreated),
+            ParticipantAdded::KIND => ParticipantAdded::from_bytes(data).map(Self::ParticipantAdded),
+            Claimed::KIND => Claimed::from_bytes(data).map(Self::Claimed),
+            ParticipantRevoked::KIND => {
+                ParticipantRevoked::from_bytes(data).map(Self::ParticipantRevoked)
+            }
+            AuthorityProposed::KIND => {
+                AuthorityProposed::from_bytes(data).map(Self::AuthorityProposed)
+            }
+            AuthorityAccepted::KIND => {
+                AuthorityAccepted::from_bytes(data).map(Self::AuthorityAccepted)
+            }
+            AuthorityTransferCancelled::KIND => {
+                AuthorityTransferCancelled::from_bytes(data).map(Self::AuthorityTransferCancelled)
+            }
+            ParticipantClosed::KIND => {
+                ParticipantClosed::from_bytes(data).map(Self::ParticipantClosed)
+            }
+            ScheduleClosed::KIND => ScheduleClosed::from_bytes(data).map(Self::ScheduleClosed),
+            MerkleRootSet::KIND => MerkleRootSet::from_bytes(data).map(Self::MerkleRootSet),
+            BeneficiaryTransferred::KIND => {
+                BeneficiaryTransferred::from_bytes(data).map(Self::BeneficiaryTransferred)
+            }
+            PauseUpdated::KIND => PauseUpdated::from_bytes(data).map(Self::PauseUpdated),
+            AllocationAdjusted::KIND => {
+                AllocationAdjusted::from_bytes(data).map(Self::AllocationAdjusted)
+            }
+            BasketMintAdded::KIND => BasketMintAdded::from_bytes(data).map(Self::BasketMintAdded),
+            BasketAllocationAdded::KIND => {
+                BasketAllocationAdded::from_bytes(data).map(Self::BasketAllocationAdded)
+            }
+            FunderUpdated::KIND => FunderUpdated::from_bytes(data).map(Self::FunderUpdated),
+            PauserUpdated::KIND => PauserUpdated::from_bytes(data).map(Self::PauserUpdated),
+            AccountMigrated::KIND => AccountMigrated::from_bytes(data).map(Self::AccountMigrated),
+            ParticipantAccelerated::KIND => {
+                ParticipantAccelerated::from_bytes(data).map(Self::ParticipantAccelerated)
+            }
+            _ => None,
+        }
+    }
+}
+
+// Carbon hands every log of the instruction to `CarbonDeserialize`, the Borsh bound is only
+// there for Anchor style events and simply forwards to the packed layout.
+impl borsh::BorshDeserialize for VestingEvent {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Self::from_bytes(&data).ok_or_else(|| {
+            borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "not a vesting event")
+        })
+    }
+}
+
+impl CarbonDeserialize for VestingEvent {
+    const DISCRIMINATOR: &'static [u8] = &EVENT_TAG;
+
+    fn deserialize(data: &[u8]) -> Option<Self> {
+        Self::from_bytes(data)
+    }
+}
@@ -1,14 +1,34 @@
-use solana_pubkey::Pubkey;
+use multi_token_vesting::Tranche;
 
 /// All instructions the vesting program can process.
 #[derive(Debug, Clone, PartialEq)]
 pub enum VestingInstruction {
     Initialize(InitializeData),
     AddParticipant(AddParticipantData),
-    Claim,
+    BatchAddParticipant(BatchAddParticipantData),
+    Claim(ClaimData),
+    Revoke,
+    ProposeAuthority,
+    AcceptAuthority,
+    CancelAuthorityTransfer,
+    CloseParticipant,
+    CloseSchedule,
+    SetMerkleRoot(SetMerkleRootData),
+    TransferBeneficiary,
+    CrankClaim,
+    Pause,
+    Unpause,
+    AdjustAllocation(AdjustAllocationData),
+    AddBasketMint,
+    AddBasketAllocation(AddBasketAllocationData),
+    GetVestingStatus,
+    SetFunder,
+    SetPauser,
+    MigrateAccount,
+    Accelerate(AccelerateData),
 }
 
-/// 41 bytes after discriminator.
+/// Owned copy of the program's `InitializeInstructionData`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct InitializeData {
     pub start_timestamp: i64,
@@ -17,49 +37,54 @@ pub struct InitializeData {
     pub total_duration: i64,
     pub seed: u64,
     pub bump: u8,
+    pub vesting_mode: u8,
+    pub time_base: u8,
+    pub tranches: Vec<Tranche>,
 }
 
-/// 8 bytes after discriminator.
+/// Owned copy of the program's `AddParticipantInstructionData`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct AddParticipantData {
     pub token_allocation_amount: u64,
+    pub start: Option<i64>,
 }
 
-// ---------- Account arrangement structs ----------
-#[allow(dead_code)]
+/// The amounts of the program's `BatchAddParticipantInstructionData`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchAddParticipantData {
+    pub token_allocation_amounts: Vec<u64>,
+}
 
-pub struct InitializeAccounts {
-    pub authority: Pubkey,
-    pub schedule: Pubkey,
-    pub mint: Pubkey,
-    pub vault: Pubkey,
-    pub system_program: Pubkey,
-    pub token_program: Pubkey,
-    pub ata_program: Pubkey,
+/// The program's `ClaimInstructionData` without the merkle proof.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClaimData {
+    pub close_when_finalized: bool,
+    pub requested_amount: Option<u64>,
+    pub merkle_allocation: Option<u64>,
 }
 
-#[allow(dead_code)]
-pub struct AddParticipantAccounts {
-    pub authority: Pubkey,
-    pub authority_ata: Pubkey,
-    pub vault: Pubkey,
-    pub participant_wallet: Pubkey,
-    pub vested_participant: Pubkey,
-    pub schedule: Pubkey,
-    pub mint: Pubkey,
-    pub system_program: Pubkey,
-    pub token_program: Pubkey,
+/// Owned copy of the program's `SetMerkleRootInstructionData`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetMerkleRootData {
+    pub merkle_root: [u8; 32],
+    pub merkle_total: u64,
+    pub merkle_claim_deadline: i64,
 }
 
-#[allow(dead_code)]
-pub struct ClaimAccounts {
-    pub participant_wallet: Pubkey,
-    pub vested_participant: Pubkey,
-    pub participant_ata: Pubkey,
-    pub vault: Pubkey,
-    pub schedule: Pubkey,
-    pub mint: Pubkey,
-    pub system_program: Pubkey,
-    pub token_program: Pubkey,
-    pub ata_program: Pubkey,
+/// Owned copy of the program's `AdjustAllocationInstructionData`, the target allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjustAllocationData {
+    pub token_allocation_amount: u64,
+}
+
+/// Owned copy of the program's `AddBasketAllocationInstructionData`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddBasketAllocationData {
+    pub token_allocation_amount: u64,
+}
+
+/// Owned copy of the program's `AccelerateInstructionData`, the share vested on top of the curve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccelerateData {
+    pub accelerated_bps: u16,
 }
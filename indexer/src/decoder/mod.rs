@@ -1,12 +1,23 @@
-pub mod accounts;
+pub mod events;
 pub mod instructions;
 
 use carbon_core::instruction::{DecodedInstruction, InstructionDecoder};
-use instructions::{AddParticipantData, InitializeData, VestingInstruction};
+use instructions::{
+    AccelerateData, AddBasketAllocationData, AddParticipantData, AdjustAllocationData,
+    BatchAddParticipantData, ClaimData, InitializeData, SetMerkleRootData, VestingInstruction,
+};
+use multi_token_vesting::{
+    Accelerate, AccelerateInstructionData, AcceptAuthority, AddBasketAllocation,
+    AddBasketAllocationInstructionData, AddBasketMint, AddParticipant, AddParticipantInstructionData,
+    AdjustAllocation, AdjustAllocationInstructionData, BatchAddParticipant,
+    BatchAddParticipantInstructionData, CancelAuthorityTransfer, Claim, ClaimInstructionData,
+    CloseParticipant, CloseSchedule, CrankClaim, GetVestingStatus, Initialize,
+    InitializeInstructionData, MigrateAccount, Pause, ProposeAuthority, Revoke, SetFunder,
+    SetMerkleRoot, SetMerkleRootInstructionData, SetPauser, TransferBeneficiary, Unpause,
+};
 use solana_pubkey::Pubkey;
 
-pub const PROGRAM_ID: Pubkey =
-    solana_pubkey::pubkey!("FwnGeaANDtRZHA1xXzjyTjr5mmEZtXBSKuA3umcRPiWG");
+pub const PROGRAM_ID: Pubkey = multi_token_vesting::client::PROGRAM_ID;
 
 pub struct VestingDecoder;
 
@@ -21,40 +32,89 @@ impl InstructionDecoder<'_> for VestingDecoder {
             return None;
         }
 
-        let data = instruction.data.as_slice();
-        if data.is_empty() {
-            return None;
-        }
-
-        let discriminator = data[0];
-        let body = &data[1..];
-
-        let decoded = match discriminator {
-            // Initialize: 41 bytes — i64, i64, i64, i64, u64, u8
-            0 => {
-                if body.len() < 41 {
-                    return None;
-                }
+        // The program's own decoders read the data, so the layouts only live in the program.
+        let decoded = match instruction.data.split_first()? {
+            (Initialize::DISCRIMINATOR, body) => {
+                let data = InitializeInstructionData::try_from(body).ok()?;
                 VestingInstruction::Initialize(InitializeData {
-                    start_timestamp: i64::from_le_bytes(body[0..8].try_into().ok()?),
-                    cliff_duration: i64::from_le_bytes(body[8..16].try_into().ok()?),
-                    step_duration: i64::from_le_bytes(body[16..24].try_into().ok()?),
-                    total_duration: i64::from_le_bytes(body[24..32].try_into().ok()?),
-                    seed: u64::from_le_bytes(body[32..40].try_into().ok()?),
-                    bump: body[40],
+                    start_timestamp: data.start_timestamp,
+                    cliff_duration: data.cliff_duration,
+                    step_duration: data.step_duration,
+                    total_duration: data.total_duration,
+                    seed: data.seed,
+                    bump: data.bump,
+                    vesting_mode: data.vesting_mode as u8,
+                    time_base: data.time_base as u8,
+                    tranches: data.tranches.to_vec(),
                 })
             }
-            // AddParticipant: 8 bytes — u64
-            1 => {
-                if body.len() < 8 {
-                    return None;
-                }
+            (AddParticipant::DISCRIMINATOR, body) => {
+                let data = AddParticipantInstructionData::try_from(body).ok()?;
                 VestingInstruction::AddParticipant(AddParticipantData {
-                    token_allocation_amount: u64::from_le_bytes(body[0..8].try_into().ok()?),
+                    token_allocation_amount: data.token_allocation_amount,
+                    start: data.start,
+                })
+            }
+            (Claim::DISCRIMINATOR, body) => {
+                let data = ClaimInstructionData::try_from(body).ok()?;
+                VestingInstruction::Claim(ClaimData {
+                    close_when_finalized: data.close_when_finalized,
+                    requested_amount: data.amount,
+                    merkle_allocation: data.merkle_allocation,
+                })
+            }
+            (Revoke::DISCRIMINATOR, _) => VestingInstruction::Revoke,
+            (ProposeAuthority::DISCRIMINATOR, _) => VestingInstruction::ProposeAuthority,
+            (AcceptAuthority::DISCRIMINATOR, _) => VestingInstruction::AcceptAuthority,
+            (CancelAuthorityTransfer::DISCRIMINATOR, _) => {
+                VestingInstruction::CancelAuthorityTransfer
+            }
+            (CloseParticipant::DISCRIMINATOR, _) => VestingInstruction::CloseParticipant,
+            (CloseSchedule::DISCRIMINATOR, _) => VestingInstruction::CloseSchedule,
+            (BatchAddParticipant::DISCRIMINATOR, body) => {
+                let data = BatchAddParticipantInstructionData::try_from(body).ok()?;
+                VestingInstruction::BatchAddParticipant(BatchAddParticipantData {
+                    token_allocation_amounts: (0..data.participant_count())
+                        .map(|index| data.amount(index))
+                        .collect(),
+                })
+            }
+            (SetMerkleRoot::DISCRIMINATOR, body) => {
+                let data = SetMerkleRootInstructionData::try_from(body).ok()?;
+                VestingInstruction::SetMerkleRoot(SetMerkleRootData {
+                    merkle_root: data.merkle_root,
+                    merkle_total: data.merkle_total,
+                    merkle_claim_deadline: data.merkle_claim_deadline,
+                })
+            }
+            (TransferBeneficiary::DISCRIMINATOR, _) => VestingInstruction::TransferBeneficiary,
+            (CrankClaim::DISCRIMINATOR, _) => VestingInstruction::CrankClaim,
+            (Pause::DISCRIMINATOR, _) => VestingInstruction::Pause,
+            (Unpause::DISCRIMINATOR, _) => VestingInstruction::Unpause,
+            (AdjustAllocation::DISCRIMINATOR, body) => {
+                let data = AdjustAllocationInstructionData::try_from(body).ok()?;
+                VestingInstruction::AdjustAllocation(AdjustAllocationData {
+                    token_allocation_amount: data.token_allocation_amount,
+                })
+            }
+            (AddBasketMint::DISCRIMINATOR, _) => VestingInstruction::AddBasketMint,
+            (AddBasketAllocation::DISCRIMINATOR, body) => {
+                let data = AddBasketAllocationInstructionData::try_from(body).ok()?;
+                VestingInstruction::AddBasketAllocation(AddBasketAllocationData {
+                    token_allocation_amount: data.token_allocation_amount,
+                })
+            }
+            // Read-only, the status goes out as return data.
+            (GetVestingStatus::DISCRIMINATOR, _) => VestingInstruction::GetVestingStatus,
+            (SetFunder::DISCRIMINATOR, _) => VestingInstruction::SetFunder,
+            (SetPauser::DISCRIMINATOR, _) => VestingInstruction::SetPauser,
+            (MigrateAccount::DISCRIMINATOR, _) => VestingInstruction::MigrateAccount,
+            (Accelerate::DISCRIMINATOR, body) => {
+                let data = AccelerateInstructionData::try_from(body).ok()?;
+                VestingInstruction::Accelerate(AccelerateData {
+                    accelerated_bps: data.accelerated_bps,
                 })
             }
-            // Claim: no data
-            2 => VestingInstruction::Claim,
             _ => return None,
         };
 
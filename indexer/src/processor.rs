@@ -2,19 +2,25 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use carbon_core::{
-    deserialize::ArrangeAccounts,
     error::CarbonResult,
     instruction::{DecodedInstruction, InstructionMetadata, NestedInstructions},
     metrics::MetricsCollection,
     processor::Processor,
 };
+use multi_token_vesting::{CrankClaim, InstructionAccount};
+use solana_pubkey::Pubkey;
 use sqlx::PgPool;
 
 use crate::decoder::{
-    accounts::ClaimArrange,
-    instructions::{AddParticipantData, InitializeData, VestingInstruction},
+    events::VestingEvent,
+    instructions::{InitializeData, VestingInstruction},
 };
 
+/// Event payloads carry raw 32-byte keys.
+fn address(bytes: [u8; 32]) -> String {
+    Pubkey::new_from_array(bytes).to_string()
+}
+
 pub struct VestingProcessor {
     pub pool: PgPool,
 }
@@ -30,25 +36,77 @@ impl Processor for VestingProcessor {
 
     async fn process(
         &mut self,
-        (metadata, instruction, nested, _raw_ix): Self::InputType,
+        (metadata, instruction, _nested, _raw_ix): Self::InputType,
         _metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
         let sig = metadata.transaction_metadata.signature.to_string();
         let slot = metadata.transaction_metadata.slot as i64;
+        // Every state change is taken from the program's own events rather than being recomputed
+        // here, instruction data and accounts only fill in what the events don't carry.
+        let events = metadata.decode_log_events::<VestingEvent>();
 
         match &instruction.data {
             VestingInstruction::Initialize(data) => {
-                self.handle_initialize(data, &instruction.accounts, &sig, slot)
-                    .await
+                self.handle_initialize(data, &events, &sig, slot).await
             }
             VestingInstruction::AddParticipant(data) => {
-                self.handle_add_participant(data, &instruction.accounts, &sig, slot)
-                    .await
+                self.handle_participants_added(&events, &sig, slot).await;
+                if let Some(start) = data.start {
+                    self.handle_participant_start(&events, start, &sig).await;
+                }
+            }
+            VestingInstruction::BatchAddParticipant(_) => {
+                self.handle_participants_added(&events, &sig, slot).await
             }
-            VestingInstruction::Claim => {
-                self.handle_claim(&instruction.accounts, &nested, &sig, slot)
+            VestingInstruction::Claim(data) => {
+                self.handle_claim(data.requested_amount, None, &events, &sig, slot)
+                    .await;
+                // A finalizing claim may close the participant account on the way.
+                self.handle_participants_closed(&events, &sig, slot).await
+            }
+            VestingInstruction::Revoke => self.handle_revoke(&events, &sig, slot).await,
+            VestingInstruction::CrankClaim => {
+                self.handle_crank_claim(&instruction.accounts, &events, &sig, slot)
                     .await
             }
+            VestingInstruction::ProposeAuthority
+            | VestingInstruction::AcceptAuthority
+            | VestingInstruction::CancelAuthorityTransfer => {
+                self.handle_authority_transfer(&events, &sig, slot).await
+            }
+            VestingInstruction::CloseParticipant => {
+                self.handle_participants_closed(&events, &sig, slot).await
+            }
+            VestingInstruction::CloseSchedule => {
+                self.handle_close_schedule(&events, &sig, slot).await
+            }
+            VestingInstruction::SetMerkleRoot(_) => {
+                self.handle_set_merkle_root(&events, &sig).await
+            }
+            VestingInstruction::TransferBeneficiary => {
+                self.handle_transfer_beneficiary(&events, &sig, slot).await
+            }
+            VestingInstruction::Pause | VestingInstruction::Unpause => {
+                self.handle_set_paused(&events, &sig, slot).await
+            }
+            VestingInstruction::AdjustAllocation(_) => {
+                self.handle_adjust_allocation(&events, &sig, slot).await
+            }
+            VestingInstruction::AddBasketMint => {
+                self.handle_add_basket_mint(&events, &sig, slot).await
+            }
+            VestingInstruction::AddBasketAllocation(_) => {
+                self.handle_add_basket_allocation(&events, &sig, slot).await
+            }
+            // Read-only, the status goes out as return data and nothing changes.
+            VestingInstruction::GetVestingStatus => {}
+            VestingInstruction::SetFunder | VestingInstruction::SetPauser => {
+                self.handle_role_updates(&events, &sig, slot).await
+            }
+            VestingInstruction::MigrateAccount => self.handle_migrate_account(&events, &sig),
+            VestingInstruction::Accelerate(_) => {
+                self.handle_accelerate(&events, &sig, slot).await
+            }
         }
 
         Ok(())
@@ -56,135 +114,831 @@ impl Processor for VestingProcessor {
 }
 
 impl VestingProcessor {
+    /// The bump and the tranches are only in the instruction data, everything else comes from the event.
     async fn handle_initialize(
         &self,
         data: &InitializeData,
-        accounts: &[solana_instruction::AccountMeta],
+        events: &[VestingEvent],
         sig: &str,
         slot: i64,
     ) {
-        let Some(accs) = InitializeData::arrange_accounts(accounts) else {
-            log::warn!("Initialize: failed to arrange accounts, tx={sig}");
+        let Some(created) = events.iter().find_map(|event| match event {
+            VestingEvent::ScheduleCreated(created) => Some(created),
+            _ => None,
+        }) else {
+            log::warn!("Initialize: no ScheduleCreated event, tx={sig}");
             return;
         };
+        let schedule = address(created.schedule);
+        let creator = address(created.creator);
 
         let result = sqlx::query(
             "INSERT INTO schedules (
                 schedule_address, mint, authority, seed,
                 start_timestamp, cliff_duration, step_duration, total_duration,
-                bump, vault, tx_signature, slot
-            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12)
+                bump, vault, tx_signature, slot, vesting_mode, creator, funder, time_base
+            ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16)
             ON CONFLICT DO NOTHING",
         )
-        .bind(accs.schedule.to_string())
-        .bind(accs.mint.to_string())
-        .bind(accs.authority.to_string())
-        .bind(data.seed as i64)
-        .bind(data.start_timestamp)
-        .bind(data.cliff_duration)
-        .bind(data.step_duration)
-        .bind(data.total_duration)
+        .bind(&schedule)
+        .bind(address(created.mint))
+        .bind(&creator)
+        .bind(created.seed as i64)
+        .bind(created.start)
+        .bind(created.cliff_duration)
+        .bind(created.step_duration)
+        .bind(created.total_duration)
         .bind(data.bump as i16)
-        .bind(accs.vault.to_string())
+        .bind(address(created.vault))
         .bind(sig)
         .bind(slot)
+        .bind(created.vesting_mode as i16)
+        .bind(&creator)
+        // the creating authority funds until SetFunder hands the role on
+        .bind(&creator)
+        .bind(created.time_base as i16)
         .execute(&self.pool)
         .await;
 
         match result {
-            Ok(_) => log::info!("Initialize: schedule={}, tx={sig}", accs.schedule),
+            Ok(_) => log::info!("Initialize: schedule={schedule}, tx={sig}"),
             Err(e) => log::error!("Initialize insert failed: {e}, tx={sig}"),
         }
+
+        for (index, tranche) in data.tranches.iter().enumerate() {
+            let result = sqlx::query(
+                "INSERT INTO schedule_tranches (
+                    schedule_address, tranche_index, offset_seconds, cumulative_bps
+                ) VALUES ($1,$2,$3,$4)
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(&schedule)
+            .bind(index as i16)
+            .bind(tranche.offset())
+            .bind(tranche.cumulative_bps() as i32)
+            .execute(&self.pool)
+            .await;
+
+            if let Err(e) = result {
+                log::error!("Initialize tranche insert failed: {e}, tx={sig}");
+            }
+        }
     }
 
-    async fn handle_add_participant(
+    /// AddParticipant, BatchAddParticipant and a merkle recipient's first claim all create
+    /// participants, the event carries what actually landed in the vault after any transfer fee.
+    async fn handle_participants_added(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::ParticipantAdded(added) = event else {
+                continue;
+            };
+            let vested_participant = address(added.vested_participant);
+
+            let result = sqlx::query(
+                "INSERT INTO participants (
+                    participant_pda, schedule_address, participant_wallet,
+                    allocated_amount, tx_signature, slot, from_merkle_leaf
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7)
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(&vested_participant)
+            .bind(address(added.schedule))
+            .bind(address(added.participant_wallet))
+            .bind(added.allocated_amount as i64)
+            .bind(sig)
+            .bind(slot)
+            .bind(added.from_merkle_leaf != 0)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(done) => {
+                    log::info!("ParticipantAdded: pda={vested_participant}, tx={sig}");
+                    // a claimed leaf leaves the merkle total, only once even if the transaction is replayed
+                    if added.from_merkle_leaf != 0 && done.rows_affected() != 0 {
+                        let result = sqlx::query(
+                            "UPDATE schedules SET merkle_total = merkle_total - $1 WHERE schedule_address = $2",
+                        )
+                        .bind(added.allocated_amount as i64)
+                        .bind(address(added.schedule))
+                        .execute(&self.pool)
+                        .await;
+
+                        if let Err(e) = result {
+                            log::error!("Merkle total update failed: {e}, tx={sig}");
+                        }
+                    }
+                }
+                Err(e) => log::error!("ParticipantAdded insert failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    /// The participant's own start is only in the instruction data, the schedule's durations run from it.
+    async fn handle_participant_start(&self, events: &[VestingEvent], start: i64, sig: &str) {
+        for event in events {
+            let VestingEvent::ParticipantAdded(added) = event else {
+                continue;
+            };
+            let vested_participant = address(added.vested_participant);
+
+            let result = sqlx::query(
+                "UPDATE participants SET start_timestamp = $1 WHERE participant_pda = $2",
+            )
+            .bind(start)
+            .bind(&vested_participant)
+            .execute(&self.pool)
+            .await;
+
+            if let Err(e) = result {
+                log::error!("Participant start update failed: {e}, tx={sig}");
+            }
+        }
+    }
+
+    async fn handle_claim(
         &self,
-        data: &AddParticipantData,
-        accounts: &[solana_instruction::AccountMeta],
+        requested_amount: Option<u64>,
+        keeper: Option<Pubkey>,
+        events: &[VestingEvent],
         sig: &str,
         slot: i64,
     ) {
-        let Some(accs) = AddParticipantData::arrange_accounts(accounts) else {
-            log::warn!("AddParticipant: failed to arrange accounts, tx={sig}");
-            return;
-        };
+        // A merkle recipient's participant account is created by its first claim.
+        self.handle_participants_added(events, sig, slot).await;
 
-        let result = sqlx::query(
-            "INSERT INTO participants (
-                participant_pda, schedule_address, participant_wallet,
-                allocated_amount, tx_signature, slot
-            ) VALUES ($1,$2,$3,$4,$5,$6)
-            ON CONFLICT DO NOTHING",
-        )
-        .bind(accs.vested_participant.to_string())
-        .bind(accs.schedule.to_string())
-        .bind(accs.participant_wallet.to_string())
-        .bind(data.token_allocation_amount as i64)
-        .bind(sig)
-        .bind(slot)
-        .execute(&self.pool)
-        .await;
+        // One event per mint paid out, the primary mint first and each basket mint after it. A mint
+        // with nothing left to pay has no event, a requested amount always makes the primary mint pay.
+        let claims = events.iter().filter_map(|event| match event {
+            VestingEvent::Claimed(claimed) => Some(claimed),
+            _ => None,
+        });
 
-        match result {
-            Ok(_) => log::info!(
-                "AddParticipant: pda={}, wallet={}, tx={sig}",
-                accs.vested_participant,
-                accs.participant_wallet
-            ),
-            Err(e) => log::error!("AddParticipant insert failed: {e}, tx={sig}"),
+        for (index, claimed) in claims.enumerate() {
+            let vested_participant = address(claimed.vested_participant);
+            let mint = address(claimed.mint);
+            let amount = claimed.amount;
+
+            let result = sqlx::query(
+                "INSERT INTO claims (
+                    participant_pda, schedule_address, participant_wallet, claimed_amount,
+                    tx_signature, slot, vested_bps, keeper, requested_amount, mint, claimed_at
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
+                ON CONFLICT (tx_signature, participant_pda, mint) DO NOTHING",
+            )
+            .bind(&vested_participant)
+            .bind(address(claimed.schedule))
+            .bind(address(claimed.participant_wallet))
+            .bind(amount as i64)
+            .bind(sig)
+            .bind(slot)
+            .bind(claimed.vested_bps)
+            .bind(keeper.map(|keeper| keeper.to_string()))
+            // A requested amount only ever applies to the primary mint.
+            .bind(requested_amount.filter(|_| index == 0).map(|amount| amount as i64))
+            .bind(&mint)
+            .bind(claimed.timestamp)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!(
+                    "Claim: pda={vested_participant}, mint={mint}, amount={amount}, tx={sig}"
+                ),
+                Err(e) => log::error!("Claim insert failed: {e}, tx={sig}"),
+            }
         }
     }
 
-    async fn handle_claim(
+    /// One event per mint, the primary mint first and each basket mint after it, each keeping
+    /// only what had vested at termination.
+    async fn handle_revoke(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        let revocations = events.iter().filter_map(|event| match event {
+            VestingEvent::ParticipantRevoked(revoked) => Some(revoked),
+            _ => None,
+        });
+
+        for (index, revoked) in revocations.enumerate() {
+            let vested_participant = address(revoked.vested_participant);
+            let mint = address(revoked.mint);
+            let clawback_amount = revoked.clawback_amount;
+
+            let result = sqlx::query(
+                "INSERT INTO revocations (
+                    participant_pda, schedule_address, mint, clawback_amount,
+                    allocated_amount, terminated_at, tx_signature, slot
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
+                ON CONFLICT (tx_signature, participant_pda, mint) DO NOTHING",
+            )
+            .bind(&vested_participant)
+            .bind(address(revoked.schedule))
+            .bind(&mint)
+            .bind(clawback_amount as i64)
+            .bind(revoked.allocated_amount as i64)
+            .bind(revoked.terminated_at)
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(r) if r.rows_affected() == 0 => continue,
+                Ok(_) => {}
+                Err(e) => {
+                    log::error!("Revoke insert failed: {e}, tx={sig}");
+                    continue;
+                }
+            }
+
+            let result = if index == 0 {
+                sqlx::query(
+                    "UPDATE participants SET allocated_amount = $2, terminated_at = $3
+                    WHERE participant_pda = $1",
+                )
+                .bind(&vested_participant)
+                .bind(revoked.allocated_amount as i64)
+                .bind(revoked.terminated_at)
+                .execute(&self.pool)
+                .await
+            } else {
+                sqlx::query(
+                    "UPDATE participant_basket_allocations SET allocated_amount = $3
+                    WHERE participant_pda = $1 AND mint = $2",
+                )
+                .bind(&vested_participant)
+                .bind(&mint)
+                .bind(revoked.allocated_amount as i64)
+                .execute(&self.pool)
+                .await
+            };
+
+            match result {
+                Ok(_) => log::info!(
+                    "Revoke: pda={vested_participant}, mint={mint}, clawback={clawback_amount}, tx={sig}"
+                ),
+                Err(e) => log::error!("Revoke update failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    /// CrankClaim takes Claim's accounts behind the keeper, and never closes or creates a participant.
+    async fn handle_crank_claim(
         &self,
         accounts: &[solana_instruction::AccountMeta],
-        nested: &NestedInstructions,
+        events: &[VestingEvent],
         sig: &str,
         slot: i64,
     ) {
-        let Some(accs) = ClaimArrange::arrange_accounts(accounts) else {
-            log::warn!("Claim: failed to arrange accounts, tx={sig}");
+        let Some(keeper) = InstructionAccount::position(&CrankClaim::ACCOUNTS, "keeper")
+            .and_then(|index| accounts.get(index))
+        else {
+            log::warn!("CrankClaim: failed to arrange accounts, tx={sig}");
             return;
         };
 
-        // Extract the claimed amount from the inner SPL Token Transfer CPI.
-        // The transfer instruction data is: 1-byte discriminator (3 = Transfer) + 8-byte u64 amount.
-        let claimed_amount = nested
-            .iter()
-            .find_map(|inner| {
-                let ix = &inner.instruction;
-                if ix.data.len() == 9 && ix.data[0] == 3 {
-                    Some(u64::from_le_bytes(
-                        ix.data[1..9].try_into().unwrap_or_default(),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0);
+        self.handle_claim(None, Some(keeper.pubkey), events, sig, slot)
+            .await
+    }
 
-        let result = sqlx::query(
-            "INSERT INTO claims (
-                participant_pda, schedule_address, participant_wallet,
-                claimed_amount, tx_signature, slot
+    async fn insert_authority_history(
+        &self,
+        schedule: &str,
+        action: &str,
+        authority: &str,
+        pending_authority: Option<&str>,
+        sig: &str,
+        slot: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO authority_history (
+                schedule_address, action, authority, pending_authority, tx_signature, slot
             ) VALUES ($1,$2,$3,$4,$5,$6)
-            ON CONFLICT (tx_signature) DO NOTHING",
+            ON CONFLICT (tx_signature, schedule_address, action) DO NOTHING",
         )
-        .bind(accs.vested_participant.to_string())
-        .bind(accs.schedule.to_string())
-        .bind(accs.participant_wallet.to_string())
-        .bind(claimed_amount as i64)
+        .bind(schedule)
+        .bind(action)
+        .bind(authority)
+        .bind(pending_authority)
         .bind(sig)
         .bind(slot)
         .execute(&self.pool)
-        .await;
+        .await
+        .map(|_| ())
+    }
 
-        match result {
-            Ok(_) => log::info!(
-                "Claim: pda={}, amount={claimed_amount}, tx={sig}",
-                accs.vested_participant
-            ),
-            Err(e) => log::error!("Claim insert failed: {e}, tx={sig}"),
+    /// ProposeAuthority, AcceptAuthority and CancelAuthorityTransfer each log one event.
+    async fn handle_authority_transfer(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let (schedule, action, authority, pending_authority) = match event {
+                VestingEvent::AuthorityProposed(proposed) => (
+                    address(proposed.schedule),
+                    "propose",
+                    address(proposed.authority),
+                    Some(address(proposed.pending_authority)),
+                ),
+                VestingEvent::AuthorityAccepted(accepted) => (
+                    address(accepted.schedule),
+                    "accept",
+                    address(accepted.authority),
+                    None,
+                ),
+                VestingEvent::AuthorityTransferCancelled(cancelled) => (
+                    address(cancelled.schedule),
+                    "cancel",
+                    address(cancelled.authority),
+                    None,
+                ),
+                _ => continue,
+            };
+
+            let result = self
+                .insert_authority_history(
+                    &schedule,
+                    action,
+                    &authority,
+                    pending_authority.as_deref(),
+                    sig,
+                    slot,
+                )
+                .await;
+            if let Err(e) = result {
+                log::error!("Authority {action} insert failed: {e}, tx={sig}");
+                continue;
+            }
+
+            if action == "accept" {
+                let result =
+                    sqlx::query("UPDATE schedules SET authority = $2 WHERE schedule_address = $1")
+                        .bind(&schedule)
+                        .bind(&authority)
+                        .execute(&self.pool)
+                        .await;
+
+                if let Err(e) = result {
+                    log::error!("AcceptAuthority update failed: {e}, tx={sig}");
+                    continue;
+                }
+            }
+
+            log::info!("Authority {action}: schedule={schedule}, authority={authority}, tx={sig}");
+        }
+    }
+
+    /// CloseParticipant and a claim that closes the account it finalizes, the row stays for its history.
+    async fn handle_participants_closed(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::ParticipantClosed(closed) = event else {
+                continue;
+            };
+            let vested_participant = address(closed.vested_participant);
+
+            let result = sqlx::query(
+                "UPDATE participants SET closed_tx_signature = $2, closed_slot = $3
+                WHERE participant_pda = $1",
+            )
+            .bind(&vested_participant)
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!("ParticipantClosed: pda={vested_participant}, tx={sig}"),
+                Err(e) => log::error!("ParticipantClosed update failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    async fn handle_close_schedule(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::ScheduleClosed(closed) = event else {
+                continue;
+            };
+            let schedule = address(closed.schedule);
+
+            let result = sqlx::query(
+                "UPDATE schedules SET closed_tx_signature = $2, closed_slot = $3
+                WHERE schedule_address = $1",
+            )
+            .bind(&schedule)
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!("ScheduleClosed: schedule={schedule}, tx={sig}"),
+                Err(e) => log::error!("ScheduleClosed update failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    async fn handle_set_merkle_root(&self, events: &[VestingEvent], sig: &str) {
+        for event in events {
+            let VestingEvent::MerkleRootSet(set) = event else {
+                continue;
+            };
+            let schedule = address(set.schedule);
+            let merkle_total = set.merkle_total;
+
+            // A zero root clears the distribution, or sweeps its unclaimed leaves after the deadline.
+            let has_root = set.merkle_root != [0u8; 32];
+            let merkle_root = has_root.then(|| {
+                set.merkle_root
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>()
+            });
+            let merkle_claim_deadline = has_root.then_some(set.merkle_claim_deadline);
+
+            let result = sqlx::query(
+                "UPDATE schedules SET merkle_root = $1, merkle_total = $2, merkle_claim_deadline = $3
+                WHERE schedule_address = $4",
+            )
+            .bind(merkle_root)
+            .bind(merkle_total as i64)
+            .bind(merkle_claim_deadline)
+            .bind(&schedule)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!(
+                    "SetMerkleRoot: schedule={schedule}, total={merkle_total}, tx={sig}"
+                ),
+                Err(e) => log::error!("SetMerkleRoot update failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    async fn handle_transfer_beneficiary(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::BeneficiaryTransferred(transferred) = event else {
+                continue;
+            };
+            let vested_participant = address(transferred.vested_participant);
+            let new_vested_participant = address(transferred.new_vested_participant);
+
+            // The grant keeps its allocation, start, acceleration and termination under the new PDA,
+            // claims made before the move stay on the old one.
+            let result = sqlx::query(
+                "INSERT INTO participants (
+                    participant_pda, schedule_address, participant_wallet, allocated_amount,
+                    tx_signature, slot, from_merkle_leaf, start_timestamp, accelerated_bps,
+                    terminated_at
+                )
+                SELECT $1, schedule_address, $2, allocated_amount, $3, $4, from_merkle_leaf,
+                    start_timestamp, accelerated_bps, terminated_at
+                FROM participants WHERE participant_pda = $5
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(&new_vested_participant)
+            .bind(address(transferred.new_wallet))
+            .bind(sig)
+            .bind(slot)
+            .bind(&vested_participant)
+            .execute(&self.pool)
+            .await;
+
+            if let Err(e) = result {
+                log::error!("TransferBeneficiary participant insert failed: {e}, tx={sig}");
+                continue;
+            }
+
+            let result = sqlx::query(
+                "INSERT INTO participant_basket_allocations (
+                    participant_pda, mint, schedule_address, allocated_amount, tx_signature, slot
+                )
+                SELECT $1, mint, schedule_address, allocated_amount, $2, $3
+                FROM participant_basket_allocations WHERE participant_pda = $4
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(&new_vested_participant)
+            .bind(sig)
+            .bind(slot)
+            .bind(&vested_participant)
+            .execute(&self.pool)
+            .await;
+
+            if let Err(e) = result {
+                log::error!("TransferBeneficiary basket allocation insert failed: {e}, tx={sig}");
+                continue;
+            }
+
+            let result = sqlx::query(
+                "UPDATE participants SET transferred_to = $1 WHERE participant_pda = $2",
+            )
+            .bind(&new_vested_participant)
+            .bind(&vested_participant)
+            .execute(&self.pool)
+            .await;
+
+            if let Err(e) = result {
+                log::error!("TransferBeneficiary participant update failed: {e}, tx={sig}");
+                continue;
+            }
+
+            let result = sqlx::query(
+                "INSERT INTO beneficiary_transfers (
+                    schedule_address, old_participant_pda, old_wallet,
+                    new_participant_pda, new_wallet, signer, recovery, tx_signature, slot
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9)
+                ON CONFLICT (tx_signature) DO NOTHING",
+            )
+            .bind(address(transferred.schedule))
+            .bind(&vested_participant)
+            .bind(address(transferred.participant_wallet))
+            .bind(&new_vested_participant)
+            .bind(address(transferred.new_wallet))
+            .bind(address(transferred.signer))
+            .bind(transferred.signer != transferred.participant_wallet)
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!(
+                    "TransferBeneficiary: {vested_participant} -> {new_vested_participant}, tx={sig}"
+                ),
+                Err(e) => log::error!("TransferBeneficiary insert failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    async fn handle_set_paused(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::PauseUpdated(updated) = event else {
+                continue;
+            };
+            let schedule = address(updated.schedule);
+            let paused = updated.paused != 0;
+            let name = if paused { "Pause" } else { "Unpause" };
+
+            let result =
+                sqlx::query("UPDATE schedules SET paused = $2 WHERE schedule_address = $1")
+                    .bind(&schedule)
+                    .bind(paused)
+                    .execute(&self.pool)
+                    .await;
+
+            if let Err(e) = result {
+                log::error!("{name} update failed: {e}, tx={sig}");
+                continue;
+            }
+
+            let result = sqlx::query(
+                "INSERT INTO pause_events (schedule_address, action, authority, tx_signature, slot)
+                VALUES ($1,$2,$3,$4,$5)
+                ON CONFLICT (tx_signature, schedule_address, action) DO NOTHING",
+            )
+            .bind(&schedule)
+            .bind(if paused { "pause" } else { "unpause" })
+            .bind(address(updated.authority))
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!("{name}: schedule={schedule}, tx={sig}"),
+                Err(e) => log::error!("{name} insert failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    async fn handle_adjust_allocation(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::AllocationAdjusted(adjusted) = event else {
+                continue;
+            };
+            let vested_participant = address(adjusted.vested_participant);
+            let allocated_amount = adjusted.allocated_amount;
+
+            let result = sqlx::query(
+                "INSERT INTO allocation_adjustments (
+                    participant_pda, schedule_address, previous_amount, new_amount,
+                    authority, tx_signature, slot
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7)
+                ON CONFLICT (tx_signature, participant_pda) DO NOTHING",
+            )
+            .bind(&vested_participant)
+            .bind(address(adjusted.schedule))
+            .bind(adjusted.previous_amount as i64)
+            .bind(allocated_amount as i64)
+            .bind(address(adjusted.authority))
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(r) if r.rows_affected() == 0 => continue,
+                Ok(_) => {}
+                Err(e) => {
+                    log::error!("AdjustAllocation insert failed: {e}, tx={sig}");
+                    continue;
+                }
+            }
+
+            let result = sqlx::query(
+                "UPDATE participants SET allocated_amount = $2 WHERE participant_pda = $1",
+            )
+            .bind(&vested_participant)
+            .bind(allocated_amount as i64)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!(
+                    "AdjustAllocation: pda={vested_participant}, amount={allocated_amount}, tx={sig}"
+                ),
+                Err(e) => log::error!("AdjustAllocation update failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    async fn handle_add_basket_mint(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::BasketMintAdded(added) = event else {
+                continue;
+            };
+            let schedule = address(added.schedule);
+            let mint = address(added.mint);
+
+            let result = sqlx::query(
+                "INSERT INTO schedule_basket_mints (schedule_address, mint, vault, tx_signature, slot)
+                VALUES ($1,$2,$3,$4,$5)
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(&schedule)
+            .bind(&mint)
+            .bind(address(added.vault))
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!("AddBasketMint: schedule={schedule}, mint={mint}, tx={sig}"),
+                Err(e) => log::error!("AddBasketMint insert failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    async fn handle_add_basket_allocation(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::BasketAllocationAdded(added) = event else {
+                continue;
+            };
+            let vested_participant = address(added.vested_participant);
+            let mint = address(added.mint);
+
+            let result = sqlx::query(
+                "INSERT INTO participant_basket_allocations (
+                    participant_pda, mint, schedule_address, allocated_amount, tx_signature, slot
+                ) VALUES ($1,$2,$3,$4,$5,$6)
+                ON CONFLICT DO NOTHING",
+            )
+            .bind(&vested_participant)
+            .bind(&mint)
+            .bind(address(added.schedule))
+            .bind(added.allocated_amount as i64)
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!(
+                    "AddBasketAllocation: pda={vested_participant}, mint={mint}, tx={sig}"
+                ),
+                Err(e) => log::error!("AddBasketAllocation insert failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    /// MigrateAccount only rewrites the account layout, none of the indexed values change.
+    fn handle_migrate_account(&self, events: &[VestingEvent], sig: &str) {
+        for event in events {
+            if let VestingEvent::AccountMigrated(migrated) = event {
+                log::info!(
+                    "MigrateAccount: account={}, version {} -> {}, tx={sig}",
+                    address(migrated.account),
+                    migrated.from_version,
+                    migrated.to_version,
+                );
+            }
+        }
+    }
+
+    /// Each trigger is kept as its own row, the participant row carries the running total.
+    async fn handle_accelerate(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let VestingEvent::ParticipantAccelerated(accelerated) = event else {
+                continue;
+            };
+            let vested_participant = address(accelerated.vested_participant);
+            let accelerated_bps = accelerated.accelerated_bps;
+
+            let result = sqlx::query(
+                "INSERT INTO accelerations (
+                    participant_pda, schedule_address, added_bps, accelerated_bps,
+                    authority, accelerated_at, tx_signature, slot
+                ) VALUES ($1,$2,$3,$4,$5,$6,$7,$8)
+                ON CONFLICT (tx_signature, participant_pda) DO NOTHING",
+            )
+            .bind(&vested_participant)
+            .bind(address(accelerated.schedule))
+            .bind(accelerated.added_bps as i32)
+            .bind(accelerated_bps as i32)
+            .bind(address(accelerated.authority))
+            .bind(accelerated.accelerated_at)
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(r) if r.rows_affected() == 0 => continue,
+                Ok(_) => {}
+                Err(e) => {
+                    log::error!("Accelerate insert failed: {e}, tx={sig}");
+                    continue;
+                }
+            }
+
+            let result = sqlx::query(
+                "UPDATE participants SET accelerated_bps = $2 WHERE participant_pda = $1",
+            )
+            .bind(&vested_participant)
+            .bind(accelerated_bps as i32)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!(
+                    "Accelerate: pda={vested_participant}, bps={accelerated_bps}, tx={sig}"
+                ),
+                Err(e) => log::error!("Accelerate update failed: {e}, tx={sig}"),
+            }
+        }
+    }
+
+    /// SetFunder and SetPauser, a removed pauser is stored as NULL.
+    async fn handle_role_updates(&self, events: &[VestingEvent], sig: &str, slot: i64) {
+        for event in events {
+            let (schedule, role, signer, holder) = match event {
+                VestingEvent::FunderUpdated(updated) => (
+                    address(updated.schedule),
+                    "funder",
+                    address(updated.previous_funder),
+                    Some(address(updated.funder)),
+                ),
+                VestingEvent::PauserUpdated(updated) => (
+                    address(updated.schedule),
+                    "pauser",
+                    address(updated.authority),
+                    (updated.pauser != [0u8; 32]).then(|| address(updated.pauser)),
+                ),
+                _ => continue,
+            };
+
+            let result = sqlx::query(
+                "INSERT INTO role_changes (schedule_address, role, signer, holder, tx_signature, slot)
+                VALUES ($1,$2,$3,$4,$5,$6)
+                ON CONFLICT (tx_signature, schedule_address, role) DO NOTHING",
+            )
+            .bind(&schedule)
+            .bind(role)
+            .bind(&signer)
+            .bind(&holder)
+            .bind(sig)
+            .bind(slot)
+            .execute(&self.pool)
+            .await;
+
+            if let Err(e) = result {
+                log::error!("Set {role} insert failed: {e}, tx={sig}");
+                continue;
+            }
+
+            // role is one of two literals, never user input
+            let result = sqlx::query(&format!(
+                "UPDATE schedules SET {role} = $2 WHERE schedule_address = $1"
+            ))
+            .bind(&schedule)
+            .bind(&holder)
+            .execute(&self.pool)
+            .await;
+
+            match result {
+                Ok(_) => log::info!(
+                    "Set {role}: schedule={schedule}, holder={}, tx={sig}",
+                    holder.as_deref().unwrap_or("none")
+                ),
+                Err(e) => log::error!("Set {role} update failed: {e}, tx={sig}"),
+            }
         }
     }
 }
@@ -0,0 +1,53 @@
+//! Off-chain instruction builders, shared by the indexer's seeder, the tests and any Rust client.
+//!
+//! Accounts are passed by name and laid out by the instruction's account table, and data comes
+//! from the encoders next to the program's own decoders, so a client can't fall out of step with
+//! what the program reads.
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use crate::{InstructionAccount, ID};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(ID);
+
+/// `discriminator` followed by `data`, with one meta per row of `accounts` taking its key from
+/// `keys` by name and its flags from the table.
+///
+/// Optional signers go in as non-signers, `set_signer` marks them when they sign. Remaining
+/// accounts are pushed onto the returned instruction.
+///
+/// Panics when `keys` misses an account of the table or names one it doesn't have.
+pub fn instruction(
+    discriminator: u8,
+    accounts: &[InstructionAccount],
+    keys: &[(&str, Pubkey)],
+    data: &[u8],
+) -> Instruction {
+    if let Some((name, _)) = keys.iter().find(|(name, _)| InstructionAccount::position(accounts, name).is_none()) {
+        panic!("no account named {name} in the instruction's account table");
+    }
+
+    let accounts = accounts
+        .iter()
+        .map(|account| {
+            let Some((_, key)) = keys.iter().find(|(name, _)| *name == account.name) else {
+                panic!("missing key for account {}", account.name);
+            };
+            AccountMeta { pubkey: *key, is_signer: account.signer, is_writable: account.writable }
+        })
+        .collect();
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: [&[discriminator], data].concat(),
+    }
+}
+
+/// Sets the signer flag on every meta of `key`, for optional signers that do sign.
+pub fn set_signer(instruction: &mut Instruction, key: &Pubkey, signer: bool) {
+    for meta in instruction.accounts.iter_mut().filter(|meta| meta.pubkey == *key) {
+        meta.is_signer = signer;
+    }
+}
@@ -1,8 +1,21 @@
 use pinocchio::program_error::ProgramError;
 use thiserror::Error;
 
-#[derive(Clone, Debug, Eq, PartialEq, Error)]
-pub enum PinocchioError {
+// declares the enum and PinocchioError::ALL from one list, so the IDL can't miss a variant
+macro_rules! pinocchio_errors {
+    ($(#[error($message:literal)] $variant:ident,)*) => {
+        #[derive(Clone, Debug, Eq, PartialEq, Error)]
+        pub enum PinocchioError {
+            $(#[error($message)] $variant,)*
+        }
+        impl PinocchioError {
+            /// Every variant in code order.
+            pub const ALL: &'static [Self] = &[$(Self::$variant,)*];
+        }
+    };
+}
+
+pinocchio_errors! {
     #[error("Invalid signer")]
     InvalidSigner,
     #[error("Invalid address")]
@@ -23,9 +36,64 @@ pub enum PinocchioError {
     ClaimAmountInvalid,
     #[error("Claim amount overflowes allocated amount")]
     ClaimAmountOverflow,
+    #[error("Participant already terminated")]
+    ParticipantTerminated,
+    #[error("No pending authority")]
+    NoPendingAuthority,
+    #[error("Claim not finalized")]
+    ClaimNotFinalized,
+    #[error("Schedule has outstanding allocations")]
+    ScheduleHasOutstandingAllocations,
+    #[error("Vesting mode invalid")]
+    VestingModeInvalid,
+    #[error("Tranche table invalid")]
+    TrancheTableInvalid,
+    #[error("Merkle proof invalid")]
+    MerkleProofInvalid,
+    #[error("Merkle root locked after cliff")]
+    MerkleRootLocked,
+    #[error("Participant not closable")]
+    ParticipantNotClosable,
+    #[error("Requested claim amount exceeds vested unclaimed amount")]
+    ClaimAmountUnavailable,
+    #[error("Schedule paused")]
+    SchedulePaused,
+    #[error("Schedule not paused")]
+    ScheduleNotPaused,
+    #[error("Allocation below claimed amount")]
+    AllocationBelowClaimed,
+    #[error("Basket mint invalid")]
+    BasketMintInvalid,
+    #[error("Basket full")]
+    BasketFull,
+    #[error("Basket locked")]
+    BasketLocked,
+    #[error("Account layout outdated")]
+    AccountOutdated,
+    #[error("Account layout up to date")]
+    AccountUpToDate,
+    #[error("Participant already fully accelerated")]
+    ParticipantFullyAccelerated,
+    #[error("Time base invalid")]
+    TimeBaseInvalid,
+    #[error("Merkle claim deadline before the end of vesting")]
+    MerkleClaimDeadlineInvalid,
+    #[error("Merkle claim deadline passed")]
+    MerkleClaimExpired,
 }
 impl From<PinocchioError> for ProgramError {
     fn from(value: PinocchioError) -> Self {
         ProgramError::Custom(value as u32)
     }
-}
\ No newline at end of file
+}
+// batch instructions fail with this offset plus the index of the offending entry, keep it clear of PinocchioError codes
+pub const BATCH_ERROR_OFFSET: u32 = 1_000;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Error)]
+#[error("Batch entry {0} invalid")]
+pub struct BatchEntryError(pub usize);
+impl From<BatchEntryError> for ProgramError {
+    fn from(value: BatchEntryError) -> Self {
+        ProgramError::Custom(BATCH_ERROR_OFFSET + value.0 as u32)
+    }
+}
@@ -0,0 +1,284 @@
+use core::mem::size_of;
+
+use pinocchio::{log::sol_log_data, pubkey::Pubkey};
+
+/// Leads every event so consumers can tell them apart from other `Program data:` logs.
+pub const EVENT_TAG: [u8; 6] = *b"vevent";
+/// Bumped whenever a payload changes. Newer versions only ever append fields, so older decoders keep working.
+/// Version 2 appended `time_base` to `ScheduleCreated`, version 3 `merkle_claim_deadline` to `MerkleRootSet`.
+pub const EVENT_VERSION: u8 = 3;
+/// `EVENT_TAG`, then the event kind, then `EVENT_VERSION`.
+pub const EVENT_HEADER_LEN: usize = EVENT_TAG.len() + 2;
+
+/// A fixed-layout payload logged with `sol_log_data` after a state change, carrying the final
+/// computed values so indexers never have to reverse-engineer them from the token CPIs.
+///
+/// Payloads are packed and hold only integers and byte arrays, so their bytes are their
+/// little endian encoding.
+pub trait Event: Copy + Sized {
+    const KIND: u8;
+
+    fn emit(&self) {
+        let mut data = Vec::with_capacity(EVENT_HEADER_LEN + size_of::<Self>());
+        data.extend_from_slice(&EVENT_TAG);
+        data.push(Self::KIND);
+        data.push(EVENT_VERSION);
+        data.extend_from_slice(unsafe {
+            core::slice::from_raw_parts(self as *const Self as *const u8, size_of::<Self>())
+        });
+        // one slice, so the whole event is a single base64 word in the log
+        sol_log_data(&[&data]);
+    }
+
+    /// Decodes a logged event of this kind, ignoring fields appended by newer versions.
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        let (header, payload) = data.split_at_checked(EVENT_HEADER_LEN)?;
+        if header[..EVENT_TAG.len()] != EVENT_TAG
+            || header[EVENT_TAG.len()] != Self::KIND
+            || payload.len() < size_of::<Self>()
+        {
+            return None;
+        }
+        Some(unsafe { core::ptr::read_unaligned(payload.as_ptr() as *const Self) })
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScheduleCreated {
+    pub schedule: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub seed: u64,
+    pub start: i64,
+    pub cliff_duration: i64,
+    pub step_duration: i64,
+    pub total_duration: i64,
+    pub vesting_mode: u8,
+    pub time_base: u8, //unit of start and the durations, and of every time logged for the schedule
+}
+impl Event for ScheduleCreated {
+    const KIND: u8 = 0;
+}
+
+/// Also emitted for a merkle recipient's first claim, which creates their participant account.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParticipantAdded {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub participant_wallet: Pubkey,
+    pub allocated_amount: u64, //what landed in the vault, after any transfer fee
+    pub from_merkle_leaf: u8,
+}
+impl Event for ParticipantAdded {
+    const KIND: u8 = 1;
+}
+
+/// One per mint paid out, the primary mint first and each basket mint after it.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Claimed {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub participant_wallet: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,         //left the vault, before any transfer fee
+    pub claimed_amount: u64, //total claimed of this mint afterwards
+    pub vested_bps: i64,
+    pub timestamp: i64,
+}
+impl Event for Claimed {
+    const KIND: u8 = 2;
+}
+
+/// One per mint, the primary mint first and each basket mint after it.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParticipantRevoked {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub mint: Pubkey,
+    pub clawback_amount: u64,
+    pub allocated_amount: u64, //what the participant keeps
+    pub terminated_at: i64,
+}
+impl Event for ParticipantRevoked {
+    const KIND: u8 = 3;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuthorityProposed {
+    pub schedule: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+impl Event for AuthorityProposed {
+    const KIND: u8 = 4;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuthorityAccepted {
+    pub schedule: Pubkey,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+impl Event for AuthorityAccepted {
+    const KIND: u8 = 5;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AuthorityTransferCancelled {
+    pub schedule: Pubkey,
+    pub authority: Pubkey,
+}
+impl Event for AuthorityTransferCancelled {
+    const KIND: u8 = 6;
+}
+
+/// Emitted by `CloseParticipant` and by a claim that closes the account it finalizes.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParticipantClosed {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub rent_payer: Pubkey,
+}
+impl Event for ParticipantClosed {
+    const KIND: u8 = 7;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScheduleClosed {
+    pub schedule: Pubkey,
+    pub authority: Pubkey,
+}
+impl Event for ScheduleClosed {
+    const KIND: u8 = 8;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MerkleRootSet {
+    pub schedule: Pubkey,
+    pub merkle_root: [u8; 32], //zeroed when the distribution is removed
+    pub merkle_total: u64,
+    pub merkle_claim_deadline: i64, //in the schedule's time base, zero along with the root
+}
+impl Event for MerkleRootSet {
+    const KIND: u8 = 9;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BeneficiaryTransferred {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub participant_wallet: Pubkey,
+    pub new_vested_participant: Pubkey,
+    pub new_wallet: Pubkey,
+    pub signer: Pubkey, //the schedule authority on a recovery
+}
+impl Event for BeneficiaryTransferred {
+    const KIND: u8 = 10;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PauseUpdated {
+    pub schedule: Pubkey,
+    pub authority: Pubkey,
+    pub paused: u8,
+}
+impl Event for PauseUpdated {
+    const KIND: u8 = 11;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AllocationAdjusted {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub authority: Pubkey,
+    pub previous_amount: u64,
+    pub allocated_amount: u64, //a top up only adds what landed in the vault
+}
+impl Event for AllocationAdjusted {
+    const KIND: u8 = 12;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BasketMintAdded {
+    pub schedule: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+}
+impl Event for BasketMintAdded {
+    const KIND: u8 = 13;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BasketAllocationAdded {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub mint: Pubkey,
+    pub allocated_amount: u64, //what landed in the vault, after any transfer fee
+}
+impl Event for BasketAllocationAdded {
+    const KIND: u8 = 14;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FunderUpdated {
+    pub schedule: Pubkey,
+    pub previous_funder: Pubkey,
+    pub funder: Pubkey,
+}
+impl Event for FunderUpdated {
+    const KIND: u8 = 15;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PauserUpdated {
+    pub schedule: Pubkey,
+    pub authority: Pubkey,
+    pub pauser: Pubkey, //all zeroes when the pauser was removed
+}
+impl Event for PauserUpdated {
+    const KIND: u8 = 16;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub discriminator: u8, //which account type, Schedule or VestedParticipant
+    pub from_version: u8,
+    pub to_version: u8,
+}
+impl Event for AccountMigrated {
+    const KIND: u8 = 17;
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ParticipantAccelerated {
+    pub schedule: Pubkey,
+    pub vested_participant: Pubkey,
+    pub authority: Pubkey,
+    pub added_bps: u16,       //what this trigger vested
+    pub accelerated_bps: u16, //the participant's total acceleration, capped at BPS_DENOMINATOR
+    pub accelerated_at: i64,
+}
+impl Event for ParticipantAccelerated {
+    const KIND: u8 = 18;
+}
@@ -0,0 +1,793 @@
+//! Codama IDL of the program, built from the program's own discriminators, sizes, field offsets
+//! and error codes. Layouts are checked against the structs while the IDL is built, so a moved or
+//! resized field fails `tests/idl.rs` instead of silently shipping a wrong IDL.
+
+use core::mem::{offset_of, size_of};
+
+use serde_json::{json, Value};
+
+use crate::{
+    Accelerate, AccelerateAccounts, AccelerateInstructionData, AcceptAuthority,
+    AcceptAuthorityAccounts, AccountMigrated, AddBasketAllocation, AddBasketAllocationAccounts,
+    AddBasketAllocationInstructionData, AddBasketMint, AddBasketMintAccounts, AddParticipant,
+    AddParticipantAccounts, AddParticipantInstructionData, AdjustAllocation,
+    AdjustAllocationAccounts, AdjustAllocationInstructionData, AllocationAdjusted,
+    AuthorityAccepted, AuthorityProposed, AuthorityTransferCancelled, BasketAllocationAdded,
+    BasketLeg, BasketMintAdded, BatchAddParticipant, BatchAddParticipantAccounts,
+    BeneficiaryTransferred, CancelAuthorityTransfer, CancelAuthorityTransferAccounts, Claim,
+    ClaimAccounts, Claimed, CloseParticipant, CloseParticipantAccounts, CloseSchedule,
+    CloseScheduleAccounts, CrankClaim, Discriminator, Event, FunderUpdated, GetVestingStatus,
+    GetVestingStatusAccounts, Initialize, InitializeAccounts, InitializeInstructionData,
+    InstructionAccount, MerkleRootSet, MigrateAccount, MigrateAccountAccounts,
+    ParticipantAccelerated, ParticipantAdded, ParticipantClosed, ParticipantRevoked, Pause,
+    PauseAccounts, PauseUpdated, PauserUpdated, PinocchioError, ProposeAuthority,
+    ProposeAuthorityAccounts, Revoke, RevokeAccounts, Schedule, ScheduleClosed, ScheduleCreated,
+    SetFunder, SetFunderAccounts, SetMerkleRoot, SetMerkleRootAccounts,
+    SetMerkleRootInstructionData, SetPauser, SetPauserAccounts, TimeBase, Tranche,
+    TransferBeneficiary, TransferBeneficiaryAccounts, Unpause, UnpauseAccounts, VestedParticipant,
+    VestingMode, VestingStatus, BATCH_ERROR_OFFSET, CLAIM_FLAG_AMOUNT,
+    CLAIM_FLAG_CLOSE_WHEN_FINALIZED, EVENT_HEADER_LEN, EVENT_TAG, EVENT_VERSION, ID,
+    MAX_BASKET_MINTS,
+};
+
+pub const IDL_PATH: &str = "idl/multi_token_vesting.json";
+
+#[derive(Clone, Copy)]
+enum Type {
+    U8,
+    U16,
+    U64,
+    I64,
+    Bool,
+    PublicKey,
+    Bytes32,
+    BasketKeys,
+    VestingMode,
+    TimeBase,
+}
+impl Type {
+    fn size(self) -> usize {
+        match self {
+            Self::U8 | Self::Bool | Self::VestingMode | Self::TimeBase => 1,
+            Self::U16 => 2,
+            Self::U64 | Self::I64 => 8,
+            Self::PublicKey | Self::Bytes32 => 32,
+            Self::BasketKeys => MAX_BASKET_MINTS * 32,
+        }
+    }
+
+    fn node(self) -> Value {
+        match self {
+            Self::U8 => number("u8"),
+            Self::U16 => number("u16"),
+            Self::U64 => number("u64"),
+            Self::I64 => number("i64"),
+            Self::Bool => json!({ "kind": "booleanTypeNode", "size": number("u8") }),
+            Self::PublicKey => json!({ "kind": "publicKeyTypeNode" }),
+            Self::Bytes32 => {
+                json!({ "kind": "fixedSizeTypeNode", "size": 32, "type": { "kind": "bytesTypeNode" } })
+            }
+            Self::BasketKeys => json!({
+                "kind": "arrayTypeNode",
+                "item": { "kind": "publicKeyTypeNode" },
+                "count": { "kind": "fixedCountNode", "value": MAX_BASKET_MINTS },
+            }),
+            Self::VestingMode => defined("vestingMode"),
+            Self::TimeBase => defined("timeBase"),
+        }
+    }
+}
+
+fn number(format: &str) -> Value {
+    json!({ "kind": "numberTypeNode", "format": format, "endian": "le" })
+}
+
+fn defined(name: &str) -> Value {
+    json!({ "kind": "definedTypeLinkNode", "name": name })
+}
+
+// every item after the fixed fields, up to the end of the data
+fn remainder(item: Value) -> Value {
+    json!({ "kind": "arrayTypeNode", "item": item, "count": { "kind": "remainderCountNode" } })
+}
+
+fn camel_case(name: &str) -> String {
+    let mut words = name.split('_');
+    let mut camel = words.next().unwrap_or_default().to_string();
+    for word in words {
+        let mut chars = word.chars();
+        camel.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+        camel.push_str(chars.as_str());
+    }
+    camel
+}
+
+// PascalCase Rust names to Codama's camelCase
+fn lower_first(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map(|c| c.to_ascii_lowercase()).into_iter().chain(chars).collect()
+}
+
+fn field(kind: &str, name: &str, node: Value, default: Option<u8>) -> Value {
+    let mut field = json!({ "kind": kind, "name": camel_case(name), "docs": [], "type": node });
+    if let Some(value) = default {
+        field["defaultValue"] = json!({ "kind": "numberValueNode", "number": value });
+        field["defaultValueStrategy"] = json!("omitted");
+    }
+    field
+}
+
+/// Struct fields of a packed layout, each offset checked against `offset_of!`.
+macro_rules! fields {
+    ($layout:ty { $($name:ident: $ty:ident),* $(,)? }) => {
+        [$((stringify!($name), Type::$ty, offset_of!($layout, $name))),*]
+    };
+}
+
+fn layout(name: &str, fields: &[(&str, Type, usize)], len: usize, discriminator: Option<u8>) -> Value {
+    let mut offset = 0;
+    let fields: Vec<Value> = fields
+        .iter()
+        .map(|&(field_name, ty, field_offset)| {
+            assert_eq!(field_offset, offset, "{name}.{field_name} is not where the IDL puts it");
+            offset += ty.size();
+            let default = discriminator.filter(|_| field_name == "discriminator");
+            field("structFieldTypeNode", field_name, ty.node(), default)
+        })
+        .collect();
+    assert_eq!(offset, len, "{name} has fields missing from the IDL");
+
+    json!({ "kind": "structTypeNode", "fields": fields })
+}
+
+fn accounts_of(accounts: &[InstructionAccount]) -> Vec<Value> {
+    accounts
+        .iter()
+        .map(|account| {
+            json!({
+                "kind": "instructionAccountNode",
+                "name": camel_case(account.name),
+                "isWritable": account.writable,
+                "isSigner": if account.optional_signer { json!("either") } else { json!(account.signer) },
+                "isOptional": false,
+                "docs": if account.doc.is_empty() { vec![] } else { vec![account.doc] },
+            })
+        })
+        .collect()
+}
+
+fn instruction(
+    name: &str,
+    discriminator: u8,
+    docs: &[&str],
+    accounts: Vec<Value>,
+    args: &[(&str, Value)],
+) -> Value {
+    let mut arguments = vec![field("instructionArgumentNode", "discriminator", number("u8"), Some(discriminator))];
+    arguments.extend(
+        args.iter()
+            .map(|(arg, node)| field("instructionArgumentNode", arg, node.clone(), None)),
+    );
+
+    json!({
+        "kind": "instructionNode",
+        "name": lower_first(name),
+        "docs": docs,
+        "optionalAccountStrategy": "programId",
+        "accounts": accounts,
+        "arguments": arguments,
+        "discriminators": [{ "kind": "fieldDiscriminatorNode", "name": "discriminator", "offset": 0 }],
+    })
+}
+
+fn args(fields: &[(&'static str, Type)], len: usize) -> Vec<(&'static str, Value)> {
+    assert_eq!(fields.iter().map(|(_, ty)| ty.size()).sum::<usize>(), len);
+    fields.iter().map(|&(name, ty)| (name, ty.node())).collect()
+}
+
+const BASKET_LEGS_DOC: &str = "Followed by one (mint, vault, token account, token program) group per basket mint.";
+const TRANSFER_HOOK_DOC: &str = "Transfer hook extra accounts of the primary mint go last.";
+
+fn instructions() -> Vec<Value> {
+    vec![
+        instruction(
+            "Initialize",
+            *Initialize::DISCRIMINATOR,
+            &["Creates a schedule and its vault. Tranche mode appends the tranche table."],
+            accounts_of(&InitializeAccounts::ACCOUNTS),
+            &[
+                args(
+                    &[
+                        ("start_timestamp", Type::I64),
+                        ("cliff_duration", Type::I64),
+                        ("step_duration", Type::I64),
+                        ("total_duration", Type::I64),
+                        ("seed", Type::U64),
+                        ("bump", Type::U8),
+                        ("vesting_mode", Type::VestingMode),
+                        ("time_base", Type::TimeBase),
+                    ],
+                    InitializeInstructionData::LEN,
+                ),
+                vec![("tranches", remainder(defined("tranche")))],
+            ]
+            .concat(),
+        ),
+        instruction(
+            "AddParticipant",
+            *AddParticipant::DISCRIMINATOR,
+            &[TRANSFER_HOOK_DOC],
+            accounts_of(&AddParticipantAccounts::ACCOUNTS),
+            &[
+                args(&[("token_allocation_amount", Type::U64)], AddParticipantInstructionData::LEN),
+                // left off, the participant follows the schedule's start
+                vec![("start", json!({ "kind": "remainderOptionTypeNode", "item": Type::I64.node() }))],
+            ]
+            .concat(),
+        ),
+        instruction(
+            "Claim",
+            *Claim::DISCRIMINATOR,
+            &[
+                &format!(
+                    "Data is empty, or a flags byte: {CLAIM_FLAG_CLOSE_WHEN_FINALIZED} closes the account once fully claimed, {CLAIM_FLAG_AMOUNT} is followed by a u64 amount."
+                ),
+                "Merkle recipients then append their u64 allocation and 32-byte proof nodes.",
+                "Remaining accounts: the rent payer when closing, then the basket legs, then transfer hook accounts.",
+            ],
+            accounts_of(&ClaimAccounts::ACCOUNTS),
+            &[("data", json!({ "kind": "bytesTypeNode" }))],
+        ),
+        instruction(
+            "Revoke",
+            *Revoke::DISCRIMINATOR,
+            &[BASKET_LEGS_DOC, TRANSFER_HOOK_DOC],
+            accounts_of(&RevokeAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "ProposeAuthority",
+            *ProposeAuthority::DISCRIMINATOR,
+            &[],
+            accounts_of(&ProposeAuthorityAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "AcceptAuthority",
+            *AcceptAuthority::DISCRIMINATOR,
+            &[],
+            accounts_of(&AcceptAuthorityAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "CancelAuthorityTransfer",
+            *CancelAuthorityTransfer::DISCRIMINATOR,
+            &[],
+            accounts_of(&CancelAuthorityTransferAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "CloseParticipant",
+            *CloseParticipant::DISCRIMINATOR,
+            &[],
+            accounts_of(&CloseParticipantAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "CloseSchedule",
+            *CloseSchedule::DISCRIMINATOR,
+            &[BASKET_LEGS_DOC, "Basket mints are writable here, withheld transfer fees of their vaults are harvested to them.", TRANSFER_HOOK_DOC],
+            accounts_of(&CloseScheduleAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "BatchAddParticipant",
+            *BatchAddParticipant::DISCRIMINATOR,
+            &[
+                "Followed by one (participant wallet, vested participant) pair per amount, then transfer hook accounts.",
+                &format!("A failing entry returns custom error {BATCH_ERROR_OFFSET} plus its index."),
+            ],
+            accounts_of(&BatchAddParticipantAccounts::ACCOUNTS),
+            &[("token_allocation_amounts", remainder(number("u64")))],
+        ),
+        instruction(
+            "SetMerkleRoot",
+            *SetMerkleRoot::DISCRIMINATOR,
+            &["Locked at the cliff until the claim deadline, after which it can only be cleared to refund the unclaimed leaves.", TRANSFER_HOOK_DOC],
+            accounts_of(&SetMerkleRootAccounts::ACCOUNTS),
+            &args(
+                &[("merkle_root", Type::Bytes32), ("merkle_total", Type::U64), ("merkle_claim_deadline", Type::I64)],
+                size_of::<SetMerkleRootInstructionData>(),
+            ),
+        ),
+        instruction(
+            "TransferBeneficiary",
+            *TransferBeneficiary::DISCRIMINATOR,
+            &[],
+            accounts_of(&TransferBeneficiaryAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "CrankClaim",
+            *CrankClaim::DISCRIMINATOR,
+            &["Claim on behalf of the participant. Remaining accounts: the basket legs, then transfer hook accounts."],
+            accounts_of(&CrankClaim::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "Pause",
+            *Pause::DISCRIMINATOR,
+            &[],
+            accounts_of(&PauseAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "Unpause",
+            *Unpause::DISCRIMINATOR,
+            &[],
+            accounts_of(&UnpauseAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "AdjustAllocation",
+            *AdjustAllocation::DISCRIMINATOR,
+            &[TRANSFER_HOOK_DOC],
+            accounts_of(&AdjustAllocationAccounts::ACCOUNTS),
+            &args(
+                &[("token_allocation_amount", Type::U64)],
+                size_of::<AdjustAllocationInstructionData>(),
+            ),
+        ),
+        instruction(
+            "AddBasketMint",
+            *AddBasketMint::DISCRIMINATOR,
+            &[],
+            accounts_of(&AddBasketMintAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "AddBasketAllocation",
+            *AddBasketAllocation::DISCRIMINATOR,
+            &[TRANSFER_HOOK_DOC],
+            accounts_of(&AddBasketAllocationAccounts::ACCOUNTS),
+            &args(
+                &[("token_allocation_amount", Type::U64)],
+                size_of::<AddBasketAllocationInstructionData>(),
+            ),
+        ),
+        instruction(
+            "GetVestingStatus",
+            *GetVestingStatus::DISCRIMINATOR,
+            &["Read-only, sets a vestingStatus as return data."],
+            accounts_of(&GetVestingStatusAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "SetFunder",
+            *SetFunder::DISCRIMINATOR,
+            &["Only the current funder can hand the role on, and only to a key that signs too."],
+            accounts_of(&SetFunderAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "SetPauser",
+            *SetPauser::DISCRIMINATOR,
+            &[],
+            accounts_of(&SetPauserAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "MigrateAccount",
+            *MigrateAccount::DISCRIMINATOR,
+            &["Remaining accounts: the vault of a version 1 schedule or the migrated schedule of a version 1 participant."],
+            accounts_of(&MigrateAccountAccounts::ACCOUNTS),
+            &[],
+        ),
+        instruction(
+            "Accelerate",
+            *Accelerate::DISCRIMINATOR,
+            &["Vests a further share of the allocation on top of the curve, 10000 bps vests everything."],
+            accounts_of(&AccelerateAccounts::ACCOUNTS),
+            &args(&[("accelerated_bps", Type::U16)], size_of::<AccelerateInstructionData>()),
+        ),
+    ]
+}
+
+fn accounts() -> Vec<Value> {
+    vec![
+        json!({
+            "kind": "accountNode",
+            "name": "schedule",
+            "docs": ["Followed by the tranche table in tranche mode. Older layout versions, version 1 without the version byte, need migrateAccount."],
+            "data": layout(
+                "Schedule",
+                &fields!(Schedule {
+                    discriminator: U8,
+                    version: U8,
+                    mint: PublicKey,
+                    authority: PublicKey,
+                    seed: U64,
+                    start: I64,
+                    cliff_duration: I64,
+                    step_duration: I64,
+                    total_duration: I64,
+                    bump: U8,
+                    pending_authority: PublicKey,
+                    participant_count: U64,
+                    outstanding_amount: U64,
+                    vesting_mode: VestingMode,
+                    merkle_root: Bytes32,
+                    merkle_total: U64,
+                    paused: Bool,
+                    basket_mint_count: U8,
+                    basket_mints: BasketKeys,
+                    creator: PublicKey,
+                    funder: PublicKey,
+                    pauser: PublicKey,
+                    vault: PublicKey,
+                    time_base: TimeBase,
+                    basket_vaults: BasketKeys,
+                    merkle_claim_deadline: I64,
+                }),
+                Schedule::LEN,
+                Some(Schedule::DISCRIMINATOR),
+            ),
+            "discriminators": [{ "kind": "fieldDiscriminatorNode", "name": "discriminator", "offset": 0 }],
+        }),
+        json!({
+            "kind": "accountNode",
+            "name": "vestedParticipant",
+            "docs": ["Followed by one basketLeg per basket mint of the schedule. Older layout versions, version 1 without the version byte, need migrateAccount."],
+            "data": layout(
+                "VestedParticipant",
+                &fields!(VestedParticipant {
+                    discriminator: U8,
+                    version: U8,
+                    schedule: PublicKey,
+                    participant: PublicKey,
+                    allocated_amount: U64,
+                    claimed_amount: U64,
+                    terminated_at: I64,
+                    rent_payer: PublicKey,
+                    from_merkle_leaf: Bool,
+                    bump: U8,
+                    start: I64,
+                    accelerated_bps: U16,
+                }),
+                VestedParticipant::LEN,
+                Some(VestedParticipant::DISCRIMINATOR),
+            ),
+            "pda": { "kind": "pdaLinkNode", "name": "vestedParticipant" },
+            "discriminators": [{ "kind": "fieldDiscriminatorNode", "name": "discriminator", "offset": 0 }],
+        }),
+    ]
+}
+
+fn defined_type(name: &str, docs: &[String], node: Value) -> Value {
+    json!({ "kind": "definedTypeNode", "name": name, "docs": docs, "type": node })
+}
+
+fn event<E: Event>(name: &str, fields: &[(&str, Type, usize)]) -> Value {
+    defined_type(
+        &lower_first(name),
+        &[format!("Event kind {}.", E::KIND)],
+        layout(name, fields, size_of::<E>(), None),
+    )
+}
+
+fn defined_types() -> Vec<Value> {
+    vec![
+        defined_type(
+            "vestingMode",
+            &[],
+            {
+                let variants: Vec<Value> = (0..=u8::MAX)
+                    .map_while(|value| VestingMode::try_from(value).ok())
+                    .map(|mode| {
+                        json!({ "kind": "enumEmptyVariantTypeNode", "name": lower_first(&format!("{mode:?}")) })
+                    })
+                    .collect();
+                json!({ "kind": "enumTypeNode", "variants": variants, "size": number("u8") })
+            },
+        ),
+        defined_type(
+            "timeBase",
+            &["Unit of a schedule's start, durations and tranche offsets, and of every time logged for it.".to_string()],
+            {
+                let variants: Vec<Value> = (0..=u8::MAX)
+                    .map_while(|value| TimeBase::try_from(value).ok())
+                    .map(|time_base| {
+                        json!({ "kind": "enumEmptyVariantTypeNode", "name": lower_first(&format!("{time_base:?}")) })
+                    })
+                    .collect();
+                json!({ "kind": "enumTypeNode", "variants": variants, "size": number("u8") })
+            },
+        ),
+        defined_type(
+            "tranche",
+            &[],
+            layout("Tranche", &fields!(Tranche { offset: I64, cumulative_bps: U16 }), Tranche::LEN, None),
+        ),
+        defined_type(
+            "basketLeg",
+            &[],
+            layout(
+                "BasketLeg",
+                &fields!(BasketLeg { allocated_amount: U64, claimed_amount: U64 }),
+                BasketLeg::LEN,
+                None,
+            ),
+        ),
+        defined_type(
+            "vestingStatus",
+            &["Return data of GetVestingStatus.".to_string()],
+            {
+                let fields = [
+                    ("vested_amount", Type::U64),
+                    ("claimed_amount", Type::U64),
+                    ("claimable_amount", Type::U64),
+                    ("next_unlock_at", Type::I64),
+                    ("fully_vested_at", Type::I64),
+                ];
+                let len = fields.iter().map(|(_, ty)| ty.size()).sum::<usize>();
+                assert_eq!(len, VestingStatus::LEN);
+                json!({
+                    "kind": "structTypeNode",
+                    "fields": fields
+                        .iter()
+                        .map(|&(name, ty)| field("structFieldTypeNode", name, ty.node(), None))
+                        .collect::<Vec<_>>(),
+                })
+            },
+        ),
+        defined_type(
+            "eventHeader",
+            &[format!(
+                "Leads every event logged with sol_log_data: {EVENT_TAG:?}, the event kind, then version {EVENT_VERSION}."
+            )],
+            {
+                assert_eq!(EVENT_TAG.len() + 2, EVENT_HEADER_LEN);
+                let tag = json!({
+                    "kind": "fixedSizeTypeNode",
+                    "size": EVENT_TAG.len(),
+                    "type": { "kind": "bytesTypeNode" },
+                });
+                json!({
+                    "kind": "structTypeNode",
+                    "fields": [
+                        field("structFieldTypeNode", "tag", tag, None),
+                        field("structFieldTypeNode", "kind", number("u8"), None),
+                        field("structFieldTypeNode", "version", number("u8"), None),
+                    ],
+                })
+            },
+        ),
+        event::<ScheduleCreated>(
+            "ScheduleCreated",
+            &fields!(ScheduleCreated {
+                schedule: PublicKey,
+                creator: PublicKey,
+                mint: PublicKey,
+                vault: PublicKey,
+                seed: U64,
+                start: I64,
+                cliff_duration: I64,
+                step_duration: I64,
+                total_duration: I64,
+                vesting_mode: VestingMode,
+                time_base: TimeBase,
+            }),
+        ),
+        event::<ParticipantAdded>(
+            "ParticipantAdded",
+            &fields!(ParticipantAdded {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                participant_wallet: PublicKey,
+                allocated_amount: U64,
+                from_merkle_leaf: Bool,
+            }),
+        ),
+        event::<Claimed>(
+            "Claimed",
+            &fields!(Claimed {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                participant_wallet: PublicKey,
+                mint: PublicKey,
+                amount: U64,
+                claimed_amount: U64,
+                vested_bps: I64,
+                timestamp: I64,
+            }),
+        ),
+        event::<ParticipantRevoked>(
+            "ParticipantRevoked",
+            &fields!(ParticipantRevoked {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                mint: PublicKey,
+                clawback_amount: U64,
+                allocated_amount: U64,
+                terminated_at: I64,
+            }),
+        ),
+        event::<AuthorityProposed>(
+            "AuthorityProposed",
+            &fields!(AuthorityProposed { schedule: PublicKey, authority: PublicKey, pending_authority: PublicKey }),
+        ),
+        event::<AuthorityAccepted>(
+            "AuthorityAccepted",
+            &fields!(AuthorityAccepted { schedule: PublicKey, previous_authority: PublicKey, authority: PublicKey }),
+        ),
+        event::<AuthorityTransferCancelled>(
+            "AuthorityTransferCancelled",
+            &fields!(AuthorityTransferCancelled { schedule: PublicKey, authority: PublicKey }),
+        ),
+        event::<ParticipantClosed>(
+            "ParticipantClosed",
+            &fields!(ParticipantClosed {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                rent_payer: PublicKey,
+            }),
+        ),
+        event::<ScheduleClosed>(
+            "ScheduleClosed",
+            &fields!(ScheduleClosed { schedule: PublicKey, authority: PublicKey }),
+        ),
+        event::<MerkleRootSet>(
+            "MerkleRootSet",
+            &fields!(MerkleRootSet {
+                schedule: PublicKey,
+                merkle_root: Bytes32,
+                merkle_total: U64,
+                merkle_claim_deadline: I64,
+            }),
+        ),
+        event::<BeneficiaryTransferred>(
+            "BeneficiaryTransferred",
+            &fields!(BeneficiaryTransferred {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                participant_wallet: PublicKey,
+                new_vested_participant: PublicKey,
+                new_wallet: PublicKey,
+                signer: PublicKey,
+            }),
+        ),
+        event::<PauseUpdated>(
+            "PauseUpdated",
+            &fields!(PauseUpdated { schedule: PublicKey, authority: PublicKey, paused: Bool }),
+        ),
+        event::<AllocationAdjusted>(
+            "AllocationAdjusted",
+            &fields!(AllocationAdjusted {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                authority: PublicKey,
+                previous_amount: U64,
+                allocated_amount: U64,
+            }),
+        ),
+        event::<BasketMintAdded>(
+            "BasketMintAdded",
+            &fields!(BasketMintAdded { schedule: PublicKey, mint: PublicKey, vault: PublicKey }),
+        ),
+        event::<BasketAllocationAdded>(
+            "BasketAllocationAdded",
+            &fields!(BasketAllocationAdded {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                mint: PublicKey,
+                allocated_amount: U64,
+            }),
+        ),
+        event::<FunderUpdated>(
+            "FunderUpdated",
+            &fields!(FunderUpdated { schedule: PublicKey, previous_funder: PublicKey, funder: PublicKey }),
+        ),
+        event::<PauserUpdated>(
+            "PauserUpdated",
+            &fields!(PauserUpdated { schedule: PublicKey, authority: PublicKey, pauser: PublicKey }),
+        ),
+        event::<AccountMigrated>(
+            "AccountMigrated",
+            &fields!(AccountMigrated { account: PublicKey, discriminator: U8, from_version: U8, to_version: U8 }),
+        ),
+        event::<ParticipantAccelerated>(
+            "ParticipantAccelerated",
+            &fields!(ParticipantAccelerated {
+                schedule: PublicKey,
+                vested_participant: PublicKey,
+                authority: PublicKey,
+                added_bps: U16,
+                accelerated_bps: U16,
+                accelerated_at: I64,
+            }),
+        ),
+    ]
+}
+
+fn constant_seed(seed: &str) -> Value {
+    json!({
+        "kind": "constantPdaSeedNode",
+        "type": { "kind": "stringTypeNode", "encoding": "utf8" },
+        "value": { "kind": "stringValueNode", "string": seed },
+    })
+}
+
+fn variable_seed(name: &str, node: Value) -> Value {
+    json!({ "kind": "variablePdaSeedNode", "name": camel_case(name), "docs": [], "type": node })
+}
+
+fn pdas() -> Vec<Value> {
+    vec![
+        json!({
+            "kind": "pdaNode",
+            "name": "schedule",
+            "docs": [],
+            "seeds": [
+                constant_seed("schedule"),
+                variable_seed("creator", Type::PublicKey.node()),
+                variable_seed("mint", Type::PublicKey.node()),
+                variable_seed("seed", Type::U64.node()),
+            ],
+        }),
+        json!({
+            "kind": "pdaNode",
+            "name": "legacySchedule",
+            "docs": ["Schedules created before addresses were namespaced, their creator is zeroed."],
+            "seeds": [constant_seed("schedule"), variable_seed("seed", Type::U64.node())],
+        }),
+        json!({
+            "kind": "pdaNode",
+            "name": "vestedParticipant",
+            "docs": [],
+            "seeds": [
+                constant_seed("participant"),
+                variable_seed("participant_wallet", Type::PublicKey.node()),
+                variable_seed("schedule", Type::PublicKey.node()),
+            ],
+        }),
+    ]
+}
+
+fn errors() -> Vec<Value> {
+    PinocchioError::ALL
+        .iter()
+        .enumerate()
+        .map(|(code, error)| {
+            assert_eq!(error.clone() as usize, code, "PinocchioError::ALL is out of code order");
+            json!({
+                "kind": "errorNode",
+                "name": lower_first(&format!("{error:?}")),
+                "code": code,
+                "message": error.to_string(),
+                "docs": [],
+            })
+        })
+        .collect()
+}
+
+/// The program's Codama root node, what `IDL_PATH` holds.
+pub fn idl() -> Value {
+    json!({
+        "kind": "rootNode",
+        "standard": "codama",
+        "version": "1.0.0",
+        "program": {
+            "kind": "programNode",
+            "name": "multiTokenVesting",
+            "publicKey": bs58::encode(ID).into_string(),
+            "version": env!("CARGO_PKG_VERSION"),
+            "docs": [],
+            "accounts": accounts(),
+            "instructions": instructions(),
+            "definedTypes": defined_types(),
+            "pdas": pdas(),
+            "errors": errors(),
+        },
+        "additionalPrograms": [],
+    })
+}
@@ -0,0 +1,125 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    Event, InstructionAccount, ParticipantAccelerated, PinocchioError, ProgramAccount, Schedule,
+    SignerAccount, VestedParticipant, BPS_DENOMINATOR,
+};
+
+pub struct AccelerateAccounts<'a> {
+    pub authority: &'a AccountInfo,          //signer
+    pub vested_participant: &'a AccountInfo, //state acc
+    pub schedule: &'a AccountInfo,
+}
+impl AccelerateAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 3] = [
+        InstructionAccount::new("authority", false, true, ""),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("schedule", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for AccelerateAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, vested_participant, schedule] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        Ok(Self {
+            authority,
+            vested_participant,
+            schedule,
+        })
+    }
+}
+#[repr(C, packed)]
+pub struct AccelerateInstructionData {
+    // share of the allocation vested on top of the curve, BPS_DENOMINATOR vests everything
+    pub accelerated_bps: u16,
+}
+impl<'a> TryFrom<&'a [u8]> for AccelerateInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<AccelerateInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let accelerated_bps = u16::from_le_bytes(data[0..2].try_into().unwrap());
+        if accelerated_bps == 0 || accelerated_bps as u64 > BPS_DENOMINATOR {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { accelerated_bps })
+    }
+}
+#[cfg(not(target_os = "solana"))]
+impl AccelerateInstructionData {
+    /// Encodes the data the way `try_from` reads it, for off-chain clients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.accelerated_bps.to_le_bytes().to_vec()
+    }
+}
+pub struct Accelerate<'a> {
+    pub accounts: AccelerateAccounts<'a>,
+    pub instruction_data: AccelerateInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Accelerate<'a> {
+    type Error = ProgramError;
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AccelerateAccounts::try_from(accounts)?;
+        let instruction_data = AccelerateInstructionData::try_from(instruction_data)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        if schedule.authority() != accounts.authority.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+        if vested_participant.schedule() != accounts.schedule.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // a revoked participant's allocation is already cut down to what was vested
+        if vested_participant.is_terminated() {
+            return Err(PinocchioError::ParticipantTerminated.into());
+        }
+        if vested_participant.accelerated_bps() as u64 == BPS_DENOMINATOR {
+            return Err(PinocchioError::ParticipantFullyAccelerated.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+impl<'a> Accelerate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &22;
+    pub fn process(&mut self) -> ProgramResult {
+        // repeated triggers add up, a double trigger is this followed by Revoke, which keeps the accelerated share
+        let mut vested_participant = VestedParticipant::load_mut(self.accounts.vested_participant)?;
+        let accelerated_bps = (vested_participant.accelerated_bps() + self.instruction_data.accelerated_bps)
+            .min(BPS_DENOMINATOR as u16);
+        vested_participant.set_accelerated_bps(accelerated_bps);
+
+        ParticipantAccelerated {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *self.accounts.vested_participant.key(),
+            authority: *self.accounts.authority.key(),
+            added_bps: self.instruction_data.accelerated_bps,
+            accelerated_bps,
+            accelerated_at: Schedule::load(self.accounts.schedule)?.now()?,
+        }
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult};
+
+use crate::{AuthorityAccepted, Event, InstructionAccount, PinocchioError, ProgramAccount, Schedule, SignerAccount};
+
+pub struct AcceptAuthorityAccounts<'a> {
+    pub pending_authority: &'a AccountInfo, //signer
+    pub schedule: &'a AccountInfo,
+}
+impl AcceptAuthorityAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 2] = [
+        InstructionAccount::new("pending_authority", false, true, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAuthorityAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [pending_authority, schedule] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        // the new authority signing proves the key is usable, a typo in the proposal can never take over the schedule
+        SignerAccount::check(pending_authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        Ok(Self {
+            pending_authority,
+            schedule,
+        })
+    }
+}
+pub struct AcceptAuthority<'a> {
+    pub accounts: AcceptAuthorityAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptAuthority<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AcceptAuthorityAccounts::try_from(accounts)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        if !schedule.has_pending_authority() {
+            return Err(PinocchioError::NoPendingAuthority.into());
+        }
+        if schedule.pending_authority() != accounts.pending_authority.key() {
+            return Err(PinocchioError::InvalidSigner.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> AcceptAuthority<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        let previous_authority = *schedule.authority();
+        schedule.set_authority(*self.accounts.pending_authority.key());
+        schedule.set_pending_authority(Pubkey::default());
+
+        AuthorityAccepted {
+            schedule: *self.accounts.schedule.key(),
+            previous_authority,
+            authority: *self.accounts.pending_authority.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
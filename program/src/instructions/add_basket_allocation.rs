@@ -0,0 +1,209 @@
+use core::mem::size_of;
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    AssociatedTokenAccount, BasketAllocationAdded, Event, InstructionAccount, MintAccount,
+    PinocchioError, ProgramAccount, Schedule, SignerAccount, TokenAccount, TokenProgram,
+    TokenTransfer, VaultAccount, VestedParticipant,
+};
+
+pub struct AddBasketAllocationAccounts<'a> {
+    pub authority: &'a AccountInfo,     //signer
+    pub funder: &'a AccountInfo,        //signer, the schedule's funder, may be the authority
+    pub funder_ata: &'a AccountInfo,    //funders ata of the basket mint
+    pub vault: &'a AccountInfo,         //schedule's ata of the basket mint
+    pub vested_participant: &'a AccountInfo,
+    pub schedule: &'a AccountInfo,
+    pub mint: &'a AccountInfo, //basket mint
+    pub token_program: &'a AccountInfo,
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl AddBasketAllocationAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 8] = [
+        InstructionAccount::new("authority", true, true, ""),
+        InstructionAccount::new("funder", false, true, "The schedule's funder, may be the authority"),
+        InstructionAccount::new("funder_ata", true, false, "Funder's ata of the basket mint"),
+        InstructionAccount::new("vault", true, false, "Schedule's ata of the basket mint"),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, "Basket mint"),
+        InstructionAccount::new("token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for AddBasketAllocationAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let ([authority, funder, funder_ata, vault, vested_participant, schedule, mint, token_program], remaining_accounts) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        SignerAccount::check(funder)?;
+        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        Ok(Self {
+            authority,
+            funder,
+            funder_ata,
+            vault,
+            vested_participant,
+            schedule,
+            mint,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+#[repr(C, packed)]
+pub struct AddBasketAllocationInstructionData {
+    pub token_allocation_amount: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for AddBasketAllocationInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<AddBasketAllocationInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let token_allocation_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        if token_allocation_amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            token_allocation_amount,
+        })
+    }
+}
+#[cfg(not(target_os = "solana"))]
+impl AddBasketAllocationInstructionData {
+    /// Encodes the data the way `try_from` reads it, for off-chain clients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.token_allocation_amount.to_le_bytes().to_vec()
+    }
+}
+pub struct AddBasketAllocation<'a> {
+    pub accounts: AddBasketAllocationAccounts<'a>,
+    pub instruction_data: AddBasketAllocationInstructionData,
+    pub basket_index: usize,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AddBasketAllocation<'a> {
+    type Error = ProgramError;
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AddBasketAllocationAccounts::try_from(accounts)?;
+        let instruction_data = AddBasketAllocationInstructionData::try_from(instruction_data)?;
+
+        let basket_index = {
+            let schedule = Schedule::load(accounts.schedule)?;
+            let tranches = Schedule::load_tranches(accounts.schedule)?;
+
+            if schedule.is_paused() {
+                return Err(PinocchioError::SchedulePaused.into());
+            }
+
+            if schedule.authority() != accounts.authority.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if schedule.funder() != accounts.funder.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            let Some(basket_index) = schedule
+                .basket_mints()
+                .iter()
+                .position(|basket_mint| basket_mint == accounts.mint.key())
+            else {
+                return Err(PinocchioError::BasketMintInvalid.into());
+            };
+
+            let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+            if vested_participant.schedule() != accounts.schedule.key() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if vested_participant.is_terminated() {
+                return Err(PinocchioError::ParticipantTerminated.into());
+            }
+
+            if schedule
+                .participant_curve(&tranches, &vested_participant)
+                .is_cliff_completed_at(schedule.now()?)
+            {
+                return Err(PinocchioError::CannotAddParticipantAfterCliff.into());
+            }
+
+            // like AddParticipant, each allocation is set once, AdjustAllocation only covers the primary mint
+            let basket_legs = VestedParticipant::load_basket_legs(accounts.vested_participant)?;
+            if basket_legs.get(basket_index).ok_or(ProgramError::InvalidAccountData)?.allocated_amount() != 0 {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+
+            basket_index
+        };
+
+        AssociatedTokenAccount::check(
+            accounts.funder_ata,
+            accounts.funder,
+            accounts.mint,
+            accounts.token_program,
+        )?;
+
+        if TokenAccount::amount(accounts.funder_ata)? < instruction_data.token_allocation_amount {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        VaultAccount::check_key(
+            accounts.vault,
+            &Schedule::load(accounts.schedule)?.basket_vaults()[basket_index],
+        )?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            basket_index,
+        })
+    }
+}
+impl<'a> AddBasketAllocation<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &17;
+    pub fn process(&mut self) -> ProgramResult {
+        // transfer fee mints deliver less than was sent, so the allocation is whatever actually landed in the vault
+        let vault_amount_before = TokenAccount::amount(self.accounts.vault)?;
+
+        TokenTransfer {
+            from: self.accounts.funder_ata,
+            mint: self.accounts.mint,
+            to: self.accounts.vault,
+            authority: self.accounts.funder,
+            token_program: self.accounts.token_program,
+            remaining_accounts: self.accounts.remaining_accounts,
+            amount: self.instruction_data.token_allocation_amount,
+            decimals: MintAccount::decimals(self.accounts.mint)?,
+        }
+        .invoke()?;
+
+        let received_amount = TokenAccount::amount(self.accounts.vault)? - vault_amount_before;
+        if received_amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // basket legs aren't part of outstanding_amount, a participant can only be closed once every leg is claimed
+        VestedParticipant::load_basket_legs_mut(self.accounts.vested_participant)?[self.basket_index]
+            .set_allocated_amount(received_amount);
+
+        BasketAllocationAdded {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *self.accounts.vested_participant.key(),
+            mint: *self.accounts.mint.key(),
+            allocated_amount: received_amount,
+        }
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{
+    AssociatedTokenAccount, BasketMintAdded, Event, InstructionAccount, MintAccount, PinocchioError,
+    ProgramAccount, Schedule, SignerAccount, TokenProgram,
+};
+
+pub struct AddBasketMintAccounts<'a> {
+    pub authority: &'a AccountInfo, //signer, pays the vault rent
+    pub schedule: &'a AccountInfo,
+    pub mint: &'a AccountInfo,  //basket mint
+    pub vault: &'a AccountInfo, //schedule's ata of the basket mint
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub associated_token_account_program: &'a AccountInfo,
+}
+impl AddBasketMintAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 7] = [
+        InstructionAccount::new("authority", true, true, "Pays the vault rent"),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, "Basket mint"),
+        InstructionAccount::new("vault", true, false, "Schedule's ata of the basket mint"),
+        InstructionAccount::new("system_program", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+        InstructionAccount::new("associated_token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for AddBasketMintAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, schedule, mint, vault, system_program, token_program, associated_token_account_program] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        MintAccount::check(mint)?;
+        ProgramAccount::check_program(system_program, &pinocchio_system::ID)?;
+        TokenProgram::check_mint(token_program, mint)?;
+        ProgramAccount::check_program(
+            associated_token_account_program,
+            &pinocchio_associated_token_account::ID,
+        )?;
+
+        Ok(Self {
+            authority,
+            schedule,
+            mint,
+            vault,
+            system_program,
+            token_program,
+            associated_token_account_program,
+        })
+    }
+}
+pub struct AddBasketMint<'a> {
+    pub accounts: AddBasketMintAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for AddBasketMint<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AddBasketMintAccounts::try_from(accounts)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        let tranches = Schedule::load_tranches(accounts.schedule)?;
+
+        if schedule.is_cliff_completed(&tranches)? {
+            return Err(PinocchioError::CannotAddParticipantAfterCliff.into());
+        }
+
+        if schedule.authority() != accounts.authority.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        // participant accounts are sized for the basket when they are created, so it can't grow under them
+        if schedule.participant_count() != 0 {
+            return Err(PinocchioError::BasketLocked.into());
+        }
+
+        if accounts.mint.key() == schedule.mint()
+            || schedule.basket_mints().contains(accounts.mint.key())
+        {
+            return Err(PinocchioError::BasketMintInvalid.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> AddBasketMint<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+    pub fn process(&mut self) -> ProgramResult {
+        AssociatedTokenAccount::init_if_needed(
+            self.accounts.vault,
+            self.accounts.mint,
+            self.accounts.authority,
+            self.accounts.schedule,
+            self.accounts.system_program,
+            self.accounts.token_program,
+        )?;
+
+        // legs are checked against the stored vault from now on, like the primary vault
+        Schedule::load_mut(self.accounts.schedule)?
+            .add_basket_mint(*self.accounts.mint.key(), *self.accounts.vault.key())?;
+
+        BasketMintAdded {
+            schedule: *self.accounts.schedule.key(),
+            mint: *self.accounts.mint.key(),
+            vault: *self.accounts.vault.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
@@ -2,16 +2,17 @@ use core::mem::size_of;
 use pinocchio::{
     account_info::AccountInfo, instruction::Seed, program_error::ProgramError, ProgramResult,
 };
-use pinocchio_token::{instructions::Transfer, state::TokenAccount};
 
 use crate::{
-    AssociatedTokenAccount, Discriminator, MintAccount, PinocchioError, ProgramAccount, Schedule,
-    SignerAccount, VestedParticipant,
+    AssociatedTokenAccount, Event, InstructionAccount, MintAccount, ParticipantAdded,
+    PinocchioError, ProgramAccount, Schedule, SignerAccount, TokenAccount, TokenProgram,
+    TokenTransfer, VaultAccount, VestedParticipant, VestedParticipantParams,
 };
 
 pub struct AddParticipantAccounts<'a> {
-    pub authority: &'a AccountInfo,     //signer
-    pub authority_ata: &'a AccountInfo, //signers ata
+    pub authority: &'a AccountInfo,     //signer, pays the participant account rent
+    pub funder: &'a AccountInfo,        //signer, the schedule's funder, may be the authority
+    pub funder_ata: &'a AccountInfo,    //funders ata
     pub vault: &'a AccountInfo,         //vault for allocations
     pub participant_wallet: &'a AccountInfo,
     pub vested_participant: &'a AccountInfo,
@@ -19,23 +20,38 @@ pub struct AddParticipantAccounts<'a> {
     pub mint: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl AddParticipantAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 10] = [
+        InstructionAccount::new("authority", true, true, "Pays the participant account rent"),
+        InstructionAccount::new("funder", false, true, "The schedule's funder, may be the authority"),
+        InstructionAccount::new("funder_ata", true, false, ""),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("participant_wallet", false, false, ""),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("system_program", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+    ];
 }
 impl<'a> TryFrom<&'a [AccountInfo]> for AddParticipantAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [authority, authority_ata, vault, participant_wallet, vested_participant, schedule, mint, system_program, token_program] =
-            accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
+        let ([authority, funder, funder_ata, vault, participant_wallet, vested_participant, schedule, mint, system_program, token_program], remaining_accounts) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
 
         SignerAccount::check(authority)?;
+        SignerAccount::check(funder)?;
         ProgramAccount::check::<Schedule>(schedule)?;
         MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
 
         Ok(Self {
             authority,
-            authority_ata,
+            funder,
+            funder_ata,
             vault,
             vested_participant,
             participant_wallet,
@@ -43,33 +59,60 @@ impl<'a> TryFrom<&'a [AccountInfo]> for AddParticipantAccounts<'a> {
             mint,
             system_program,
             token_program,
+            remaining_accounts,
         })
     }
 }
-#[repr(C, packed)]
 pub struct AddParticipantInstructionData {
     pub token_allocation_amount: u64,
+    // optional trailing i64, the participant's own start, e.g. a hire date, the schedule's durations run from it
+    pub start: Option<i64>,
+}
+impl AddParticipantInstructionData {
+    pub const LEN: usize = size_of::<u64>();
 }
 impl<'a> TryFrom<&'a [u8]> for AddParticipantInstructionData {
     type Error = ProgramError;
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<AddParticipantInstructionData>() {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        let start = match data.len() {
+            Self::LEN => None,
+            len if len == Self::LEN + size_of::<i64>() => {
+                Some(i64::from_le_bytes(data[Self::LEN..].try_into().unwrap()))
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
 
         let token_allocation_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
         if token_allocation_amount == 0 {
             return Err(ProgramError::InvalidInstructionData);
         }
 
+        // 0 is what a participant without their own start stores
+        if start.is_some_and(|start| start <= 0) {
+            return Err(PinocchioError::StartTimeInvalid.into());
+        }
+
         Ok(Self {
             token_allocation_amount,
+            start,
         })
     }
 }
+#[cfg(not(target_os = "solana"))]
+impl AddParticipantInstructionData {
+    /// Encodes the data the way `try_from` reads it, for off-chain clients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = self.token_allocation_amount.to_le_bytes().to_vec();
+        if let Some(start) = self.start {
+            data.extend_from_slice(&start.to_le_bytes());
+        }
+        data
+    }
+}
 pub struct AddParticipant<'a> {
     pub accounts: AddParticipantAccounts<'a>,
     pub instruction_data: AddParticipantInstructionData,
+    pub bump: u8,
 }
 impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AddParticipant<'a> {
     type Error = ProgramError;
@@ -80,20 +123,34 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AddParticipant<'a> {
         let instruction_data = AddParticipantInstructionData::try_from(instruction_data)?;
 
         let schedule = Schedule::load(accounts.schedule)?;
+        let tranches = Schedule::load_tranches(accounts.schedule)?;
 
-        if schedule.is_cliff_completed() {
+        // a participant with their own start may join after the schedule's cliff, but not after their own
+        let mut curve = schedule.vesting_curve(&tranches);
+        if let Some(start) = instruction_data.start {
+            curve = curve.starting_at(start);
+        }
+        if curve.is_cliff_completed_at(schedule.now()?) {
             return Err(PinocchioError::CannotAddParticipantAfterCliff.into());
         }
 
+        if schedule.is_paused() {
+            return Err(PinocchioError::SchedulePaused.into());
+        }
+
         if schedule.authority() != accounts.authority.key() {
             return Err(ProgramError::IllegalOwner);
         }
 
+        if schedule.funder() != accounts.funder.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
         if accounts.mint.key() != schedule.mint() {
             return Err(ProgramError::InvalidAccountData);
         }
 
-        ProgramAccount::verify_seeds(
+        let bump = ProgramAccount::verify_seeds_with_bump(
             &[
                 Seed::from(b"participant"),
                 Seed::from(accounts.participant_wallet.key()),
@@ -103,71 +160,87 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AddParticipant<'a> {
         )?;
 
         AssociatedTokenAccount::check(
-            accounts.authority_ata,
-            accounts.authority,
+            accounts.funder_ata,
+            accounts.funder,
             accounts.mint,
             accounts.token_program,
         )?;
 
-        let authority_ata = TokenAccount::from_account_info(accounts.authority_ata)?;
-        if authority_ata.amount() < instruction_data.token_allocation_amount {
+        if TokenAccount::amount(accounts.funder_ata)? < instruction_data.token_allocation_amount {
             return Err(ProgramError::InsufficientFunds);
         }
 
-        AssociatedTokenAccount::check(
-            accounts.vault,
-            accounts.schedule,
-            accounts.mint,
-            accounts.token_program,
-        )?;
+        VaultAccount::check(accounts.vault, accounts.schedule)?;
 
         Ok(Self {
             accounts,
             instruction_data,
+            bump,
         })
     }
 }
 impl<'a> AddParticipant<'a> {
     pub const DISCRIMINATOR: &'a u8 = &1;
     pub fn process(&mut self) -> ProgramResult {
-        let bump_binding = [
-            // could we get the bump from verify_seeds to avoid wasting CUs on find_program_address again, find_program_address is not very performant so we should avoid calling it multiple times
-            ProgramAccount::get_bump(&[
-                Seed::from(b"participant"),
-                Seed::from(self.accounts.participant_wallet.key()),
-                Seed::from(self.accounts.schedule.key()),
-            ])?,
-        ];
+        // the bump found while verifying the address, so find_program_address only runs once
+        let bump_binding = [self.bump];
         let seeds = [
             Seed::from(b"participant"),
             Seed::from(self.accounts.participant_wallet.key()),
             Seed::from(self.accounts.schedule.key()),
             Seed::from(&bump_binding),
         ];
+        let basket_mint_count = Schedule::load(self.accounts.schedule)?.basket_mint_count();
         ProgramAccount::init::<VestedParticipant>(
             self.accounts.authority,
             self.accounts.vested_participant,
             &seeds,
-            VestedParticipant::LEN,
+            VestedParticipant::space(basket_mint_count),
         )?;
 
-        let mut vested_participant_state =
-            VestedParticipant::load_mut(self.accounts.vested_participant)?;
-        vested_participant_state.set_inner(
-            *self.accounts.schedule.key(),
-            *self.accounts.participant_wallet.key(),
-            self.instruction_data.token_allocation_amount,
-            0,
-        )?;
+        // transfer fee mints deliver less than was sent, so the allocation is whatever actually landed in the vault
+        let vault_amount_before = TokenAccount::amount(self.accounts.vault)?;
 
-        Transfer {
-            from: self.accounts.authority_ata,
-            amount: self.instruction_data.token_allocation_amount,
+        TokenTransfer {
+            from: self.accounts.funder_ata,
+            mint: self.accounts.mint,
             to: self.accounts.vault,
-            authority: self.accounts.authority,
+            authority: self.accounts.funder,
+            token_program: self.accounts.token_program,
+            remaining_accounts: self.accounts.remaining_accounts,
+            amount: self.instruction_data.token_allocation_amount,
+            decimals: MintAccount::decimals(self.accounts.mint)?,
         }
         .invoke()?;
 
+        let received_amount = TokenAccount::amount(self.accounts.vault)? - vault_amount_before;
+        if received_amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut vested_participant_state =
+            VestedParticipant::load_mut(self.accounts.vested_participant)?;
+        vested_participant_state.set_inner(VestedParticipantParams {
+            schedule: *self.accounts.schedule.key(),
+            wallet: *self.accounts.participant_wallet.key(),
+            allocated_amount: received_amount,
+            rent_payer: *self.accounts.authority.key(),
+            bump: self.bump,
+            start: self.instruction_data.start.unwrap_or(0),
+            ..Default::default()
+        })?;
+
+        Schedule::load_mut(self.accounts.schedule)?.add_participant(received_amount)?;
+
+        ParticipantAdded {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *self.accounts.vested_participant.key(),
+            participant_wallet: *self.accounts.participant_wallet.key(),
+            allocated_amount: received_amount,
+            from_merkle_leaf: 0,
+        }
+        .emit();
+
         Ok(())
     }
 }
@@ -0,0 +1,257 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    AllocationAdjusted, AssociatedTokenAccount, Event, InstructionAccount, MintAccount,
+    PinocchioError, ProgramAccount, Schedule, ScheduleSeeds, SignerAccount, TokenAccount,
+    TokenProgram, TokenTransfer, VaultAccount, VestedParticipant, BPS_DENOMINATOR,
+};
+
+pub struct AdjustAllocationAccounts<'a> {
+    pub authority: &'a AccountInfo,     //signer
+    pub funder: &'a AccountInfo,        //the schedule's funder, only has to sign a top up
+    pub funder_ata: &'a AccountInfo,    //funds a top up, receives the refund of a reduction
+    pub vault: &'a AccountInfo,
+    pub vested_participant: &'a AccountInfo,
+    pub schedule: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl AdjustAllocationAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 8] = [
+        InstructionAccount::new("authority", true, true, ""),
+        InstructionAccount::optional_signer("funder", false, "The schedule's funder, may be the authority"),
+        InstructionAccount::new("funder_ata", true, false, "Funds a top up, receives the refund of a reduction"),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for AdjustAllocationAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let ([authority, funder, funder_ata, vault, vested_participant, schedule, mint, token_program], remaining_accounts) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        Ok(Self {
+            authority,
+            funder,
+            funder_ata,
+            vault,
+            vested_participant,
+            schedule,
+            mint,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+#[repr(C, packed)]
+pub struct AdjustAllocationInstructionData {
+    // the allocation the participant should end up with, not a delta
+    pub token_allocation_amount: u64,
+}
+impl<'a> TryFrom<&'a [u8]> for AdjustAllocationInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<AdjustAllocationInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let token_allocation_amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        if token_allocation_amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            token_allocation_amount,
+        })
+    }
+}
+#[cfg(not(target_os = "solana"))]
+impl AdjustAllocationInstructionData {
+    /// Encodes the data the way `try_from` reads it, for off-chain clients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.token_allocation_amount.to_le_bytes().to_vec()
+    }
+}
+pub struct AdjustAllocation<'a> {
+    pub accounts: AdjustAllocationAccounts<'a>,
+    pub instruction_data: AdjustAllocationInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for AdjustAllocation<'a> {
+    type Error = ProgramError;
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = AdjustAllocationAccounts::try_from(accounts)?;
+        let instruction_data = AdjustAllocationInstructionData::try_from(instruction_data)?;
+
+        {
+            let schedule = Schedule::load(accounts.schedule)?;
+            let tranches = Schedule::load_tranches(accounts.schedule)?;
+
+            if schedule.is_paused() {
+                return Err(PinocchioError::SchedulePaused.into());
+            }
+
+            if schedule.authority() != accounts.authority.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if schedule.funder() != accounts.funder.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if accounts.mint.key() != schedule.mint() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+            if vested_participant.schedule() != accounts.schedule.key() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if vested_participant.is_terminated() {
+                return Err(PinocchioError::ParticipantTerminated.into());
+            }
+
+            // same window as AddParticipant, nothing can have vested on the curve yet
+            let now = schedule.now()?;
+            if schedule
+                .participant_curve(&tranches, &vested_participant)
+                .is_cliff_completed_at(now)
+            {
+                return Err(PinocchioError::CannotAddParticipantAfterCliff.into());
+            }
+
+            // an accelerated share may already be claimed, the new allocation has to vest at least that much
+            let vested_percentage = schedule.participant_vested_percentage_at(&tranches, &vested_participant, now, BPS_DENOMINATOR);
+            let vested_amount = instruction_data.token_allocation_amount
+                .checked_mul(vested_percentage as u64)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                / BPS_DENOMINATOR;
+            if vested_amount < vested_participant.claimed_amount() {
+                return Err(PinocchioError::AllocationBelowClaimed.into());
+            }
+
+            let allocated_amount = vested_participant.allocated_amount();
+            if instruction_data.token_allocation_amount == allocated_amount {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            if instruction_data.token_allocation_amount > allocated_amount {
+                SignerAccount::check(accounts.funder)?;
+
+                if TokenAccount::amount(accounts.funder_ata)?
+                    < instruction_data.token_allocation_amount - allocated_amount
+                {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+            }
+        }
+
+        AssociatedTokenAccount::check(
+            accounts.funder_ata,
+            accounts.funder,
+            accounts.mint,
+            accounts.token_program,
+        )?;
+
+        VaultAccount::check(accounts.vault, accounts.schedule)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+impl<'a> AdjustAllocation<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+    pub fn process(&mut self) -> ProgramResult {
+        let (allocated_amount, schedule_seeds) = {
+            let schedule = Schedule::load(self.accounts.schedule)?;
+            let vested_participant = VestedParticipant::load(self.accounts.vested_participant)?;
+            (vested_participant.allocated_amount(), ScheduleSeeds::from_schedule(&schedule))
+        };
+        let token_allocation_amount = self.instruction_data.token_allocation_amount;
+
+        if token_allocation_amount > allocated_amount {
+            // like AddParticipant, a transfer fee mint only adds what actually landed in the vault
+            let vault_amount_before = TokenAccount::amount(self.accounts.vault)?;
+
+            TokenTransfer {
+                from: self.accounts.funder_ata,
+                mint: self.accounts.mint,
+                to: self.accounts.vault,
+                authority: self.accounts.funder,
+                token_program: self.accounts.token_program,
+                remaining_accounts: self.accounts.remaining_accounts,
+                amount: token_allocation_amount - allocated_amount,
+                decimals: MintAccount::decimals(self.accounts.mint)?,
+            }
+            .invoke()?;
+
+            let received_amount = TokenAccount::amount(self.accounts.vault)? - vault_amount_before;
+            if received_amount == 0 {
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            VestedParticipant::load_mut(self.accounts.vested_participant)?
+                .set_allocated_amount(allocated_amount + received_amount);
+            Schedule::load_mut(self.accounts.schedule)?.reserve_outstanding_amount(received_amount)?;
+
+            self.emit_adjusted(allocated_amount, allocated_amount + received_amount);
+        } else {
+            let refund_amount = allocated_amount - token_allocation_amount;
+
+            let seeds = schedule_seeds.signer_seeds();
+            let signer = [Signer::from(seeds.as_slice())];
+
+            TokenTransfer {
+                from: self.accounts.vault,
+                mint: self.accounts.mint,
+                to: self.accounts.funder_ata,
+                authority: self.accounts.schedule,
+                token_program: self.accounts.token_program,
+                remaining_accounts: self.accounts.remaining_accounts,
+                amount: refund_amount,
+                decimals: MintAccount::decimals(self.accounts.mint)?,
+            }
+            .invoke_signed(&signer)?;
+
+            VestedParticipant::load_mut(self.accounts.vested_participant)?
+                .set_allocated_amount(token_allocation_amount);
+            Schedule::load_mut(self.accounts.schedule)?.release_outstanding_amount(refund_amount)?;
+
+            self.emit_adjusted(allocated_amount, token_allocation_amount);
+        }
+
+        Ok(())
+    }
+
+    fn emit_adjusted(&self, previous_amount: u64, allocated_amount: u64) {
+        AllocationAdjusted {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *self.accounts.vested_participant.key(),
+            authority: *self.accounts.authority.key(),
+            previous_amount,
+            allocated_amount,
+        }
+        .emit();
+    }
+}
@@ -0,0 +1,271 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, ProgramResult,
+};
+
+use crate::{
+    AssociatedTokenAccount, BatchEntryError, Event, InstructionAccount, MintAccount,
+    ParticipantAdded, PinocchioError, ProgramAccount, Schedule, SignerAccount, TokenAccount,
+    TokenProgram, TokenTransfer, VaultAccount, VestedParticipant, VestedParticipantParams,
+};
+
+pub struct BatchAddParticipantAccounts<'a> {
+    pub authority: &'a AccountInfo,     //signer, pays the participant account rent
+    pub funder: &'a AccountInfo,        //signer, the schedule's funder, may be the authority
+    pub funder_ata: &'a AccountInfo,    //funders ata
+    pub vault: &'a AccountInfo,         //vault for allocations
+    pub schedule: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub participants: &'a [AccountInfo], //(participant_wallet, vested_participant) pairs, one per amount
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl BatchAddParticipantAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 8] = [
+        InstructionAccount::new("authority", true, true, "Pays the participant account rent"),
+        InstructionAccount::new("funder", false, true, "The schedule's funder, may be the authority"),
+        InstructionAccount::new("funder_ata", true, false, ""),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("system_program", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<(&'a [AccountInfo], usize)> for BatchAddParticipantAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(
+        (accounts, participant_count): (&'a [AccountInfo], usize),
+    ) -> Result<Self, Self::Error> {
+        let ([authority, funder, funder_ata, vault, schedule, mint, system_program, token_program], rest) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        if rest.len() < 2 * participant_count {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (participants, remaining_accounts) = rest.split_at(2 * participant_count);
+
+        SignerAccount::check(authority)?;
+        SignerAccount::check(funder)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        ProgramAccount::check_program(system_program, &pinocchio_system::ID)?;
+        MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        Ok(Self {
+            authority,
+            funder,
+            funder_ata,
+            vault,
+            schedule,
+            mint,
+            system_program,
+            token_program,
+            participants,
+            remaining_accounts,
+        })
+    }
+}
+pub struct BatchAddParticipantInstructionData<'a> {
+    pub token_allocation_amounts: &'a [u8], //u64 per participant
+    pub total_amount: u64,
+}
+impl<'a> BatchAddParticipantInstructionData<'a> {
+    #[inline(always)]
+    pub fn participant_count(&self) -> usize {
+        self.token_allocation_amounts.len() / size_of::<u64>()
+    }
+    #[inline(always)]
+    pub fn amount(&self, index: usize) -> u64 {
+        let offset = index * size_of::<u64>();
+        u64::from_le_bytes(
+            self.token_allocation_amounts[offset..offset + size_of::<u64>()]
+                .try_into()
+                .unwrap(),
+        )
+    }
+}
+impl<'a> TryFrom<&'a [u8]> for BatchAddParticipantInstructionData<'a> {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.is_empty() || !data.len().is_multiple_of(size_of::<u64>()) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut instruction_data = Self {
+            token_allocation_amounts: data,
+            total_amount: 0,
+        };
+
+        for index in 0..instruction_data.participant_count() {
+            let amount = instruction_data.amount(index);
+            if amount == 0 {
+                return Err(BatchEntryError(index).into());
+            }
+            instruction_data.total_amount = instruction_data
+                .total_amount
+                .checked_add(amount)
+                .ok_or(BatchEntryError(index))?;
+        }
+
+        Ok(instruction_data)
+    }
+}
+#[cfg(not(target_os = "solana"))]
+impl BatchAddParticipantInstructionData<'_> {
+    /// Encodes one amount per participant the way `try_from` reads them, for off-chain clients.
+    pub fn pack(amounts: &[u64]) -> Vec<u8> {
+        amounts.iter().flat_map(|amount| amount.to_le_bytes()).collect()
+    }
+}
+pub struct BatchAddParticipant<'a> {
+    pub accounts: BatchAddParticipantAccounts<'a>,
+    pub instruction_data: BatchAddParticipantInstructionData<'a>,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for BatchAddParticipant<'a> {
+    type Error = ProgramError;
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let instruction_data = BatchAddParticipantInstructionData::try_from(instruction_data)?;
+        let accounts =
+            BatchAddParticipantAccounts::try_from((accounts, instruction_data.participant_count()))?;
+
+        {
+            let schedule = Schedule::load(accounts.schedule)?;
+            let tranches = Schedule::load_tranches(accounts.schedule)?;
+
+            if schedule.is_cliff_completed(&tranches)? {
+                return Err(PinocchioError::CannotAddParticipantAfterCliff.into());
+            }
+
+            if schedule.is_paused() {
+                return Err(PinocchioError::SchedulePaused.into());
+            }
+
+            if schedule.authority() != accounts.authority.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if schedule.funder() != accounts.funder.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if accounts.mint.key() != schedule.mint() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+        }
+
+        AssociatedTokenAccount::check(
+            accounts.funder_ata,
+            accounts.funder,
+            accounts.mint,
+            accounts.token_program,
+        )?;
+
+        if TokenAccount::amount(accounts.funder_ata)? < instruction_data.total_amount {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        VaultAccount::check(accounts.vault, accounts.schedule)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+impl<'a> BatchAddParticipant<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &9;
+    pub fn process(&mut self) -> ProgramResult {
+        // one transfer for the whole batch instead of one per participant
+        let vault_amount_before = TokenAccount::amount(self.accounts.vault)?;
+
+        TokenTransfer {
+            from: self.accounts.funder_ata,
+            mint: self.accounts.mint,
+            to: self.accounts.vault,
+            authority: self.accounts.funder,
+            token_program: self.accounts.token_program,
+            remaining_accounts: self.accounts.remaining_accounts,
+            amount: self.instruction_data.total_amount,
+            decimals: MintAccount::decimals(self.accounts.mint)?,
+        }
+        .invoke()?;
+
+        let received_amount = TokenAccount::amount(self.accounts.vault)? - vault_amount_before;
+
+        // any failing entry aborts the whole batch, the error code points at it
+        for index in 0..self.instruction_data.participant_count() {
+            self.add_participant(index, received_amount)
+                .map_err(|_| BatchEntryError(index))?;
+        }
+
+        Ok(())
+    }
+
+    fn add_participant(&self, index: usize, received_amount: u64) -> ProgramResult {
+        let participant_wallet = &self.accounts.participants[2 * index];
+        let vested_participant = &self.accounts.participants[2 * index + 1];
+
+        // a wallet listed twice would find its account already created by the earlier entry
+        if vested_participant.lamports() != 0 {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let bump = ProgramAccount::verify_seeds_with_bump(
+            &[
+                Seed::from(b"participant"),
+                Seed::from(participant_wallet.key()),
+                Seed::from(self.accounts.schedule.key()),
+            ],
+            vested_participant,
+        )?;
+        let bump_binding = [bump];
+        let seeds = [
+            Seed::from(b"participant"),
+            Seed::from(participant_wallet.key()),
+            Seed::from(self.accounts.schedule.key()),
+            Seed::from(&bump_binding),
+        ];
+        let basket_mint_count = Schedule::load(self.accounts.schedule)?.basket_mint_count();
+        ProgramAccount::init::<VestedParticipant>(
+            self.accounts.authority,
+            vested_participant,
+            &seeds,
+            VestedParticipant::space(basket_mint_count),
+        )?;
+
+        // transfer fee mints deliver less than was sent, split what landed in the vault pro rata, rounding dust stays in the vault
+        let allocated_amount = ((self.instruction_data.amount(index) as u128)
+            .checked_mul(received_amount as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            / self.instruction_data.total_amount as u128) as u64;
+        if allocated_amount == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        VestedParticipant::load_mut(vested_participant)?.set_inner(VestedParticipantParams {
+            schedule: *self.accounts.schedule.key(),
+            wallet: *participant_wallet.key(),
+            allocated_amount,
+            rent_payer: *self.accounts.authority.key(),
+            bump,
+            ..Default::default()
+        })?;
+
+        Schedule::load_mut(self.accounts.schedule)?.add_participant(allocated_amount)?;
+
+        ParticipantAdded {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *vested_participant.key(),
+            participant_wallet: *participant_wallet.key(),
+            allocated_amount,
+            from_merkle_leaf: 0,
+        }
+        .emit();
+
+        Ok(())
+    }
+}
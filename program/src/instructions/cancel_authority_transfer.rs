@@ -0,0 +1,63 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult};
+
+use crate::{AuthorityTransferCancelled, Event, InstructionAccount, PinocchioError, ProgramAccount, Schedule, SignerAccount};
+
+pub struct CancelAuthorityTransferAccounts<'a> {
+    pub authority: &'a AccountInfo, //signer
+    pub schedule: &'a AccountInfo,
+}
+impl CancelAuthorityTransferAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 2] = [
+        InstructionAccount::new("authority", false, true, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelAuthorityTransferAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, schedule] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        Ok(Self {
+            authority,
+            schedule,
+        })
+    }
+}
+pub struct CancelAuthorityTransfer<'a> {
+    pub accounts: CancelAuthorityTransferAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for CancelAuthorityTransfer<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CancelAuthorityTransferAccounts::try_from(accounts)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        if schedule.authority() != accounts.authority.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+        if !schedule.has_pending_authority() {
+            return Err(PinocchioError::NoPendingAuthority.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> CancelAuthorityTransfer<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &6;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.set_pending_authority(Pubkey::default());
+
+        AuthorityTransferCancelled {
+            schedule: *self.accounts.schedule.key(),
+            authority: *self.accounts.authority.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
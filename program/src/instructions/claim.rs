@@ -1,12 +1,12 @@
 use std::ops::{Div, Mul};
 
 use pinocchio::{ProgramResult, account_info::AccountInfo, instruction::{Seed, Signer}, program_error::ProgramError};
-use pinocchio_token::{instructions::Transfer, state::TokenAccount};
 
-use crate::{AssociatedTokenAccount, MintAccount, PinocchioError, ProgramAccount, Schedule, SignerAccount, VestedParticipant};
+use crate::{merkle_leaf, verify_merkle_proof, AssociatedTokenAccount, BasketLegAccounts, Claimed, Event, InstructionAccount, MintAccount, ParticipantAdded, ParticipantClosed, PinocchioError, ProgramAccount, Schedule, ScheduleSeeds, SignerAccount, TokenAccount, TokenProgram, TokenTransfer, VaultAccount, VestedParticipant, VestedParticipantParams, BPS_DENOMINATOR, MERKLE_NODE_LEN};
 
 pub struct ClaimAccounts<'a> {
-    pub participant_wallet: &'a AccountInfo, //signer 
+    pub participant_wallet: &'a AccountInfo, //signer, unless a keeper cranks the claim
+    pub payer: &'a AccountInfo, //pays ata rent, the participant itself or a keeper
     pub vested_participant: &'a AccountInfo, //state acc
     pub participant_ata: &'a AccountInfo, //claimers ata
     pub vault: &'a AccountInfo, //vault for sending from
@@ -15,52 +15,213 @@ pub struct ClaimAccounts<'a> {
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
     pub associated_token_account_program: &'a AccountInfo,
+    pub basket_legs: Vec<BasketLegAccounts<'a>>, //one group per basket mint, paying into the participant's atas
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl ClaimAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 9] = [
+        InstructionAccount::new("participant_wallet", true, true, ""),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("participant_ata", true, false, ""),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("system_program", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+        InstructionAccount::new("associated_token_program", false, false, ""),
+    ];
 }
 impl<'a> TryFrom<&'a [AccountInfo]> for ClaimAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [
-            participant_wallet,
-            vested_participant,
-            participant_ata,
-            vault,
-            schedule,
-            mint,
-            system_program,
-            token_program,
-            associated_token_account_program
-        ] = accounts else {
-            return Err(ProgramError::NotEnoughAccountKeys)
-        };
+        let (
+            [
+                participant_wallet,
+                vested_participant,
+                participant_ata,
+                vault,
+                schedule,
+                mint,
+                system_program,
+                token_program,
+                associated_token_account_program,
+            ],
+            remaining_accounts,
+        ) = InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
 
         SignerAccount::check(participant_wallet)?;
-        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
         ProgramAccount::check::<Schedule>(schedule)?;
         MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        Ok(Self { participant_wallet, payer: participant_wallet, vested_participant, participant_ata, vault, schedule, mint, system_program, token_program, associated_token_account_program, basket_legs: Vec::new(), remaining_accounts })
+    }
+}
+// bits of the leading flags byte
+pub const CLAIM_FLAG_CLOSE_WHEN_FINALIZED: u8 = 1 << 0;
+pub const CLAIM_FLAG_AMOUNT: u8 = 1 << 1;
+
+pub struct ClaimInstructionData<'a> {
+    // when set, the participant account is closed once fully claimed and the rent payer is expected as the first remaining account
+    pub close_when_finalized: bool,
+    // withdraw only this much of the vested but unclaimed amount, everything available when unset
+    pub amount: Option<u64>,
+    // merkle recipients pass their leaf allocation and proof, the first claim creates their participant record
+    pub merkle_allocation: Option<u64>,
+    pub merkle_proof: &'a [u8],
+}
+impl<'a> TryFrom<&'a [u8]> for ClaimInstructionData<'a> {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        // empty data keeps the original no-argument claim working
+        let (flags, data) = data.split_first().unwrap_or((&0, &[]));
+        if flags & !(CLAIM_FLAG_CLOSE_WHEN_FINALIZED | CLAIM_FLAG_AMOUNT) != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let close_when_finalized = flags & CLAIM_FLAG_CLOSE_WHEN_FINALIZED != 0;
+
+        let (amount, merkle_data) = if flags & CLAIM_FLAG_AMOUNT != 0 {
+            let Some((amount, merkle_data)) = data.split_first_chunk::<8>() else {
+                return Err(ProgramError::InvalidInstructionData);
+            };
+            let amount = u64::from_le_bytes(*amount);
+            if amount == 0 {
+                return Err(PinocchioError::ClaimAmountInvalid.into());
+            }
+            (Some(amount), merkle_data)
+        } else {
+            (None, data)
+        };
 
-        Ok(Self { participant_wallet, vested_participant, participant_ata, vault, schedule, mint, system_program, token_program, associated_token_account_program })
+        let (merkle_allocation, merkle_proof) = match merkle_data {
+            [] => (None, merkle_data),
+            leaf if leaf.len() >= 8 && (leaf.len() - 8).is_multiple_of(MERKLE_NODE_LEN) => (
+                Some(u64::from_le_bytes(leaf[0..8].try_into().unwrap())),
+                &leaf[8..],
+            ),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self {
+            close_when_finalized,
+            amount,
+            merkle_allocation,
+            merkle_proof,
+        })
+    }
+}
+#[cfg(not(target_os = "solana"))]
+impl ClaimInstructionData<'_> {
+    /// Encodes the data the way `try_from` reads it, for off-chain clients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut flags = 0;
+        if self.close_when_finalized {
+            flags |= CLAIM_FLAG_CLOSE_WHEN_FINALIZED;
+        }
+        if self.amount.is_some() {
+            flags |= CLAIM_FLAG_AMOUNT;
+        }
+        // the original no-argument claim stays empty
+        if flags == 0 && self.merkle_allocation.is_none() {
+            return Vec::new();
+        }
+
+        let mut data = vec![flags];
+        if let Some(amount) = self.amount {
+            data.extend_from_slice(&amount.to_le_bytes());
+        }
+        if let Some(allocation) = self.merkle_allocation {
+            data.extend_from_slice(&allocation.to_le_bytes());
+            data.extend_from_slice(self.merkle_proof);
+        }
+        data
     }
 }
 pub struct Claim<'a> {
     pub accounts: ClaimAccounts<'a>,
+    pub instruction_data: ClaimInstructionData<'a>,
+    pub rent_payer: Option<&'a AccountInfo>,
 }
-impl<'a> TryFrom<&'a [AccountInfo]> for Claim<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Claim<'a> {
     type Error = ProgramError;
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
         let accounts = ClaimAccounts::try_from(accounts)?;
+        let instruction_data = ClaimInstructionData::try_from(instruction_data)?;
+
+        Self::new(accounts, instruction_data)
+    }
+}
+impl<'a> Claim<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    // shared by Claim and CrankClaim once their accounts are parsed
+    pub fn new(mut accounts: ClaimAccounts<'a>, instruction_data: ClaimInstructionData<'a>) -> Result<Self, ProgramError> {
+        // nothing leaves the vault while paused, vesting keeps accruing so the claim simply waits
+        if Schedule::load(accounts.schedule)?.is_paused() {
+            return Err(PinocchioError::SchedulePaused.into());
+        }
+
+        // the proof is only needed once, later claims may keep sending it.
+        // Ownership tells whether the record exists, its address may hold lamports anyone sent it
+        if let Some(allocation) = instruction_data.merkle_allocation {
+            if !accounts.vested_participant.is_owned_by(&crate::ID) {
+                Self::create_merkle_participant(&accounts, allocation, instruction_data.merkle_proof)?;
+            }
+        }
+
+        ProgramAccount::check::<VestedParticipant>(accounts.vested_participant)?;
+
+        let rent_payer = if instruction_data.close_when_finalized {
+            let [rent_payer, remaining_accounts @ ..] = accounts.remaining_accounts else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+            if vested_participant.is_from_merkle_leaf() {
+                return Err(PinocchioError::ParticipantNotClosable.into());
+            }
+            if vested_participant.rent_payer() != rent_payer.key() {
+                return Err(PinocchioError::InvalidAddress.into());
+            }
+            accounts.remaining_accounts = remaining_accounts;
+            Some(rent_payer)
+        } else {
+            None
+        };
+
+        // basket legs follow the rent payer, the transfer hook accounts come last and only serve the primary mint
+        let (basket_legs, remaining_accounts) = {
+            let schedule = Schedule::load(accounts.schedule)?;
+            BasketLegAccounts::split(accounts.remaining_accounts, &schedule)?
+        };
+        accounts.basket_legs = basket_legs;
+        accounts.remaining_accounts = remaining_accounts;
 
         {
             let schedule = Schedule::load(accounts.schedule)?;
-            if !schedule.is_cliff_completed() {
+            let tranches = Schedule::load_tranches(accounts.schedule)?;
+            let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+            // an accelerated share is claimable straight away, cliff or not
+            if !vested_participant.is_accelerated()
+                && !schedule
+                    .participant_curve(&tranches, &vested_participant)
+                    .is_cliff_completed_at(schedule.now()?)
+            {
                 return Err(PinocchioError::CannotClaimBeforeCliff.into());
             }
 
-            let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
             if accounts.mint.key() != schedule.mint() || accounts.schedule.key() != vested_participant.schedule() {
                 return Err(ProgramError::InvalidAccountData);
             }
             
-            if vested_participant.is_claim_finalized() {
+            // a basket leg can still be owed after the primary mint is done, e.g. when revoke floored them differently
+            if vested_participant.is_claim_finalized()
+                && VestedParticipant::load_basket_legs(accounts.vested_participant)?
+                    .iter()
+                    .all(|leg| leg.is_claim_finalized())
+            {
                 return Err(PinocchioError::CannotDoubleClaim.into());
             }
             if *vested_participant.participant() != *accounts.participant_wallet.key() {
@@ -71,90 +232,267 @@ impl<'a> TryFrom<&'a [AccountInfo]> for Claim<'a> {
             }
         }
 
-        AssociatedTokenAccount::check(
-            accounts.vault, 
-            accounts.schedule, 
-            accounts.mint, 
-            accounts.token_program
-        )?;
+        VaultAccount::check(accounts.vault, accounts.schedule)?;
 
         AssociatedTokenAccount::init_if_needed(
             accounts.participant_ata,
             accounts.mint,
-            accounts.participant_wallet,
+            accounts.payer,
             accounts.participant_wallet,
             accounts.system_program,
             accounts.token_program,
         )?;
 
-        ProgramAccount::verify_seeds(
+        for basket_leg in &accounts.basket_legs {
+            AssociatedTokenAccount::init_if_needed(
+                basket_leg.token_account,
+                basket_leg.mint,
+                accounts.payer,
+                accounts.participant_wallet,
+                accounts.system_program,
+                basket_leg.token_program,
+            )?;
+        }
+
+        // the record stores its own bump, so no bump search is needed to check its address
+        let bump_binding = [VestedParticipant::load(accounts.vested_participant)?.bump()];
+        ProgramAccount::verify_address(
             &[
-                Seed::from(b"participant"), 
+                Seed::from(b"participant"),
                 Seed::from(accounts.participant_wallet.key()),
                 Seed::from(accounts.schedule.key()),
-            ], 
-            accounts.vested_participant, 
+                Seed::from(&bump_binding),
+            ],
+            accounts.vested_participant,
         )?;
 
-        Ok(Self { accounts })
+        Ok(Self { accounts, instruction_data, rent_payer })
+    }
+
+    fn create_merkle_participant(accounts: &ClaimAccounts, allocation: u64, proof: &[u8]) -> ProgramResult {
+        {
+            let schedule = Schedule::load(accounts.schedule)?;
+            if !schedule.has_merkle_root()
+                || !verify_merkle_proof(
+                    schedule.merkle_root(),
+                    merkle_leaf(accounts.schedule.key(), accounts.participant_wallet.key(), allocation),
+                    proof,
+                )
+            {
+                return Err(PinocchioError::MerkleProofInvalid.into());
+            }
+            // past the deadline the unclaimed leaves belong to the funder again
+            if schedule.now()? > schedule.merkle_claim_deadline() {
+                return Err(PinocchioError::MerkleClaimExpired.into());
+            }
+        }
+
+        let bump = ProgramAccount::verify_seeds_with_bump(
+            &[
+                Seed::from(b"participant"),
+                Seed::from(accounts.participant_wallet.key()),
+                Seed::from(accounts.schedule.key()),
+            ],
+            accounts.vested_participant,
+        )?;
+        let bump_binding = [bump];
+        let seeds = [
+            Seed::from(b"participant"),
+            Seed::from(accounts.participant_wallet.key()),
+            Seed::from(accounts.schedule.key()),
+            Seed::from(&bump_binding),
+        ];
+
+        // the claimer pays for their own record, the authority never pre-creates anything
+        let basket_mint_count = Schedule::load(accounts.schedule)?.basket_mint_count();
+        ProgramAccount::init::<VestedParticipant>(
+            accounts.participant_wallet,
+            accounts.vested_participant,
+            &seeds,
+            VestedParticipant::space(basket_mint_count),
+        )?;
+
+        // the allocation was already reserved as outstanding by SetMerkleRoot, and the record is never closed so it isn't counted as a participant.
+        // It leaves the merkle total, which only covers unclaimed leaves
+        {
+            let mut schedule = Schedule::load_mut(accounts.schedule)?;
+            let merkle_total = schedule.merkle_total().checked_sub(allocation).ok_or(ProgramError::InsufficientFunds)?;
+            schedule.set_merkle_total(merkle_total);
+        }
+
+        VestedParticipant::load_mut(accounts.vested_participant)?.set_inner(VestedParticipantParams {
+            schedule: *accounts.schedule.key(),
+            wallet: *accounts.participant_wallet.key(),
+            allocated_amount: allocation,
+            rent_payer: *accounts.participant_wallet.key(),
+            from_merkle_leaf: true,
+            bump,
+            ..Default::default()
+        })?;
+
+        ParticipantAdded {
+            schedule: *accounts.schedule.key(),
+            vested_participant: *accounts.vested_participant.key(),
+            participant_wallet: *accounts.participant_wallet.key(),
+            allocated_amount: allocation,
+            from_merkle_leaf: 1,
+        }
+        .emit();
+
+        Ok(())
     }
-}
-impl<'a> Claim<'a> {
-    pub const DISCRIMINATOR: &'a u8 = &2;
-    pub fn process(&mut self) -> ProgramResult {
-        let (claim_amount, seed) = {
-            const BPS_DENOMINATOR: u64 = 10_000;     
 
+    pub fn process(&mut self) -> ProgramResult {
+        let (claim_amount, leg_claim_amounts, vested_percentage, timestamp, schedule_seeds) = {
             let schedule = Schedule::load(self.accounts.schedule)?;
+            let timestamp = schedule.now()?;
+            let tranches = Schedule::load_tranches(self.accounts.schedule)?;
             let vested_participant = VestedParticipant::load(self.accounts.vested_participant)?;
             
+            // a revoked participant's allocation was already cut down to what was vested at termination
+            let vested_percentage = if vested_participant.is_terminated() {
+                BPS_DENOMINATOR as i64
+            } else {
+                schedule.participant_vested_percentage_at(&tranches, &vested_participant, timestamp, BPS_DENOMINATOR)
+            };
+
             let possible_claim_amount = vested_participant.allocated_amount()
-                    .mul(schedule.steps_passed_percentage(BPS_DENOMINATOR) as u64)
+                    .mul(vested_percentage as u64)
                     .div(BPS_DENOMINATOR);
             
-            let claim_amount = possible_claim_amount - vested_participant.claimed_amount();
-            if claim_amount == 0 {
+            // never underflows while AdjustAllocation keeps the vested share above what was claimed, saturating all the same
+            let available_amount = possible_claim_amount.saturating_sub(vested_participant.claimed_amount());
+
+            // basket mints vest on the same curve and always pay out everything available, a requested amount only applies to the primary mint
+            let leg_claim_amounts = VestedParticipant::load_basket_legs(self.accounts.vested_participant)?
+                .iter()
+                .map(|leg| {
+                    leg.allocated_amount()
+                        .mul(vested_percentage as u64)
+                        .div(BPS_DENOMINATOR)
+                        .saturating_sub(leg.claimed_amount())
+                })
+                .collect::<Vec<u64>>();
+            if leg_claim_amounts.len() != self.accounts.basket_legs.len() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // each mint rounds on its own, so a leg may still be owed when the primary mint has nothing left and the other way round
+            if available_amount == 0 && leg_claim_amounts.iter().all(|amount| *amount == 0) {
                 return Err(PinocchioError::ClaimAmountInvalid.into());
             }
 
-            (claim_amount, schedule.seed())
+            let claim_amount = match self.instruction_data.amount {
+                Some(amount) if amount > available_amount => {
+                    return Err(PinocchioError::ClaimAmountUnavailable.into());
+                }
+                Some(amount) => amount,
+                None => available_amount,
+            };
+
+            (claim_amount, leg_claim_amounts, vested_percentage, timestamp, ScheduleSeeds::from_schedule(&schedule))
         };
 
-        {
-            let vault = TokenAccount::from_account_info(self.accounts.vault)?;
-            if vault.amount() < claim_amount {
-                return Err(ProgramError::InsufficientFunds);
-            }
+        if TokenAccount::amount(self.accounts.vault)? < claim_amount {
+            return Err(ProgramError::InsufficientFunds);
         }
 
-        let seed_binding = seed.to_le_bytes();
-        let bump = ProgramAccount::get_bump(&[
-            Seed::from(b"schedule"),
-            Seed::from(&seed_binding),
-            ])?;
-        let bump_binding = [bump];
-        let seeds = [
-            Seed::from(b"schedule"),
-            Seed::from(&seed_binding),
-            Seed::from(&bump_binding)
-        ];
-        let signer = [Signer::from(&seeds)];
+        let seeds = schedule_seeds.signer_seeds();
+        let signer = [Signer::from(seeds.as_slice())];
 
-        Transfer {
-            from: self.accounts.vault,
-            amount: claim_amount,
-            to: self.accounts.participant_ata,
-            authority: self.accounts.schedule,
-        }.invoke_signed(&signer)?;
+        let is_claim_finalized = if claim_amount > 0 {
+            TokenTransfer {
+                from: self.accounts.vault,
+                mint: self.accounts.mint,
+                to: self.accounts.participant_ata,
+                authority: self.accounts.schedule,
+                token_program: self.accounts.token_program,
+                remaining_accounts: self.accounts.remaining_accounts,
+                amount: claim_amount,
+                decimals: MintAccount::decimals(self.accounts.mint)?,
+            }.invoke_signed(&signer)?;
 
-        let mut vested_participant = VestedParticipant::load_mut(self.accounts.vested_participant)?;
-        
-        let total_claimed_amount = vested_participant.claimed_amount() + claim_amount;
-        if total_claimed_amount > vested_participant.allocated_amount() {
-            return Err(PinocchioError::ClaimAmountOverflow.into());
+            let mut vested_participant = VestedParticipant::load_mut(self.accounts.vested_participant)?;
+            
+            // claimed_amount tracks what left the vault, any transfer fee is withheld from what the participant receives
+            let total_claimed_amount = vested_participant.claimed_amount() + claim_amount;
+            if total_claimed_amount > vested_participant.allocated_amount() {
+                return Err(PinocchioError::ClaimAmountOverflow.into());
+            }
+
+            vested_participant.set_claimed_amount(total_claimed_amount);
+
+            Claimed {
+                schedule: *self.accounts.schedule.key(),
+                vested_participant: *self.accounts.vested_participant.key(),
+                participant_wallet: *self.accounts.participant_wallet.key(),
+                mint: *self.accounts.mint.key(),
+                amount: claim_amount,
+                claimed_amount: total_claimed_amount,
+                vested_bps: vested_percentage,
+                timestamp,
+            }
+            .emit();
+
+            vested_participant.is_claim_finalized()
+        } else {
+            VestedParticipant::load(self.accounts.vested_participant)?.is_claim_finalized()
+        };
+
+        for ((index, basket_leg), leg_claim_amount) in self.accounts.basket_legs.iter().enumerate().zip(leg_claim_amounts) {
+            if leg_claim_amount == 0 {
+                continue;
+            }
+
+            // transfer hook accounts only serve the primary mint
+            TokenTransfer {
+                from: basket_leg.vault,
+                mint: basket_leg.mint,
+                to: basket_leg.token_account,
+                authority: self.accounts.schedule,
+                token_program: basket_leg.token_program,
+                remaining_accounts: &[],
+                amount: leg_claim_amount,
+                decimals: MintAccount::decimals(basket_leg.mint)?,
+            }.invoke_signed(&signer)?;
+
+            let mut basket_legs = VestedParticipant::load_basket_legs_mut(self.accounts.vested_participant)?;
+            let claimed_amount = basket_legs[index].claimed_amount() + leg_claim_amount;
+            basket_legs[index].set_claimed_amount(claimed_amount);
+
+            Claimed {
+                schedule: *self.accounts.schedule.key(),
+                vested_participant: *self.accounts.vested_participant.key(),
+                participant_wallet: *self.accounts.participant_wallet.key(),
+                mint: *basket_leg.mint.key(),
+                amount: leg_claim_amount,
+                claimed_amount,
+                vested_bps: vested_percentage,
+                timestamp,
+            }
+            .emit();
         }
 
-        vested_participant.set_claimed_amount(total_claimed_amount);
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.release_outstanding_amount(claim_amount)?;
+
+        // the legs may finish apart from the primary mint, the account only goes away once every mint is paid out
+        let is_claim_finalized = is_claim_finalized
+            && VestedParticipant::load_basket_legs(self.accounts.vested_participant)?
+                .iter()
+                .all(|leg| leg.is_claim_finalized());
+
+        if let (true, Some(rent_payer)) = (is_claim_finalized, self.rent_payer) {
+            schedule.remove_participant()?;
+            ProgramAccount::close(self.accounts.vested_participant, rent_payer)?;
+
+            ParticipantClosed {
+                schedule: *self.accounts.schedule.key(),
+                vested_participant: *self.accounts.vested_participant.key(),
+                rent_payer: *rent_payer.key(),
+            }
+            .emit();
+        }
 
         Ok(())
     }
@@ -0,0 +1,79 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Event, InstructionAccount, ParticipantClosed, PinocchioError, ProgramAccount, Schedule, VestedParticipant};
+
+pub struct CloseParticipantAccounts<'a> {
+    pub vested_participant: &'a AccountInfo, //state acc
+    pub schedule: &'a AccountInfo,
+    pub rent_payer: &'a AccountInfo,         //whoever paid rent in AddParticipant
+}
+impl CloseParticipantAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 3] = [
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("rent_payer", true, false, "Whoever paid the rent in AddParticipant"),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseParticipantAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [vested_participant, schedule, rent_payer] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        Ok(Self {
+            vested_participant,
+            schedule,
+            rent_payer,
+        })
+    }
+}
+pub struct CloseParticipant<'a> {
+    pub accounts: CloseParticipantAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseParticipant<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CloseParticipantAccounts::try_from(accounts)?;
+
+        // no signer needed, the lamports can only ever go back to the original payer
+        let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+        if !vested_participant.is_claim_finalized()
+            || !VestedParticipant::load_basket_legs(accounts.vested_participant)?
+                .iter()
+                .all(|leg| leg.is_claim_finalized())
+        {
+            return Err(PinocchioError::ClaimNotFinalized.into());
+        }
+        // closing would free the pda and let the same merkle leaf be claimed again
+        if vested_participant.is_from_merkle_leaf() {
+            return Err(PinocchioError::ParticipantNotClosable.into());
+        }
+        if vested_participant.schedule() != accounts.schedule.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if vested_participant.rent_payer() != accounts.rent_payer.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> CloseParticipant<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &7;
+    pub fn process(&mut self) -> ProgramResult {
+        Schedule::load_mut(self.accounts.schedule)?.remove_participant()?;
+        ProgramAccount::close(self.accounts.vested_participant, self.accounts.rent_payer)?;
+
+        ParticipantClosed {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *self.accounts.vested_participant.key(),
+            rent_payer: *self.accounts.rent_payer.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    BasketLegAccounts, Event, InstructionAccount, MintAccount, PinocchioError, ProgramAccount,
+    Schedule, ScheduleClosed, ScheduleSeeds, SignerAccount, TokenAccount, TokenCloseAccount,
+    TokenHarvestWithheldTokensToMint, TokenProgram, TokenTransfer, VaultAccount,
+};
+
+pub struct CloseScheduleAccounts<'a> {
+    pub authority: &'a AccountInfo,     //signer, receives the rent
+    pub funder_token_account: &'a AccountInfo, //a token account of the funder, receives any dust left in the vault
+    pub vault: &'a AccountInfo,
+    pub schedule: &'a AccountInfo,
+    pub mint: &'a AccountInfo, //writable, withheld transfer fees are harvested to it
+    pub token_program: &'a AccountInfo,
+    pub basket_legs: Vec<BasketLegAccounts<'a>>, //one group per basket mint, dust goes to a funder's token account of that mint, mints writable as above
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl CloseScheduleAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 6] = [
+        InstructionAccount::new("authority", true, true, "Receives the rent"),
+        InstructionAccount::new("funder_token_account", true, false, "A token account of the funder, receives any dust left in the vault"),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", true, false, "Receives any transfer fees withheld on the vault"),
+        InstructionAccount::new("token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseScheduleAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let ([authority, funder_token_account, vault, schedule, mint, token_program], remaining_accounts) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        Ok(Self {
+            authority,
+            funder_token_account,
+            vault,
+            schedule,
+            mint,
+            token_program,
+            basket_legs: Vec::new(),
+            remaining_accounts,
+        })
+    }
+}
+pub struct CloseSchedule<'a> {
+    pub accounts: CloseScheduleAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseSchedule<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut accounts = CloseScheduleAccounts::try_from(accounts)?;
+
+        {
+            let schedule = Schedule::load(accounts.schedule)?;
+            if schedule.authority() != accounts.authority.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if accounts.mint.key() != schedule.mint() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // participants have to be fully claimed and closed first, otherwise their accounts would point to a dead schedule
+            if schedule.has_outstanding_allocations() {
+                return Err(PinocchioError::ScheduleHasOutstandingAllocations.into());
+            }
+
+            // every basket vault is emptied and closed along with the schedule
+            let (basket_legs, remaining_accounts) =
+                BasketLegAccounts::split(accounts.remaining_accounts, &schedule)?;
+            // dust is the funder's like any other unallocated tokens
+            if TokenAccount::mint(accounts.funder_token_account)? != *schedule.mint() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if TokenAccount::owner(accounts.funder_token_account)? != *schedule.funder() {
+                return Err(PinocchioError::InvalidAddress.into());
+            }
+            for basket_leg in &basket_legs {
+                if TokenAccount::mint(basket_leg.token_account)? != *basket_leg.mint.key() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                if TokenAccount::owner(basket_leg.token_account)? != *schedule.funder() {
+                    return Err(PinocchioError::InvalidAddress.into());
+                }
+            }
+            accounts.basket_legs = basket_legs;
+            accounts.remaining_accounts = remaining_accounts;
+        }
+
+        VaultAccount::check(accounts.vault, accounts.schedule)?;
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> CloseSchedule<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &8;
+    pub fn process(&mut self) -> ProgramResult {
+        let schedule_seeds = ScheduleSeeds::from_schedule(&*Schedule::load(self.accounts.schedule)?);
+        let seeds = schedule_seeds.signer_seeds();
+        let signer = [Signer::from(seeds.as_slice())];
+
+        // a token account can only be closed once empty
+        let dust_amount = TokenAccount::amount(self.accounts.vault)?;
+        if dust_amount > 0 {
+            TokenTransfer {
+                from: self.accounts.vault,
+                mint: self.accounts.mint,
+                to: self.accounts.funder_token_account,
+                authority: self.accounts.schedule,
+                token_program: self.accounts.token_program,
+                remaining_accounts: self.accounts.remaining_accounts,
+                amount: dust_amount,
+                decimals: MintAccount::decimals(self.accounts.mint)?,
+            }
+            .invoke_signed(&signer)?;
+        }
+
+        // token-2022 refuses to close a vault that still holds withheld transfer fees
+        if TokenAccount::withheld_amount(self.accounts.vault)? > 0 {
+            TokenHarvestWithheldTokensToMint {
+                mint: self.accounts.mint,
+                account: self.accounts.vault,
+                token_program: self.accounts.token_program,
+            }
+            .invoke()?;
+        }
+
+        TokenCloseAccount {
+            account: self.accounts.vault,
+            destination: self.accounts.authority,
+            authority: self.accounts.schedule,
+            token_program: self.accounts.token_program,
+        }
+        .invoke_signed(&signer)?;
+
+        // transfer hook accounts only serve the primary mint
+        for basket_leg in &self.accounts.basket_legs {
+            let dust_amount = TokenAccount::amount(basket_leg.vault)?;
+            if dust_amount > 0 {
+                TokenTransfer {
+                    from: basket_leg.vault,
+                    mint: basket_leg.mint,
+                    to: basket_leg.token_account,
+                    authority: self.accounts.schedule,
+                    token_program: basket_leg.token_program,
+                    remaining_accounts: &[],
+                    amount: dust_amount,
+                    decimals: MintAccount::decimals(basket_leg.mint)?,
+                }
+                .invoke_signed(&signer)?;
+            }
+
+            if TokenAccount::withheld_amount(basket_leg.vault)? > 0 {
+                TokenHarvestWithheldTokensToMint {
+                    mint: basket_leg.mint,
+                    account: basket_leg.vault,
+                    token_program: basket_leg.token_program,
+                }
+                .invoke()?;
+            }
+
+            TokenCloseAccount {
+                account: basket_leg.vault,
+                destination: self.accounts.authority,
+                authority: self.accounts.schedule,
+                token_program: basket_leg.token_program,
+            }
+            .invoke_signed(&signer)?;
+        }
+
+        ProgramAccount::close(self.accounts.schedule, self.accounts.authority)?;
+
+        ScheduleClosed {
+            schedule: *self.accounts.schedule.key(),
+            authority: *self.accounts.authority.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
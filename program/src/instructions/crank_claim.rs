@@ -0,0 +1,79 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Claim, ClaimAccounts, ClaimInstructionData, InstructionAccount, MintAccount, ProgramAccount, Schedule, SignerAccount, TokenProgram};
+
+pub struct CrankClaim<'a> {
+    pub claim: Claim<'a>,
+}
+impl CrankClaim<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 10] = [
+        InstructionAccount::new("keeper", true, true, "Pays the ata rent"),
+        InstructionAccount::new("participant_wallet", false, false, ""),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("participant_ata", true, false, ""),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("system_program", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+        InstructionAccount::new("associated_token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for CrankClaim<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        // same accounts as Claim behind the keeper, except that the participant doesn't sign
+        let (
+            [
+                keeper,
+                participant_wallet,
+                vested_participant,
+                participant_ata,
+                vault,
+                schedule,
+                mint,
+                system_program,
+                token_program,
+                associated_token_account_program,
+            ],
+            remaining_accounts,
+        ) = InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(keeper)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        let accounts = ClaimAccounts {
+            participant_wallet,
+            payer: keeper,
+            vested_participant,
+            participant_ata,
+            vault,
+            schedule,
+            mint,
+            system_program,
+            token_program,
+            associated_token_account_program,
+            basket_legs: Vec::new(),
+            remaining_accounts,
+        };
+
+        // no closing and no merkle proofs, those stay with the participant
+        let instruction_data = ClaimInstructionData {
+            close_when_finalized: false,
+            amount: None,
+            merkle_allocation: None,
+            merkle_proof: &[],
+        };
+
+        // Claim only pays out to the canonical ATA of the recorded participant, so the keeper can't redirect anything
+        Ok(Self { claim: Claim::new(accounts, instruction_data)? })
+    }
+}
+impl<'a> CrankClaim<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &12;
+    pub fn process(&mut self) -> ProgramResult {
+        self.claim.process()
+    }
+}
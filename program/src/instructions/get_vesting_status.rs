@@ -0,0 +1,143 @@
+use core::mem::size_of;
+use std::ops::{Div, Mul};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{InstructionAccount, ProgramAccount, Schedule, VestedParticipant, BPS_DENOMINATOR};
+
+pub struct GetVestingStatusAccounts<'a> {
+    pub schedule: &'a AccountInfo,
+    pub vested_participant: &'a AccountInfo,
+}
+impl GetVestingStatusAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 2] = [
+        InstructionAccount::new("schedule", false, false, ""),
+        InstructionAccount::new("vested_participant", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for GetVestingStatusAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [schedule, vested_participant] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        ProgramAccount::check::<Schedule>(schedule)?;
+        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
+
+        Ok(Self {
+            schedule,
+            vested_participant,
+        })
+    }
+}
+/// Return data of `GetVestingStatus`, little endian `u64`/`i64` fields in declaration order.
+///
+/// Amounts are in the schedule's primary mint, basket legs vest at the same percentage.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VestingStatus {
+    pub vested_amount: u64,
+    pub claimed_amount: u64,
+    pub claimable_amount: u64, //what a Claim would pay right now, 0 while the schedule is paused
+    pub next_unlock_at: i64,   //0 once nothing is left to unlock
+    pub fully_vested_at: i64,  //the termination timestamp for a revoked participant, now for one accelerated to everything
+}
+impl VestingStatus {
+    pub const LEN: usize = 3 * size_of::<u64>() + 2 * size_of::<i64>();
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        bytes[0..8].copy_from_slice(&self.vested_amount.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.claimed_amount.to_le_bytes());
+        bytes[16..24].copy_from_slice(&self.claimable_amount.to_le_bytes());
+        bytes[24..32].copy_from_slice(&self.next_unlock_at.to_le_bytes());
+        bytes[32..40].copy_from_slice(&self.fully_vested_at.to_le_bytes());
+        bytes
+    }
+
+    // for callers reading the return data after a cpi or a simulation
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            vested_amount: u64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+            claimed_amount: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            claimable_amount: u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+            next_unlock_at: i64::from_le_bytes(bytes[24..32].try_into().unwrap()),
+            fully_vested_at: i64::from_le_bytes(bytes[32..40].try_into().unwrap()),
+        })
+    }
+}
+pub struct GetVestingStatus<'a> {
+    pub accounts: GetVestingStatusAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for GetVestingStatus<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = GetVestingStatusAccounts::try_from(accounts)?;
+
+        if VestedParticipant::load(accounts.vested_participant)?.schedule() != accounts.schedule.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> GetVestingStatus<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &18;
+    pub fn process(&mut self) -> ProgramResult {
+        let schedule = Schedule::load(self.accounts.schedule)?;
+        let now = schedule.now()?;
+        let tranches = Schedule::load_tranches(self.accounts.schedule)?;
+        let vested_participant = VestedParticipant::load(self.accounts.vested_participant)?;
+        let curve = schedule.participant_curve(&tranches, &vested_participant);
+
+        // same math as Claim, a revoked participant's allocation was already cut down to what was vested
+        let (vested_percentage, next_unlock_at, fully_vested_at) = if vested_participant.is_terminated() {
+            (BPS_DENOMINATOR as i64, 0, vested_participant.terminated_at())
+        } else {
+            let vested_percentage =
+                schedule.participant_vested_percentage_at(&tranches, &vested_participant, now, BPS_DENOMINATOR);
+            // a participant accelerated to everything has nothing left to unlock
+            if vested_percentage == BPS_DENOMINATOR as i64 {
+                (vested_percentage, 0, curve.fully_vested_at().min(now))
+            } else {
+                (
+                    vested_percentage,
+                    curve.next_unlock_at(now, BPS_DENOMINATOR).unwrap_or(0),
+                    curve.fully_vested_at(),
+                )
+            }
+        };
+
+        let vested_amount = vested_participant.allocated_amount()
+            .mul(vested_percentage as u64)
+            .div(BPS_DENOMINATOR);
+        let claimed_amount = vested_participant.claimed_amount();
+        let claimable_amount = if schedule.is_paused() {
+            0
+        } else {
+            vested_amount.saturating_sub(claimed_amount)
+        };
+
+        // nothing is written, so this can be simulated over rpc or read with get_return_data after a cpi
+        set_return_data(
+            &VestingStatus {
+                vested_amount,
+                claimed_amount,
+                claimable_amount,
+                next_unlock_at,
+                fully_vested_at,
+            }
+            .to_bytes(),
+        );
+
+        Ok(())
+    }
+}
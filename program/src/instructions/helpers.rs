@@ -1,8 +1,8 @@
-use pinocchio::{ProgramResult, account_info::AccountInfo, instruction::{Seed, Signer}, program_error::ProgramError, pubkey::{Pubkey, find_program_address}, sysvars::{Sysvar, rent::Rent}};
+use pinocchio::{ProgramResult, account_info::AccountInfo, instruction::{AccountMeta, Instruction, Seed, Signer}, program::{invoke_signed, slice_invoke_signed}, program_error::ProgramError, pubkey::{Pubkey, create_program_address, find_program_address}, sysvars::{Sysvar, rent::Rent}};
 use pinocchio_associated_token_account::instructions::Create;
-use pinocchio_system::instructions::CreateAccount;
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
 
-use crate::{Discriminator, PinocchioError};
+use crate::{Discriminator, PinocchioError, Schedule, Versioned};
 
 pub struct ProgramAccount;
 impl ProgramAccount {
@@ -30,7 +30,7 @@ impl ProgramAccount {
         Ok(())
     }
 
-    pub fn init<'a, T: Sized>(
+    pub fn init<'a, T: Versioned>(
         payer: &AccountInfo,
         account: &AccountInfo,
         seeds: &[Seed<'a>],
@@ -38,14 +38,42 @@ impl ProgramAccount {
     ) -> ProgramResult {
         let lamports = Rent::get()?.minimum_balance(space);
         let signer = [Signer::from(seeds)];
-        CreateAccount {
-            from: payer,
-            to: account,
-            lamports,
-            space: space as u64,
-            owner: &crate::ID,
-        }
-        .invoke_signed(&signer)?;
+        let current_lamports = account.lamports();
+        if current_lamports == 0 {
+            CreateAccount {
+                from: payer,
+                to: account,
+                lamports,
+                space: space as u64,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&signer)?;
+        } else {
+            // anyone can send lamports to a pda before it exists, CreateAccount refuses a funded address so it's built in steps
+            if current_lamports < lamports {
+                Transfer {
+                    from: payer,
+                    to: account,
+                    lamports: lamports - current_lamports,
+                }
+                .invoke()?;
+            }
+            Allocate {
+                account,
+                space: space as u64,
+            }
+            .invoke_signed(&signer)?;
+            Assign {
+                account,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&signer)?;
+        }
+
+        // the loaders reject a header without a known version, so it is written before anything loads the account
+        let mut data = account.try_borrow_mut_data()?;
+        data[0] = T::DISCRIMINATOR;
+        data[1] = T::VERSION;
         Ok(())
     }
 
@@ -63,6 +91,37 @@ impl ProgramAccount {
         Ok(())
     }
 
+    // verify_seeds that hands back the bump, so the pda is only searched for once
+    pub fn verify_seeds_with_bump(
+        seeds: &[Seed],
+        account: &AccountInfo,
+    ) -> Result<u8, ProgramError> {
+        let seed_bytes: Vec<&[u8]> = seeds.iter().map(|s| s.as_ref()).collect();
+        let (expected_public_key, bump) = find_program_address(&seed_bytes, &crate::ID);
+
+        if account.key().ne(&expected_public_key) {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        Ok(bump)
+    }
+
+    // for accounts that store their own bump, create_program_address skips the bump search of find_program_address
+    pub fn verify_address(
+        seeds: &[Seed],
+        account: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        let seed_bytes: Vec<&[u8]> = seeds.iter().map(|s| s.as_ref()).collect();
+        let expected_public_key = create_program_address(&seed_bytes, &crate::ID)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if account.key().ne(&expected_public_key) {
+            return Err(ProgramError::InvalidAccountData)
+        }
+
+        Ok(())
+    }
+
     pub fn get_bump(
         seeds: &[Seed],
     ) -> Result<u8, ProgramError> {
@@ -97,30 +156,296 @@ impl SignerAccount {
     }
 }
 
-pub struct MintAccount;
-impl MintAccount {
+/// One row of an instruction's account table, `XAccounts::ACCOUNTS` lists them in the order the
+/// instruction destructures its accounts.
+///
+/// The table sizes the destructuring, and the IDL and the off-chain builders in `client` are
+/// generated from it, so none of them can drift from what the program reads.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InstructionAccount {
+    pub name: &'static str,
+    pub writable: bool,
+    pub signer: bool,
+    // signs only on some calls, e.g. the funder when tokens are pulled from its ata
+    pub optional_signer: bool,
+    pub doc: &'static str,
+}
+impl InstructionAccount {
+    pub const fn new(name: &'static str, writable: bool, signer: bool, doc: &'static str) -> Self {
+        Self { name, writable, signer, optional_signer: false, doc }
+    }
+    pub const fn optional_signer(name: &'static str, writable: bool, doc: &'static str) -> Self {
+        Self { name, writable, signer: false, optional_signer: true, doc }
+    }
+
+    /// The accounts of `table` followed by whatever remaining accounts the instruction takes.
+    #[inline(always)]
+    pub fn split<'a, const N: usize>(
+        _table: &[Self; N],
+        accounts: &'a [AccountInfo],
+    ) -> Result<(&'a [AccountInfo; N], &'a [AccountInfo]), ProgramError> {
+        accounts.split_first_chunk().ok_or(ProgramError::NotEnoughAccountKeys)
+    }
+
+    /// Exactly the accounts of `table`.
+    #[inline(always)]
+    pub fn exact<'a, const N: usize>(
+        _table: &[Self; N],
+        accounts: &'a [AccountInfo],
+    ) -> Result<&'a [AccountInfo; N], ProgramError> {
+        accounts.try_into().map_err(|_| ProgramError::NotEnoughAccountKeys)
+    }
+
+    /// Index of `name` in `accounts`, how off-chain consumers pick an account out of an instruction.
+    pub fn position(accounts: &[Self], name: &str) -> Option<usize> {
+        accounts.iter().position(|account| account.name == name)
+    }
+}
+
+//TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = [
+    0x06, 0xdd, 0xf6, 0xe1, 0xee, 0x75, 0x8f, 0xde, 0x18, 0x42, 0x5d, 0xbc, 0xe4, 0x6c, 0xcd, 0xda,
+    0xb6, 0x1a, 0xfc, 0x4d, 0x83, 0xb9, 0x0d, 0x27, 0xfe, 0xbd, 0xf9, 0x28, 0xd8, 0xa1, 0x8b, 0xfc,
+];
+
+// token-2022 accounts with extensions are padded to the token account length and tagged with an account type byte right after it
+const TOKEN_2022_ACCOUNT_TYPE_OFFSET: usize = pinocchio_token::state::TokenAccount::LEN;
+const TOKEN_2022_ACCOUNT_TYPE_MINT: u8 = 1;
+const TOKEN_2022_ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+// extensions follow the account type byte as (u16 type, u16 length, value) entries
+const TOKEN_2022_EXTENSIONS_OFFSET: usize = TOKEN_2022_ACCOUNT_TYPE_OFFSET + 1;
+const TOKEN_2022_EXTENSION_TRANSFER_FEE_AMOUNT: u16 = 2;
+
+pub struct TokenProgram;
+impl TokenProgram {
     pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_token::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
+        if *account.key() != pinocchio_token::ID && *account.key() != TOKEN_2022_PROGRAM_ID {
+            return Err(ProgramError::IncorrectProgramId);
         }
+        Ok(())
+    }
 
-        if account.data_len().ne(&pinocchio_token::state::Mint::LEN) {
-            return Err(ProgramError::InvalidAccountData);
+    // the token program passed in has to be the one owning the mint, otherwise vault derivation and cpis would target the wrong program
+    pub fn check_mint(account: &AccountInfo, mint: &AccountInfo) -> Result<(), ProgramError> {
+        Self::check(account)?;
+        if !mint.is_owned_by(account.key()) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(())
+    }
+
+    fn check_len(
+        account: &AccountInfo,
+        base_len: usize,
+        account_type: u8,
+    ) -> Result<(), ProgramError> {
+        if account.is_owned_by(&pinocchio_token::ID) {
+            if account.data_len().ne(&base_len) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            return Ok(());
         }
 
+        if !account.is_owned_by(&TOKEN_2022_PROGRAM_ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        if account.data_len().eq(&base_len) {
+            return Ok(());
+        }
+        if account.data_len() <= TOKEN_2022_ACCOUNT_TYPE_OFFSET
+            || account.try_borrow_data()?[TOKEN_2022_ACCOUNT_TYPE_OFFSET] != account_type
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
         Ok(())
     }
 }
 
+pub struct MintAccount;
+impl MintAccount {
+    pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
+        TokenProgram::check_len(
+            account,
+            pinocchio_token::state::Mint::LEN,
+            TOKEN_2022_ACCOUNT_TYPE_MINT,
+        )
+    }
+
+    pub fn decimals(account: &AccountInfo) -> Result<u8, ProgramError> {
+        Self::check(account)?;
+        let data = account.try_borrow_data()?;
+        // the base mint layout is shared by both token programs, extensions only come after it
+        Ok(unsafe { pinocchio_token::state::Mint::from_bytes_unchecked(&data) }.decimals())
+    }
+}
+
 pub struct TokenAccount;
 impl TokenAccount {
     pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_token::ID) {
-            return Err(ProgramError::InvalidAccountOwner);
+        TokenProgram::check_len(
+            account,
+            pinocchio_token::state::TokenAccount::LEN,
+            TOKEN_2022_ACCOUNT_TYPE_ACCOUNT,
+        )
+    }
+
+    pub fn amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+        Self::check(account)?;
+        let data = account.try_borrow_data()?;
+        Ok(unsafe { pinocchio_token::state::TokenAccount::from_bytes_unchecked(&data) }.amount())
+    }
+
+    pub fn mint(account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+        Self::check(account)?;
+        let data = account.try_borrow_data()?;
+        Ok(*unsafe { pinocchio_token::state::TokenAccount::from_bytes_unchecked(&data) }.mint())
+    }
+
+    pub fn owner(account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+        Self::check(account)?;
+        let data = account.try_borrow_data()?;
+        Ok(*unsafe { pinocchio_token::state::TokenAccount::from_bytes_unchecked(&data) }.owner())
+    }
+
+    // transfer fees token-2022 withheld on the account, 0 for anything without the transfer fee extension
+    pub fn withheld_amount(account: &AccountInfo) -> Result<u64, ProgramError> {
+        Self::check(account)?;
+        let data = account.try_borrow_data()?;
+        let mut extensions = data.get(TOKEN_2022_EXTENSIONS_OFFSET..).unwrap_or_default();
+        while let [type_0, type_1, len_0, len_1, rest @ ..] = extensions {
+            let len = u16::from_le_bytes([*len_0, *len_1]) as usize;
+            let value = rest.get(..len).ok_or(ProgramError::InvalidAccountData)?;
+            if u16::from_le_bytes([*type_0, *type_1]) == TOKEN_2022_EXTENSION_TRANSFER_FEE_AMOUNT {
+                let withheld_amount = value.try_into().map_err(|_| ProgramError::InvalidAccountData)?;
+                return Ok(u64::from_le_bytes(withheld_amount));
+            }
+            extensions = &rest[len..];
         }
-        if account.data_len().ne(&pinocchio_token::state::TokenAccount::LEN) {
-            return Err(ProgramError::InvalidAccountData);
+        Ok(0)
+    }
+}
+
+/// `TransferChecked` against whichever token program owns the mint.
+///
+/// pinocchio_token only targets the legacy program, so the cpi is built by hand here. Any
+/// `remaining_accounts` are appended to the instruction, which is how token-2022 resolves the
+/// extra accounts of a transfer hook.
+pub struct TokenTransfer<'a> {
+    pub from: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub to: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining_accounts: &'a [AccountInfo],
+    pub amount: u64,
+    pub decimals: u8,
+}
+impl TokenTransfer<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut account_metas = Vec::with_capacity(4 + self.remaining_accounts.len());
+        account_metas.push(AccountMeta::writable(self.from.key()));
+        account_metas.push(AccountMeta::readonly(self.mint.key()));
+        account_metas.push(AccountMeta::writable(self.to.key()));
+        account_metas.push(AccountMeta::readonly_signer(self.authority.key()));
+
+        let mut account_infos = Vec::with_capacity(4 + self.remaining_accounts.len());
+        account_infos.extend_from_slice(&[self.from, self.mint, self.to, self.authority]);
+
+        for account in self.remaining_accounts {
+            account_metas.push(AccountMeta::new(account.key(), account.is_writable(), false));
+            account_infos.push(account);
         }
+
+        // [0]: TransferChecked discriminator, [1..9]: amount, [9]: decimals
+        let mut instruction_data = [0u8; 10];
+        instruction_data[0] = 12;
+        instruction_data[1..9].copy_from_slice(&self.amount.to_le_bytes());
+        instruction_data[9] = self.decimals;
+
+        let instruction = Instruction {
+            program_id: self.token_program.key(),
+            accounts: &account_metas,
+            data: &instruction_data,
+        };
+
+        slice_invoke_signed(&instruction, &account_infos, signers)
+    }
+}
+
+/// `CloseAccount` against whichever token program owns the account.
+pub struct TokenCloseAccount<'a> {
+    pub account: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub authority: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+impl TokenCloseAccount<'_> {
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.account.key()),
+            AccountMeta::writable(self.destination.key()),
+            AccountMeta::readonly_signer(self.authority.key()),
+        ];
+
+        let instruction = Instruction {
+            program_id: self.token_program.key(),
+            accounts: &account_metas,
+            data: &[9],
+        };
+
+        invoke_signed(
+            &instruction,
+            &[self.account, self.destination, self.authority],
+            signers,
+        )
+    }
+}
+
+/// Token-2022 `HarvestWithheldTokensToMint`, moves the transfer fees withheld on `account` to the mint.
+///
+/// Token-2022 won't close an account that still holds withheld fees. Harvesting is
+/// permissionless, the mint only has to be writable.
+pub struct TokenHarvestWithheldTokensToMint<'a> {
+    pub mint: &'a AccountInfo,
+    pub account: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+impl TokenHarvestWithheldTokensToMint<'_> {
+    pub fn invoke(&self) -> ProgramResult {
+        let account_metas = [
+            AccountMeta::writable(self.mint.key()),
+            AccountMeta::writable(self.account.key()),
+        ];
+
+        // [0]: TransferFeeExtension, [1]: HarvestWithheldTokensToMint
+        let instruction = Instruction {
+            program_id: self.token_program.key(),
+            accounts: &account_metas,
+            data: &[26, 4],
+        };
+
+        invoke_signed(&instruction, &[self.mint, self.account], &[])
+    }
+}
+
+pub struct VaultAccount;
+impl VaultAccount {
+    // the vault address is stored on the schedule at Initialize, so no ata derivation is needed here
+    pub fn check(account: &AccountInfo, schedule: &AccountInfo) -> Result<(), ProgramError> {
+        Self::check_key(account, Schedule::load(schedule)?.vault())
+    }
+
+    // basket vaults are stored the same way by AddBasketMint
+    pub fn check_key(account: &AccountInfo, vault: &Pubkey) -> Result<(), ProgramError> {
+        TokenAccount::check(account)?;
+        if account.key() != vault {
+            return Err(PinocchioError::InvalidAddress.into())
+        }
+
         Ok(())
     }
 }
@@ -175,4 +500,94 @@ impl AssociatedTokenAccount {
             Err(_) => Self::init(account, mint, payer, owner, system_program, token_program)
         }
     }
-}
\ No newline at end of file
+}
+/// Seeds of a schedule PDA, `["schedule", creator, mint, seed]`.
+///
+/// Schedules created before PDAs were namespaced have a zeroed `creator` and keep signing with
+/// the global `["schedule", seed]` seeds, so their vaults never have to move.
+pub struct ScheduleSeeds {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub seed: [u8; 8],
+    pub bump: [u8; 1],
+}
+impl ScheduleSeeds {
+    // copied out of the account, so the borrow is released before any cpi
+    pub fn from_schedule(schedule: &Schedule) -> Self {
+        Self {
+            creator: *schedule.creator(),
+            mint: *schedule.mint(),
+            seed: schedule.seed().to_le_bytes(),
+            bump: [schedule.bump()],
+        }
+    }
+
+    pub fn is_legacy(&self) -> bool {
+        self.creator == Pubkey::default()
+    }
+
+    /// Seeds without the bump, for deriving or verifying the address.
+    pub fn seeds(&self) -> Vec<Seed<'_>> {
+        if self.is_legacy() {
+            return vec![Seed::from(b"schedule"), Seed::from(&self.seed)];
+        }
+        vec![
+            Seed::from(b"schedule"),
+            Seed::from(&self.creator),
+            Seed::from(&self.mint),
+            Seed::from(&self.seed),
+        ]
+    }
+
+    /// Seeds including the bump, for signing as the schedule.
+    pub fn signer_seeds(&self) -> Vec<Seed<'_>> {
+        let mut seeds = self.seeds();
+        seeds.push(Seed::from(&self.bump));
+        seeds
+    }
+}
+/// One `(mint, vault, token_account, token_program)` group per basket mint of a schedule.
+///
+/// `token_account` is whatever the leg pays into or out of: the participant's ATA on claims,
+/// and a token account of the funder on revokes and when the schedule is closed.
+pub struct BasketLegAccounts<'a> {
+    pub mint: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub token_account: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+impl<'a> BasketLegAccounts<'a> {
+    pub const ACCOUNTS_LEN: usize = 4;
+
+    /// Splits one group per basket mint of `schedule` off the front of `accounts` and checks them
+    /// against the stored mints and vaults, the rest is handed back untouched.
+    pub fn split(
+        accounts: &'a [AccountInfo],
+        schedule: &Schedule,
+    ) -> Result<(Vec<Self>, &'a [AccountInfo]), ProgramError> {
+        let basket_mints = schedule.basket_mints();
+        if accounts.len() < basket_mints.len() * Self::ACCOUNTS_LEN {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        let (legs, remaining_accounts) = accounts.split_at(basket_mints.len() * Self::ACCOUNTS_LEN);
+
+        let legs = legs
+            .chunks_exact(Self::ACCOUNTS_LEN)
+            .zip(basket_mints.iter().zip(schedule.basket_vaults()))
+            .map(|(leg, (basket_mint, basket_vault))| {
+                let [mint, vault, token_account, token_program] = leg else {
+                    return Err(ProgramError::NotEnoughAccountKeys);
+                };
+                if mint.key() != basket_mint {
+                    return Err(PinocchioError::BasketMintInvalid.into());
+                }
+                MintAccount::check(mint)?;
+                TokenProgram::check_mint(token_program, mint)?;
+                VaultAccount::check_key(vault, basket_vault)?;
+                Ok(Self { mint, vault, token_account, token_program })
+            })
+            .collect::<Result<Vec<_>, ProgramError>>()?;
+
+        Ok((legs, remaining_accounts))
+    }
+}
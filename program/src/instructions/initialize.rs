@@ -1,11 +1,11 @@
 use crate::{
-    AssociatedTokenAccount, Discriminator, MintAccount, PinocchioError, ProgramAccount, Schedule,
-    SignerAccount,
+    AssociatedTokenAccount, Event, InstructionAccount, MintAccount, PinocchioError, ProgramAccount,
+    Schedule, ScheduleCreated, ScheduleParams, ScheduleSeeds, SignerAccount, TimeBase, TokenProgram,
+    Tranche, VestingMode,
 };
 use core::mem::size_of;
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::Seed,
     program_error::ProgramError,
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
@@ -19,14 +19,22 @@ pub struct InitializeAccounts<'a> {
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
 }
+impl InitializeAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 7] = [
+        InstructionAccount::new("authority", true, true, "Creator of the schedule, pays the rent"),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("vault", true, false, "Schedule's ata of the mint"),
+        InstructionAccount::new("system_program", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+        InstructionAccount::new("associated_token_program", false, false, ""),
+    ];
+}
 impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
     type Error = ProgramError;
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
         let [authority, schedule, mint, vault, system_program, token_program, associated_token_account_program] =
-            accounts
-        else {
-            return Err(ProgramError::NotEnoughAccountKeys);
-        };
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
 
         SignerAccount::check(authority)?;
         MintAccount::check(mint)?;
@@ -34,7 +42,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
         // do i need to check token and system programs account keys?
         // yes, we should check the the program keys that we cpi into otherwise an attacker could pass in malicious program accounts and cause havoc
         ProgramAccount::check_program(system_program, &pinocchio_system::ID)?;
-        ProgramAccount::check_program(token_program, &pinocchio_token::ID)?;
+        // either token program is fine as long as it is the one that owns the mint
+        TokenProgram::check_mint(token_program, mint)?;
 
         AssociatedTokenAccount::init_if_needed(
             vault,
@@ -55,8 +64,7 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
         })
     }
 }
-#[repr(C, packed)]
-pub struct InitializeInstructionData {
+pub struct InitializeInstructionData<'a> {
     pub start_timestamp: i64,
     pub cliff_duration: i64,
     pub step_duration: i64,
@@ -64,11 +72,19 @@ pub struct InitializeInstructionData {
     pub seed: u64,
     // be careful with passing in bumps through instruction data, here thanks to verify_seeds we are safe but in general its better to avoid passing bumps through instruction data and just calculate them on the fly especially since we are already doing find_program_addres so we don't waste any extra CUs
     pub bump: u8,
+    pub vesting_mode: VestingMode,
+    // unit of start, the durations and the tranche offsets
+    pub time_base: TimeBase,
+    // only in tranche mode, (offset, cumulative bps) rows follow the fixed fields
+    pub tranches: &'a [Tranche],
+}
+impl<'a> InitializeInstructionData<'a> {
+    pub const LEN: usize = 5 * size_of::<i64>() + 3 * size_of::<u8>();
 }
-impl<'a> TryFrom<&'a [u8]> for InitializeInstructionData {
+impl<'a> TryFrom<&'a [u8]> for InitializeInstructionData<'a> {
     type Error = ProgramError;
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<InitializeInstructionData>() {
+        if data.len() < Self::LEN {
             return Err(ProgramError::InvalidInstructionData);
         }
 
@@ -78,18 +94,44 @@ impl<'a> TryFrom<&'a [u8]> for InitializeInstructionData {
         let total_duration = i64::from_le_bytes(data[24..32].try_into().unwrap());
         let seed = u64::from_le_bytes(data[32..40].try_into().unwrap());
         let bump = u8::from_le_bytes(data[40..41].try_into().unwrap());
+        let vesting_mode = VestingMode::try_from(data[41])?;
+        let time_base = TimeBase::try_from(data[42])?;
+        let tranches = Tranche::from_bytes(&data[Self::LEN..])
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-        let unix_timestamp = Clock::get()?.unix_timestamp;
-
-        if start_timestamp < unix_timestamp {
-            return Err(PinocchioError::StartTimeInvalid.into());
+        match vesting_mode {
+            VestingMode::Step => {
+                if total_duration == 0
+                    || step_duration == 0
+                    || (total_duration - cliff_duration) % step_duration != 0
+                {
+                    return Err(PinocchioError::DurationInvalid.into());
+                }
+            }
+            // the table alone defines the curve, cliff and total must mirror its first and last row
+            VestingMode::Tranche => {
+                Tranche::validate(tranches)?;
+                if step_duration != 0
+                    || cliff_duration != tranches[0].offset()
+                    || total_duration != tranches[tranches.len() - 1].offset()
+                {
+                    return Err(PinocchioError::DurationInvalid.into());
+                }
+            }
+            // unlocks every second, a step would be meaningless
+            VestingMode::Linear => {
+                if total_duration <= 0
+                    || step_duration != 0
+                    || cliff_duration < 0
+                    || cliff_duration > total_duration
+                {
+                    return Err(PinocchioError::DurationInvalid.into());
+                }
+            }
         }
 
-        if total_duration == 0
-            || step_duration == 0
-            || (total_duration - cliff_duration) % step_duration != 0
-        {
-            return Err(PinocchioError::DurationInvalid.into());
+        if vesting_mode != VestingMode::Tranche && !tranches.is_empty() {
+            return Err(PinocchioError::TrancheTableInvalid.into());
         }
 
         Ok(Self {
@@ -99,12 +141,32 @@ impl<'a> TryFrom<&'a [u8]> for InitializeInstructionData {
             total_duration,
             seed,
             bump,
+            vesting_mode,
+            time_base,
+            tranches,
         })
     }
 }
+#[cfg(not(target_os = "solana"))]
+impl InitializeInstructionData<'_> {
+    /// Encodes the data the way `try_from` reads it, for off-chain clients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN + self.tranches.len() * Tranche::LEN);
+        data.extend_from_slice(&self.start_timestamp.to_le_bytes());
+        data.extend_from_slice(&self.cliff_duration.to_le_bytes());
+        data.extend_from_slice(&self.step_duration.to_le_bytes());
+        data.extend_from_slice(&self.total_duration.to_le_bytes());
+        data.extend_from_slice(&self.seed.to_le_bytes());
+        data.push(self.bump);
+        data.push(self.vesting_mode as u8);
+        data.push(self.time_base as u8);
+        data.extend_from_slice(Tranche::as_bytes(self.tranches));
+        data
+    }
+}
 pub struct Initialize<'a> {
     pub accounts: InitializeAccounts<'a>,
-    pub instruction_data: InitializeInstructionData,
+    pub instruction_data: InitializeInstructionData<'a>,
 }
 impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Initialize<'a> {
     type Error = ProgramError;
@@ -114,10 +176,16 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Initialize<'a> {
         let accounts = InitializeAccounts::try_from(accounts)?;
         let instruction_data = InitializeInstructionData::try_from(instruction_data)?;
 
-        let seed_binding = instruction_data.seed.to_le_bytes();
-        let seeds = [Seed::from(b"schedule"), Seed::from(&seed_binding)];
+        // checked here rather than in the data so the layout can be decoded off-chain without a clock
+        if instruction_data.start_timestamp < instruction_data.time_base.now(&Clock::get()?) {
+            return Err(PinocchioError::StartTimeInvalid.into());
+        }
 
-        ProgramAccount::verify_seeds(&seeds, accounts.schedule)?;
+        // the signing authority namespaces the address, so nobody else can take it by initializing the same seed first
+        ProgramAccount::verify_seeds(
+            &Self::schedule_seeds(&accounts, &instruction_data).seeds(),
+            accounts.schedule,
+        )?;
 
         Ok(Self {
             accounts,
@@ -127,33 +195,62 @@ impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Initialize<'a> {
 }
 impl<'a> Initialize<'a> {
     pub const DISCRIMINATOR: &'a u8 = &0;
+    fn schedule_seeds(
+        accounts: &InitializeAccounts,
+        instruction_data: &InitializeInstructionData,
+    ) -> ScheduleSeeds {
+        ScheduleSeeds {
+            creator: *accounts.authority.key(),
+            mint: *accounts.mint.key(),
+            seed: instruction_data.seed.to_le_bytes(),
+            bump: [instruction_data.bump],
+        }
+    }
     pub fn process(&mut self) -> ProgramResult {
-        let seed_binding = self.instruction_data.seed.to_le_bytes();
-        let bump_binding = [self.instruction_data.bump];
-        let seeds = [
-            Seed::from(b"schedule"),
-            Seed::from(&seed_binding),
-            Seed::from(&bump_binding),
-        ];
+        let schedule_seeds = Self::schedule_seeds(&self.accounts, &self.instruction_data);
 
         ProgramAccount::init::<Schedule>(
             self.accounts.authority,
             self.accounts.schedule,
-            &seeds,
-            Schedule::LEN,
+            &schedule_seeds.signer_seeds(),
+            Schedule::space(self.instruction_data.tranches.len()),
         )?;
 
-        let mut schedule_state = Schedule::load_mut(self.accounts.schedule)?;
-        schedule_state.set_inner(
-            *self.accounts.mint.key(),
-            *self.accounts.authority.key(),
-            self.instruction_data.seed,
-            self.instruction_data.start_timestamp,
-            self.instruction_data.cliff_duration,
-            self.instruction_data.step_duration,
-            self.instruction_data.total_duration,
-            self.instruction_data.bump,
-        )?;
+        Schedule::load_mut(self.accounts.schedule)?.set_inner(ScheduleParams {
+            mint: *self.accounts.mint.key(),
+            authority: *self.accounts.authority.key(),
+            seed: self.instruction_data.seed,
+            start: self.instruction_data.start_timestamp,
+            cliff_duration: self.instruction_data.cliff_duration,
+            step_duration: self.instruction_data.step_duration,
+            total_duration: self.instruction_data.total_duration,
+            bump: self.instruction_data.bump,
+            vesting_mode: self.instruction_data.vesting_mode,
+            creator: *self.accounts.authority.key(),
+            // the creating authority funds until it hands that role to a treasury with SetFunder
+            funder: *self.accounts.authority.key(),
+            // init_if_needed verified the ata address, later instructions only compare against it
+            vault: *self.accounts.vault.key(),
+            time_base: self.instruction_data.time_base,
+        })?;
+
+        Schedule::load_tranches_mut(self.accounts.schedule)?
+            .copy_from_slice(self.instruction_data.tranches);
+
+        ScheduleCreated {
+            schedule: *self.accounts.schedule.key(),
+            creator: *self.accounts.authority.key(),
+            mint: *self.accounts.mint.key(),
+            vault: *self.accounts.vault.key(),
+            seed: self.instruction_data.seed,
+            start: self.instruction_data.start_timestamp,
+            cliff_duration: self.instruction_data.cliff_duration,
+            step_duration: self.instruction_data.step_duration,
+            total_duration: self.instruction_data.total_duration,
+            vesting_mode: self.instruction_data.vesting_mode as u8,
+            time_base: self.instruction_data.time_base as u8,
+        }
+        .emit();
 
         Ok(())
     }
@@ -0,0 +1,256 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    AccountMigrated, Discriminator, Event, InstructionAccount, PinocchioError, ProgramAccount,
+    Schedule, ScheduleParams, SignerAccount, TimeBase, TokenAccount, Versioned, VestedParticipant,
+    VestedParticipantParams, VestingMode,
+};
+
+pub struct MigrateAccountAccounts<'a> {
+    pub payer: &'a AccountInfo,   //signer, tops up the rent of the larger layout, anyone may migrate
+    pub account: &'a AccountInfo, //schedule or vested participant
+    pub system_program: &'a AccountInfo,
+    pub remaining_accounts: &'a [AccountInfo], //the vault of a version 1 schedule, the schedule of a version 1 participant
+}
+impl MigrateAccountAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 3] = [
+        InstructionAccount::new("payer", true, true, "Tops up the rent of the larger layout"),
+        InstructionAccount::new("account", true, false, "A schedule or vested participant on an older layout"),
+        InstructionAccount::new("system_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateAccountAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let ([payer, account, system_program], remaining_accounts) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(payer)?;
+        if !account.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        ProgramAccount::check_program(system_program, &pinocchio_system::ID)?;
+
+        Ok(Self {
+            payer,
+            account,
+            system_program,
+            remaining_accounts,
+        })
+    }
+}
+pub struct MigrateAccount<'a> {
+    pub accounts: MigrateAccountAccounts<'a>,
+    pub discriminator: u8,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateAccount<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateAccountAccounts::try_from(accounts)?;
+
+        // the discriminator byte never moves, so it tells which layouts to expect
+        let (discriminator, from_version, to_version) = {
+            let data = accounts.account.try_borrow_data()?;
+            match data.first() {
+                Some(&Schedule::DISCRIMINATOR) => (
+                    Schedule::DISCRIMINATOR,
+                    Schedule::version_of(&data)?,
+                    Schedule::VERSION,
+                ),
+                Some(&VestedParticipant::DISCRIMINATOR) => (
+                    VestedParticipant::DISCRIMINATOR,
+                    VestedParticipant::version_of(&data)?,
+                    VestedParticipant::VERSION,
+                ),
+                _ => return Err(ProgramError::InvalidAccountData),
+            }
+        };
+
+        if from_version == to_version {
+            return Err(PinocchioError::AccountUpToDate.into());
+        }
+
+        Ok(Self {
+            accounts,
+            discriminator,
+            from_version,
+            to_version,
+        })
+    }
+}
+impl<'a> MigrateAccount<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &21;
+    pub fn process(&mut self) -> ProgramResult {
+        // one step per version, so an account several versions behind walks through all of them
+        let mut version = self.from_version;
+        while version < self.to_version {
+            version = self.upgrade(version)?;
+        }
+
+        // the larger layout needs more rent, whoever migrates pays the difference
+        let minimum_balance = Rent::get()?.minimum_balance(self.accounts.account.data_len());
+        let lamports = self.accounts.account.lamports();
+        if lamports < minimum_balance {
+            Transfer {
+                from: self.accounts.payer,
+                to: self.accounts.account,
+                lamports: minimum_balance - lamports,
+            }
+            .invoke()?;
+        }
+
+        AccountMigrated {
+            account: *self.accounts.account.key(),
+            discriminator: self.discriminator,
+            from_version: self.from_version,
+            to_version: self.to_version,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    fn upgrade(&self, version: u8) -> Result<u8, ProgramError> {
+        let old_len = self.accounts.account.data_len();
+        match (self.discriminator, version) {
+            // version 1 is the original layout, which predates every later field, so it goes straight to the current one
+            (Schedule::DISCRIMINATOR, 1) => self.upgrade_original_schedule(old_len),
+            (VestedParticipant::DISCRIMINATOR, 1) => self.upgrade_original_participant(old_len),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Fills in what the original program never stored: the vault and the funder.
+    /// It only ever created step schedules of SPL Token mints, signed for with the `["schedule", seed]` seeds.
+    fn upgrade_original_schedule(&self, old_len: usize) -> Result<u8, ProgramError> {
+        let [vault, ..] = self.accounts.remaining_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        // the original program had no tranches
+        if old_len != Schedule::fixed_len(1) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let original = {
+            let data = self.accounts.account.try_borrow_data()?;
+            unsafe { (data[1..].as_ptr() as *const OriginalSchedule).read() }
+        };
+
+        // the vault was the schedule's ata
+        TokenAccount::check(vault)?;
+        if find_program_address(
+            &[self.accounts.account.key(), &pinocchio_token::ID, &original.mint],
+            &pinocchio_associated_token_account::ID,
+        )
+        .0
+        .ne(vault.key())
+        {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        self.accounts.account.resize(Schedule::LEN)?;
+        // the loader needs the version byte before it hands out the new layout
+        self.accounts.account.try_borrow_mut_data()?[1] = Schedule::VERSION;
+
+        // the outstanding amount starts at zero, each participant adds its unclaimed allocation as it's migrated.
+        // the vault balance can't stand in for it, anyone can send tokens to the vault
+        Schedule::load_mut(self.accounts.account)?.set_inner(ScheduleParams {
+            mint: original.mint,
+            authority: original.authority,
+            seed: u64::from_le_bytes(original.seed),
+            start: original.start,
+            cliff_duration: original.cliff_duration,
+            step_duration: original.step_duration,
+            total_duration: original.total_duration,
+            bump: original.bump,
+            vesting_mode: VestingMode::Step,
+            // a zeroed creator keeps signing with the original seeds
+            creator: Pubkey::default(),
+            funder: original.authority,
+            vault: *vault.key(),
+            time_base: TimeBase::UnixTimestamp,
+        })?;
+
+        Ok(Schedule::VERSION)
+    }
+
+    /// Needs its schedule migrated first, which counts it and its unclaimed allocation, and refunds the rent to its authority,
+    /// the original program made the authority pay for every participant.
+    fn upgrade_original_participant(&self, old_len: usize) -> Result<u8, ProgramError> {
+        let [schedule, ..] = self.accounts.remaining_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if old_len != VestedParticipant::fixed_len(1) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let original = {
+            let data = self.accounts.account.try_borrow_data()?;
+            unsafe { (data[1..].as_ptr() as *const OriginalVestedParticipant).read() }
+        };
+        if original.schedule != *schedule.key() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // the original program searched the bump on every claim, it's stored from now on
+        let (address, bump) = find_program_address(
+            &[b"participant", &original.participant, &original.schedule],
+            &crate::ID,
+        );
+        if address != *self.accounts.account.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        ProgramAccount::check::<Schedule>(schedule)?;
+        let mut schedule = Schedule::load_mut(schedule)?;
+        schedule.add_participant(
+            original.allocated_amount.checked_sub(original.claimed_amount).ok_or(ProgramError::ArithmeticOverflow)?,
+        )?;
+
+        self.accounts.account.resize(VestedParticipant::LEN)?;
+        self.accounts.account.try_borrow_mut_data()?[1] = VestedParticipant::VERSION;
+
+        VestedParticipant::load_mut(self.accounts.account)?.set_inner(VestedParticipantParams {
+            schedule: original.schedule,
+            wallet: original.participant,
+            allocated_amount: original.allocated_amount,
+            claimed_amount: original.claimed_amount,
+            rent_payer: *schedule.authority(),
+            bump,
+            ..Default::default()
+        })?;
+
+        Ok(VestedParticipant::VERSION)
+    }
+}
+
+/// Version 1 of `Schedule` after the discriminator, as the original program wrote it.
+#[repr(C, packed)]
+struct OriginalSchedule {
+    mint: Pubkey,
+    authority: Pubkey,
+    seed: [u8; 8],
+    start: i64,
+    cliff_duration: i64,
+    step_duration: i64,
+    total_duration: i64,
+    bump: u8,
+}
+
+/// Version 1 of `VestedParticipant` after the discriminator, as the original program wrote it.
+#[repr(C, packed)]
+struct OriginalVestedParticipant {
+    schedule: Pubkey,
+    participant: Pubkey,
+    allocated_amount: u64,
+    claimed_amount: u64,
+}
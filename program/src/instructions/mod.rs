@@ -1,9 +1,49 @@
+pub mod accelerate;
+pub mod accept_authority;
+pub mod add_basket_allocation;
+pub mod add_basket_mint;
 pub mod add_participant;
+pub mod adjust_allocation;
+pub mod batch_add_participant;
+pub mod cancel_authority_transfer;
 pub mod claim;
+pub mod close_participant;
+pub mod close_schedule;
+pub mod crank_claim;
+pub mod get_vesting_status;
 pub mod initialize;
+pub mod migrate_account;
+pub mod pause;
+pub mod propose_authority;
+pub mod revoke;
+pub mod set_funder;
+pub mod set_merkle_root;
+pub mod set_pauser;
+pub mod transfer_beneficiary;
+pub mod unpause;
 pub mod helpers;
 
+pub use accelerate::*;
+pub use accept_authority::*;
+pub use add_basket_allocation::*;
+pub use add_basket_mint::*;
 pub use add_participant::*;
+pub use adjust_allocation::*;
+pub use batch_add_participant::*;
+pub use cancel_authority_transfer::*;
 pub use claim::*;
+pub use close_participant::*;
+pub use close_schedule::*;
+pub use crank_claim::*;
+pub use get_vesting_status::*;
 pub use initialize::*;
+pub use migrate_account::*;
+pub use pause::*;
+pub use propose_authority::*;
+pub use revoke::*;
+pub use set_funder::*;
+pub use set_merkle_root::*;
+pub use set_pauser::*;
+pub use transfer_beneficiary::*;
+pub use unpause::*;
 pub use helpers::*;
\ No newline at end of file
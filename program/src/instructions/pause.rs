@@ -0,0 +1,65 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Event, InstructionAccount, PauseUpdated, PinocchioError, ProgramAccount, Schedule, SignerAccount};
+
+pub struct PauseAccounts<'a> {
+    pub authority: &'a AccountInfo, //signer, the schedule's authority or its pauser
+    pub schedule: &'a AccountInfo,
+}
+impl PauseAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 2] = [
+        InstructionAccount::new("authority", false, true, "The schedule authority or its pauser"),
+        InstructionAccount::new("schedule", true, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for PauseAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, schedule] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        Ok(Self {
+            authority,
+            schedule,
+        })
+    }
+}
+pub struct Pause<'a> {
+    pub accounts: PauseAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for Pause<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = PauseAccounts::try_from(accounts)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        if !schedule.can_pause(accounts.authority.key()) {
+            return Err(ProgramError::IllegalOwner);
+        }
+        if schedule.is_paused() {
+            return Err(PinocchioError::SchedulePaused.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> Pause<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+    pub fn process(&mut self) -> ProgramResult {
+        // claims and new participants fail until Unpause, vesting time keeps accruing meanwhile
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.set_paused(true);
+
+        PauseUpdated {
+            schedule: *self.accounts.schedule.key(),
+            authority: *self.accounts.authority.key(),
+            paused: 1,
+        }
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult};
+
+use crate::{AuthorityProposed, Event, InstructionAccount, PinocchioError, ProgramAccount, Schedule, SignerAccount};
+
+pub struct ProposeAuthorityAccounts<'a> {
+    pub authority: &'a AccountInfo,     //signer
+    pub schedule: &'a AccountInfo,
+    pub new_authority: &'a AccountInfo, //has to accept before it becomes the authority
+}
+impl ProposeAuthorityAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 3] = [
+        InstructionAccount::new("authority", false, true, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("new_authority", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeAuthorityAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, schedule, new_authority] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        if *new_authority.key() == Pubkey::default() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        Ok(Self {
+            authority,
+            schedule,
+            new_authority,
+        })
+    }
+}
+pub struct ProposeAuthority<'a> {
+    pub accounts: ProposeAuthorityAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for ProposeAuthority<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = ProposeAuthorityAccounts::try_from(accounts)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        if schedule.authority() != accounts.authority.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> ProposeAuthority<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+    pub fn process(&mut self) -> ProgramResult {
+        // proposing again simply replaces the previous proposal
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.set_pending_authority(*self.accounts.new_authority.key());
+
+        AuthorityProposed {
+            schedule: *self.accounts.schedule.key(),
+            authority: *self.accounts.authority.key(),
+            pending_authority: *self.accounts.new_authority.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
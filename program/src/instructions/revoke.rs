@@ -0,0 +1,216 @@
+use std::ops::{Div, Mul};
+
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    BasketLegAccounts, Event, InstructionAccount, MintAccount, ParticipantRevoked, PinocchioError,
+    ProgramAccount, Schedule, ScheduleSeeds, SignerAccount, TokenAccount, TokenProgram,
+    TokenTransfer, VaultAccount, VestedParticipant, BPS_DENOMINATOR,
+};
+
+pub struct RevokeAccounts<'a> {
+    pub authority: &'a AccountInfo,          //signer
+    pub vested_participant: &'a AccountInfo, //state acc
+    pub clawback_account: &'a AccountInfo,   //a token account of the funder, receives the unvested remainder
+    pub vault: &'a AccountInfo,              //vault for sending from
+    pub schedule: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub basket_legs: Vec<BasketLegAccounts<'a>>, //one group per basket mint, paying into a funder's token account of that mint
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl RevokeAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 7] = [
+        InstructionAccount::new("authority", true, true, ""),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("clawback_account", true, false, "A token account of the funder, receives the unvested remainder"),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let ([authority, vested_participant, clawback_account, vault, schedule, mint, token_program], remaining_accounts) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        MintAccount::check(mint)?;
+        TokenAccount::check(clawback_account)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        Ok(Self {
+            authority,
+            vested_participant,
+            clawback_account,
+            vault,
+            schedule,
+            mint,
+            token_program,
+            basket_legs: Vec::new(),
+            remaining_accounts,
+        })
+    }
+}
+pub struct Revoke<'a> {
+    pub accounts: RevokeAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for Revoke<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let mut accounts = RevokeAccounts::try_from(accounts)?;
+
+        {
+            let schedule = Schedule::load(accounts.schedule)?;
+            if schedule.authority() != accounts.authority.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+            if accounts.mint.key() != schedule.mint() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+            if *vested_participant.schedule() != *accounts.schedule.key() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if vested_participant.is_terminated() {
+                return Err(PinocchioError::ParticipantTerminated.into());
+            }
+
+            // the authority may send the clawback to any account of the schedule mint, but only one the funder owns
+            if TokenAccount::mint(accounts.clawback_account)? != *schedule.mint() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            if TokenAccount::owner(accounts.clawback_account)? != *schedule.funder() {
+                return Err(PinocchioError::InvalidAddress.into());
+            }
+
+            let (basket_legs, remaining_accounts) =
+                BasketLegAccounts::split(accounts.remaining_accounts, &schedule)?;
+            for basket_leg in &basket_legs {
+                if TokenAccount::mint(basket_leg.token_account)? != *basket_leg.mint.key() {
+                    return Err(ProgramError::InvalidAccountData);
+                }
+                if TokenAccount::owner(basket_leg.token_account)? != *schedule.funder() {
+                    return Err(PinocchioError::InvalidAddress.into());
+                }
+            }
+            accounts.basket_legs = basket_legs;
+            accounts.remaining_accounts = remaining_accounts;
+        }
+
+        VaultAccount::check(accounts.vault, accounts.schedule)?;
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> Revoke<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+    pub fn process(&mut self) -> ProgramResult {
+        let now = Schedule::load(self.accounts.schedule)?.now()?;
+        // in the schedule's time base, 0 marks a participant that was never revoked so epoch 0 is recorded as 1
+        let terminated_at = now.max(1);
+
+        let (unvested_amount, vested_amount, vested_percentage, schedule_seeds) = {
+            let vested_percentage = {
+                let schedule = Schedule::load(self.accounts.schedule)?;
+                let tranches = Schedule::load_tranches(self.accounts.schedule)?;
+                let vested_participant = VestedParticipant::load(self.accounts.vested_participant)?;
+                schedule.participant_vested_percentage_at(&tranches, &vested_participant, now, BPS_DENOMINATOR)
+            };
+
+            let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+            let mut vested_participant = VestedParticipant::load_mut(self.accounts.vested_participant)?;
+
+            // the participant keeps whatever is vested at the termination timestamp, claimed or not
+            let vested_amount = vested_participant.allocated_amount()
+                .mul(vested_percentage as u64)
+                .div(BPS_DENOMINATOR);
+            let unvested_amount = vested_participant.allocated_amount() - vested_amount;
+
+            vested_participant.set_allocated_amount(vested_amount);
+            vested_participant.set_terminated_at(terminated_at);
+            schedule.release_outstanding_amount(unvested_amount)?;
+
+            (unvested_amount, vested_amount, vested_percentage, ScheduleSeeds::from_schedule(&schedule))
+        };
+
+        let seeds = schedule_seeds.signer_seeds();
+        let signer = [Signer::from(seeds.as_slice())];
+
+        if unvested_amount != 0 {
+            TokenTransfer {
+                from: self.accounts.vault,
+                mint: self.accounts.mint,
+                to: self.accounts.clawback_account,
+                authority: self.accounts.schedule,
+                token_program: self.accounts.token_program,
+                remaining_accounts: self.accounts.remaining_accounts,
+                amount: unvested_amount,
+                decimals: MintAccount::decimals(self.accounts.mint)?,
+            }
+            .invoke_signed(&signer)?;
+        }
+
+        ParticipantRevoked {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *self.accounts.vested_participant.key(),
+            mint: *self.accounts.mint.key(),
+            clawback_amount: unvested_amount,
+            allocated_amount: vested_amount,
+            terminated_at,
+        }
+        .emit();
+
+        // basket legs are cut down at the same vested percentage, transfer hook accounts only serve the primary mint
+        for (index, basket_leg) in self.accounts.basket_legs.iter().enumerate() {
+            let (leg_unvested_amount, leg_vested_amount) = {
+                let mut basket_legs = VestedParticipant::load_basket_legs_mut(self.accounts.vested_participant)?;
+                let leg = basket_legs.get_mut(index).ok_or(ProgramError::InvalidAccountData)?;
+                let leg_vested_amount = leg.allocated_amount()
+                    .mul(vested_percentage as u64)
+                    .div(BPS_DENOMINATOR);
+                let leg_unvested_amount = leg.allocated_amount() - leg_vested_amount;
+                leg.set_allocated_amount(leg_vested_amount);
+                (leg_unvested_amount, leg_vested_amount)
+            };
+
+            ParticipantRevoked {
+                schedule: *self.accounts.schedule.key(),
+                vested_participant: *self.accounts.vested_participant.key(),
+                mint: *basket_leg.mint.key(),
+                clawback_amount: leg_unvested_amount,
+                allocated_amount: leg_vested_amount,
+                terminated_at,
+            }
+            .emit();
+
+            if leg_unvested_amount == 0 {
+                continue;
+            }
+
+            TokenTransfer {
+                from: basket_leg.vault,
+                mint: basket_leg.mint,
+                to: basket_leg.token_account,
+                authority: self.accounts.schedule,
+                token_program: basket_leg.token_program,
+                remaining_accounts: &[],
+                amount: leg_unvested_amount,
+                decimals: MintAccount::decimals(basket_leg.mint)?,
+            }
+            .invoke_signed(&signer)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Event, FunderUpdated, InstructionAccount, ProgramAccount, Schedule, SignerAccount};
+
+pub struct SetFunderAccounts<'a> {
+    pub funder: &'a AccountInfo,     //signer, the current funder
+    pub schedule: &'a AccountInfo,
+    pub new_funder: &'a AccountInfo, //signer, pays allocations and receives refunds from now on
+}
+impl SetFunderAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 3] = [
+        InstructionAccount::new("funder", false, true, "Current funder"),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("new_funder", false, true, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for SetFunderAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [funder, schedule, new_funder] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(funder)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        // a mistyped funder could never sign again and would block every funding instruction for good
+        SignerAccount::check(new_funder)?;
+
+        Ok(Self {
+            funder,
+            schedule,
+            new_funder,
+        })
+    }
+}
+pub struct SetFunder<'a> {
+    pub accounts: SetFunderAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for SetFunder<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SetFunderAccounts::try_from(accounts)?;
+
+        // refunds follow the funder, so only the funder itself can hand the role on, not the authority
+        let schedule = Schedule::load(accounts.schedule)?;
+        if schedule.funder() != accounts.funder.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> SetFunder<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &19;
+    pub fn process(&mut self) -> ProgramResult {
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.set_funder(*self.accounts.new_funder.key());
+
+        FunderUpdated {
+            schedule: *self.accounts.schedule.key(),
+            previous_funder: *self.accounts.funder.key(),
+            funder: *self.accounts.new_funder.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
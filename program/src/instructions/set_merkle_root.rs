@@ -0,0 +1,242 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::Signer,
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    AssociatedTokenAccount, Event, InstructionAccount, MerkleRootSet, MintAccount, PinocchioError,
+    ProgramAccount, Schedule, ScheduleSeeds, SignerAccount, TokenAccount, TokenProgram,
+    TokenTransfer, VaultAccount,
+};
+
+pub struct SetMerkleRootAccounts<'a> {
+    pub authority: &'a AccountInfo,     //signer
+    pub funder: &'a AccountInfo,        //the schedule's funder, only has to sign when the total grows
+    pub funder_ata: &'a AccountInfo,    //funds the merkle total, receives refunds when it shrinks
+    pub vault: &'a AccountInfo,
+    pub schedule: &'a AccountInfo,
+    pub mint: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub remaining_accounts: &'a [AccountInfo], //transfer hook extra accounts
+}
+impl SetMerkleRootAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 7] = [
+        InstructionAccount::new("authority", true, true, ""),
+        InstructionAccount::optional_signer("funder", false, "The schedule's funder, may be the authority"),
+        InstructionAccount::new("funder_ata", true, false, "Funds a larger total, receives the refund of a smaller one"),
+        InstructionAccount::new("vault", true, false, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("mint", false, false, ""),
+        InstructionAccount::new("token_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for SetMerkleRootAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let ([authority, funder, funder_ata, vault, schedule, mint, token_program], remaining_accounts) =
+            InstructionAccount::split(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        MintAccount::check(mint)?;
+        TokenProgram::check_mint(token_program, mint)?;
+
+        Ok(Self {
+            authority,
+            funder,
+            funder_ata,
+            vault,
+            schedule,
+            mint,
+            token_program,
+            remaining_accounts,
+        })
+    }
+}
+#[repr(C, packed)]
+pub struct SetMerkleRootInstructionData {
+    pub merkle_root: [u8; 32],
+    // sum of all leaf allocations, the vault is topped up or refunded by the difference to the unclaimed leaves
+    pub merkle_total: u64,
+    // in the schedule's time base, no earlier than the end of vesting, after it the unclaimed leaves can be swept
+    pub merkle_claim_deadline: i64,
+}
+impl<'a> TryFrom<&'a [u8]> for SetMerkleRootInstructionData {
+    type Error = ProgramError;
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<SetMerkleRootInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let merkle_root: [u8; 32] = data[0..32].try_into().unwrap();
+        let merkle_total = u64::from_le_bytes(data[32..40].try_into().unwrap());
+        let merkle_claim_deadline = i64::from_le_bytes(data[40..48].try_into().unwrap());
+
+        // an empty root with funds would lock them, a root without funds can't pay anyone
+        if (merkle_root == [0u8; 32]) != (merkle_total == 0) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        if merkle_root == [0u8; 32] && merkle_claim_deadline != 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            merkle_root,
+            merkle_total,
+            merkle_claim_deadline,
+        })
+    }
+}
+#[cfg(not(target_os = "solana"))]
+impl SetMerkleRootInstructionData {
+    /// Encodes the data the way `try_from` reads it, for off-chain clients.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [self.merkle_root.as_slice(), &self.merkle_total.to_le_bytes(), &self.merkle_claim_deadline.to_le_bytes()].concat()
+    }
+}
+pub struct SetMerkleRoot<'a> {
+    pub accounts: SetMerkleRootAccounts<'a>,
+    pub instruction_data: SetMerkleRootInstructionData,
+}
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetMerkleRoot<'a> {
+    type Error = ProgramError;
+    fn try_from(
+        (instruction_data, accounts): (&'a [u8], &'a [AccountInfo]),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SetMerkleRootAccounts::try_from(accounts)?;
+        let instruction_data = SetMerkleRootInstructionData::try_from(instruction_data)?;
+
+        {
+            let schedule = Schedule::load(accounts.schedule)?;
+            let tranches = Schedule::load_tranches(accounts.schedule)?;
+
+            // recipients may start claiming at the cliff, the leaves can't change under them after that.
+            // Once the claim deadline passed the root can only be cleared, which refunds the leaves nobody claimed
+            let now = schedule.now()?;
+            if schedule.is_cliff_completed_at(&tranches, now) {
+                let sweeps_expired_leaves = schedule.has_merkle_root()
+                    && now > schedule.merkle_claim_deadline()
+                    && instruction_data.merkle_root == [0u8; 32];
+                if !sweeps_expired_leaves {
+                    return Err(PinocchioError::MerkleRootLocked.into());
+                }
+            }
+
+            // every recipient gets the whole vesting period to claim
+            if instruction_data.merkle_root != [0u8; 32]
+                && instruction_data.merkle_claim_deadline < schedule.vesting_curve(&tranches).fully_vested_at()
+            {
+                return Err(PinocchioError::MerkleClaimDeadlineInvalid.into());
+            }
+
+            if schedule.authority() != accounts.authority.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if schedule.funder() != accounts.funder.key() {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if accounts.mint.key() != schedule.mint() {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            if instruction_data.merkle_total > schedule.merkle_total() {
+                SignerAccount::check(accounts.funder)?;
+
+                if TokenAccount::amount(accounts.funder_ata)?
+                    < instruction_data.merkle_total - schedule.merkle_total()
+                {
+                    return Err(ProgramError::InsufficientFunds);
+                }
+            }
+        }
+
+        AssociatedTokenAccount::check(
+            accounts.funder_ata,
+            accounts.funder,
+            accounts.mint,
+            accounts.token_program,
+        )?;
+
+        VaultAccount::check(accounts.vault, accounts.schedule)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+impl<'a> SetMerkleRoot<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+    pub fn process(&mut self) -> ProgramResult {
+        let (previous_total, schedule_seeds) = {
+            let schedule = Schedule::load(self.accounts.schedule)?;
+            (schedule.merkle_total(), ScheduleSeeds::from_schedule(&schedule))
+        };
+        let merkle_total = self.instruction_data.merkle_total;
+
+        if merkle_total > previous_total {
+            let top_up_amount = merkle_total - previous_total;
+            let vault_amount_before = TokenAccount::amount(self.accounts.vault)?;
+
+            TokenTransfer {
+                from: self.accounts.funder_ata,
+                mint: self.accounts.mint,
+                to: self.accounts.vault,
+                authority: self.accounts.funder,
+                token_program: self.accounts.token_program,
+                remaining_accounts: self.accounts.remaining_accounts,
+                amount: top_up_amount,
+                decimals: MintAccount::decimals(self.accounts.mint)?,
+            }
+            .invoke()?;
+
+            // every leaf must be payable in full, so a transfer fee can't be absorbed here
+            if TokenAccount::amount(self.accounts.vault)? - vault_amount_before != top_up_amount {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            Schedule::load_mut(self.accounts.schedule)?
+                .reserve_outstanding_amount(top_up_amount)?;
+        } else if merkle_total < previous_total {
+            let refund_amount = previous_total - merkle_total;
+
+            let seeds = schedule_seeds.signer_seeds();
+            let signer = [Signer::from(seeds.as_slice())];
+
+            TokenTransfer {
+                from: self.accounts.vault,
+                mint: self.accounts.mint,
+                to: self.accounts.funder_ata,
+                authority: self.accounts.schedule,
+                token_program: self.accounts.token_program,
+                remaining_accounts: self.accounts.remaining_accounts,
+                amount: refund_amount,
+                decimals: MintAccount::decimals(self.accounts.mint)?,
+            }
+            .invoke_signed(&signer)?;
+
+            Schedule::load_mut(self.accounts.schedule)?
+                .release_outstanding_amount(refund_amount)?;
+        }
+
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.set_merkle_root(self.instruction_data.merkle_root);
+        schedule.set_merkle_total(merkle_total);
+        schedule.set_merkle_claim_deadline(self.instruction_data.merkle_claim_deadline);
+
+        MerkleRootSet {
+            schedule: *self.accounts.schedule.key(),
+            merkle_root: self.instruction_data.merkle_root,
+            merkle_total,
+            merkle_claim_deadline: self.instruction_data.merkle_claim_deadline,
+        }
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Event, InstructionAccount, PauserUpdated, ProgramAccount, Schedule, SignerAccount};
+
+pub struct SetPauserAccounts<'a> {
+    pub authority: &'a AccountInfo,  //signer
+    pub schedule: &'a AccountInfo,
+    pub new_pauser: &'a AccountInfo, //the system program removes the pauser
+}
+impl SetPauserAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 3] = [
+        InstructionAccount::new("authority", false, true, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+        InstructionAccount::new("new_pauser", false, false, "The system program removes the pauser"),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for SetPauserAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, schedule, new_pauser] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        Ok(Self {
+            authority,
+            schedule,
+            new_pauser,
+        })
+    }
+}
+pub struct SetPauser<'a> {
+    pub accounts: SetPauserAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for SetPauser<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SetPauserAccounts::try_from(accounts)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        if schedule.authority() != accounts.authority.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> SetPauser<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+    pub fn process(&mut self) -> ProgramResult {
+        // the system program's address is all zeroes, which is what an unset pauser looks like
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.set_pauser(*self.accounts.new_pauser.key());
+
+        PauserUpdated {
+            schedule: *self.accounts.schedule.key(),
+            authority: *self.accounts.authority.key(),
+            pauser: *self.accounts.new_pauser.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
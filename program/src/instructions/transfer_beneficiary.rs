@@ -0,0 +1,192 @@
+use pinocchio::{
+    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, ProgramResult,
+};
+
+use crate::{BeneficiaryTransferred, Event, InstructionAccount, PinocchioError, ProgramAccount, Schedule, SignerAccount, VestedParticipant, VestedParticipantParams};
+
+pub struct TransferBeneficiaryAccounts<'a> {
+    pub signer: &'a AccountInfo,                 //current participant, or the schedule authority for recovery
+    pub participant_wallet: &'a AccountInfo,     //current beneficiary
+    pub vested_participant: &'a AccountInfo,     //state acc being moved
+    pub new_wallet: &'a AccountInfo,             //signer, pays rent for the new state acc
+    pub new_vested_participant: &'a AccountInfo, //pda of the new beneficiary
+    pub schedule: &'a AccountInfo,
+    pub rent_payer: &'a AccountInfo, //whoever paid rent for the old state acc
+    pub system_program: &'a AccountInfo,
+}
+impl TransferBeneficiaryAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 8] = [
+        InstructionAccount::new("signer", true, true, "Current participant, or the schedule authority for recovery"),
+        InstructionAccount::new("participant_wallet", false, false, ""),
+        InstructionAccount::new("vested_participant", true, false, ""),
+        InstructionAccount::new("new_wallet", true, true, "Pays the rent of the new account"),
+        InstructionAccount::new("new_vested_participant", true, false, ""),
+        InstructionAccount::new("schedule", false, false, ""),
+        InstructionAccount::new("rent_payer", true, false, "Whoever paid the rent of the old account"),
+        InstructionAccount::new("system_program", false, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for TransferBeneficiaryAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, participant_wallet, vested_participant, new_wallet, new_vested_participant, schedule, rent_payer, system_program] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(signer)?;
+        // the new key has to sign, so a grant can never be sent to a mistyped or dead key
+        SignerAccount::check(new_wallet)?;
+        ProgramAccount::check::<VestedParticipant>(vested_participant)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+        ProgramAccount::check_program(system_program, &pinocchio_system::ID)?;
+
+        if new_wallet.key() == participant_wallet.key() {
+            return Err(PinocchioError::InvalidAddress.into());
+        }
+
+        Ok(Self {
+            signer,
+            participant_wallet,
+            vested_participant,
+            new_wallet,
+            new_vested_participant,
+            schedule,
+            rent_payer,
+            system_program,
+        })
+    }
+}
+pub struct TransferBeneficiary<'a> {
+    pub accounts: TransferBeneficiaryAccounts<'a>,
+    pub new_bump: u8,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for TransferBeneficiary<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = TransferBeneficiaryAccounts::try_from(accounts)?;
+
+        {
+            let schedule = Schedule::load(accounts.schedule)?;
+            let vested_participant = VestedParticipant::load(accounts.vested_participant)?;
+
+            // the participant moves their own grant, the authority can recover it for a participant who lost their key
+            if accounts.signer.key() != accounts.participant_wallet.key()
+                && accounts.signer.key() != schedule.authority()
+            {
+                return Err(ProgramError::IllegalOwner);
+            }
+
+            if vested_participant.schedule() != accounts.schedule.key()
+                || vested_participant.participant() != accounts.participant_wallet.key()
+            {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            // nothing left to move, and an emptied merkle record has to stay where it is
+            if vested_participant.is_claim_finalized() {
+                return Err(PinocchioError::CannotDoubleClaim.into());
+            }
+
+            if !vested_participant.is_from_merkle_leaf()
+                && vested_participant.rent_payer() != accounts.rent_payer.key()
+            {
+                return Err(PinocchioError::InvalidAddress.into());
+            }
+        }
+
+        let bump_binding = [VestedParticipant::load(accounts.vested_participant)?.bump()];
+        ProgramAccount::verify_address(
+            &[
+                Seed::from(b"participant"),
+                Seed::from(accounts.participant_wallet.key()),
+                Seed::from(accounts.schedule.key()),
+                Seed::from(&bump_binding),
+            ],
+            accounts.vested_participant,
+        )?;
+
+        // the new wallet may already hold a grant in this schedule. An unclaimed merkle leaf of the new wallet can't be
+        // seen here, its pda is taken by the moved grant so that leaf can't be claimed afterwards.
+        // Ownership tells whether a record exists, lamports sent to the address don't block the move
+        if accounts.new_vested_participant.is_owned_by(&crate::ID) {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let new_bump = ProgramAccount::verify_seeds_with_bump(
+            &[
+                Seed::from(b"participant"),
+                Seed::from(accounts.new_wallet.key()),
+                Seed::from(accounts.schedule.key()),
+            ],
+            accounts.new_vested_participant,
+        )?;
+
+        Ok(Self { accounts, new_bump })
+    }
+}
+impl<'a> TransferBeneficiary<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+    pub fn process(&mut self) -> ProgramResult {
+        let bump_binding = [self.new_bump];
+        let seeds = [
+            Seed::from(b"participant"),
+            Seed::from(self.accounts.new_wallet.key()),
+            Seed::from(self.accounts.schedule.key()),
+            Seed::from(&bump_binding),
+        ];
+        // same size as the old account, so any basket legs come along
+        ProgramAccount::init::<VestedParticipant>(
+            self.accounts.new_wallet,
+            self.accounts.new_vested_participant,
+            &seeds,
+            self.accounts.vested_participant.data_len(),
+        )?;
+
+        let from_merkle_leaf = {
+            let vested_participant = VestedParticipant::load(self.accounts.vested_participant)?;
+            VestedParticipant::load_mut(self.accounts.new_vested_participant)?.set_inner(VestedParticipantParams {
+                schedule: *self.accounts.schedule.key(),
+                wallet: *self.accounts.new_wallet.key(),
+                allocated_amount: vested_participant.allocated_amount(),
+                claimed_amount: vested_participant.claimed_amount(),
+                terminated_at: vested_participant.terminated_at(),
+                rent_payer: *self.accounts.new_wallet.key(),
+                from_merkle_leaf: vested_participant.is_from_merkle_leaf(),
+                bump: self.new_bump,
+                start: vested_participant.start(),
+                accelerated_bps: vested_participant.accelerated_bps(),
+            })?;
+            VestedParticipant::load_basket_legs_mut(self.accounts.new_vested_participant)?
+                .copy_from_slice(&VestedParticipant::load_basket_legs(self.accounts.vested_participant)?);
+            vested_participant.is_from_merkle_leaf()
+        };
+
+        // participant_count and outstanding_amount are unchanged, the grant only changes hands
+        if from_merkle_leaf {
+            // closing would free the old pda and let the leaf be claimed again, keep it as a fully claimed record instead.
+            // The basket legs moved too, so they are emptied the same way and only the new record carries them
+            {
+                // the record's borrow has to end before its legs are borrowed, they live in the same data
+                let mut vested_participant = VestedParticipant::load_mut(self.accounts.vested_participant)?;
+                let claimed_amount = vested_participant.claimed_amount();
+                vested_participant.set_allocated_amount(claimed_amount);
+            }
+            for leg in VestedParticipant::load_basket_legs_mut(self.accounts.vested_participant)?.iter_mut() {
+                leg.set_allocated_amount(leg.claimed_amount());
+            }
+        } else {
+            ProgramAccount::close(self.accounts.vested_participant, self.accounts.rent_payer)?;
+        }
+
+        BeneficiaryTransferred {
+            schedule: *self.accounts.schedule.key(),
+            vested_participant: *self.accounts.vested_participant.key(),
+            participant_wallet: *self.accounts.participant_wallet.key(),
+            new_vested_participant: *self.accounts.new_vested_participant.key(),
+            new_wallet: *self.accounts.new_wallet.key(),
+            signer: *self.accounts.signer.key(),
+        }
+        .emit();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::{Event, InstructionAccount, PauseUpdated, PinocchioError, ProgramAccount, Schedule, SignerAccount};
+
+pub struct UnpauseAccounts<'a> {
+    pub authority: &'a AccountInfo, //signer
+    pub schedule: &'a AccountInfo,
+}
+impl UnpauseAccounts<'_> {
+    pub const ACCOUNTS: [InstructionAccount; 2] = [
+        InstructionAccount::new("authority", false, true, ""),
+        InstructionAccount::new("schedule", true, false, ""),
+    ];
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for UnpauseAccounts<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, schedule] =
+            InstructionAccount::exact(&Self::ACCOUNTS, accounts)?;
+
+        SignerAccount::check(authority)?;
+        ProgramAccount::check::<Schedule>(schedule)?;
+
+        Ok(Self {
+            authority,
+            schedule,
+        })
+    }
+}
+pub struct Unpause<'a> {
+    pub accounts: UnpauseAccounts<'a>,
+}
+impl<'a> TryFrom<&'a [AccountInfo]> for Unpause<'a> {
+    type Error = ProgramError;
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = UnpauseAccounts::try_from(accounts)?;
+
+        let schedule = Schedule::load(accounts.schedule)?;
+        // the pauser can only stop the schedule, resuming it is the authority's call
+        if schedule.authority() != accounts.authority.key() {
+            return Err(ProgramError::IllegalOwner);
+        }
+        if !schedule.is_paused() {
+            return Err(PinocchioError::ScheduleNotPaused.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+impl<'a> Unpause<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+    pub fn process(&mut self) -> ProgramResult {
+        // whatever vested during the pause is claimable right away
+        let mut schedule = Schedule::load_mut(self.accounts.schedule)?;
+        schedule.set_paused(false);
+
+        PauseUpdated {
+            schedule: *self.accounts.schedule.key(),
+            authority: *self.accounts.authority.key(),
+            paused: 0,
+        }
+        .emit();
+
+        Ok(())
+    }
+}
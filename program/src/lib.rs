@@ -9,6 +9,18 @@ pub use errors::*;
 pub mod state;
 pub use state::*;
 
+pub mod merkle;
+pub use merkle::*;
+
+pub mod events;
+pub use events::*;
+
+#[cfg(not(target_os = "solana"))]
+pub mod idl;
+
+#[cfg(not(target_os = "solana"))]
+pub mod client;
+
 entrypoint!(process_instruction);
 
 //FwnGeaANDtRZHA1xXzjyTjr5mmEZtXBSKuA3umcRPiWG.
@@ -25,7 +37,27 @@ fn process_instruction(
     match instruction_data.split_first() {
         Some((Initialize::DISCRIMINATOR, data)) => Initialize::try_from((data, accounts))?.process(),
         Some((AddParticipant::DISCRIMINATOR, data)) => AddParticipant::try_from((data, accounts))?.process(),
-        Some((Claim::DISCRIMINATOR, _)) => Claim::try_from(accounts)?.process(),
+        Some((Claim::DISCRIMINATOR, data)) => Claim::try_from((data, accounts))?.process(),
+        Some((Revoke::DISCRIMINATOR, _)) => Revoke::try_from(accounts)?.process(),
+        Some((ProposeAuthority::DISCRIMINATOR, _)) => ProposeAuthority::try_from(accounts)?.process(),
+        Some((AcceptAuthority::DISCRIMINATOR, _)) => AcceptAuthority::try_from(accounts)?.process(),
+        Some((CancelAuthorityTransfer::DISCRIMINATOR, _)) => CancelAuthorityTransfer::try_from(accounts)?.process(),
+        Some((CloseParticipant::DISCRIMINATOR, _)) => CloseParticipant::try_from(accounts)?.process(),
+        Some((CloseSchedule::DISCRIMINATOR, _)) => CloseSchedule::try_from(accounts)?.process(),
+        Some((BatchAddParticipant::DISCRIMINATOR, data)) => BatchAddParticipant::try_from((data, accounts))?.process(),
+        Some((SetMerkleRoot::DISCRIMINATOR, data)) => SetMerkleRoot::try_from((data, accounts))?.process(),
+        Some((TransferBeneficiary::DISCRIMINATOR, _)) => TransferBeneficiary::try_from(accounts)?.process(),
+        Some((CrankClaim::DISCRIMINATOR, _)) => CrankClaim::try_from(accounts)?.process(),
+        Some((Pause::DISCRIMINATOR, _)) => Pause::try_from(accounts)?.process(),
+        Some((Unpause::DISCRIMINATOR, _)) => Unpause::try_from(accounts)?.process(),
+        Some((AdjustAllocation::DISCRIMINATOR, data)) => AdjustAllocation::try_from((data, accounts))?.process(),
+        Some((AddBasketMint::DISCRIMINATOR, _)) => AddBasketMint::try_from(accounts)?.process(),
+        Some((AddBasketAllocation::DISCRIMINATOR, data)) => AddBasketAllocation::try_from((data, accounts))?.process(),
+        Some((GetVestingStatus::DISCRIMINATOR, _)) => GetVestingStatus::try_from(accounts)?.process(),
+        Some((SetFunder::DISCRIMINATOR, _)) => SetFunder::try_from(accounts)?.process(),
+        Some((SetPauser::DISCRIMINATOR, _)) => SetPauser::try_from(accounts)?.process(),
+        Some((MigrateAccount::DISCRIMINATOR, _)) => MigrateAccount::try_from(accounts)?.process(),
+        Some((Accelerate::DISCRIMINATOR, data)) => Accelerate::try_from((data, accounts))?.process(),
         _ => Err(ProgramError::InvalidInstructionData)
     }
 }
\ No newline at end of file
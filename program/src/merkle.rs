@@ -0,0 +1,109 @@
+use pinocchio::pubkey::Pubkey;
+
+// domain separation so a leaf can never be passed off as an inner node
+pub const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+pub const MERKLE_NODE_PREFIX: &[u8] = &[1];
+pub const MERKLE_NODE_LEN: usize = 32;
+
+#[inline(always)]
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash = [0u8; 32];
+        unsafe {
+            pinocchio::syscalls::sol_sha256(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                hash.as_mut_ptr(),
+            );
+        }
+        hash
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Leaf for one recipient, `sha256(0x00 || schedule || wallet || allocation_le)`.
+///
+/// The schedule is part of the leaf, so a proof can't be replayed on another schedule that reuses the root.
+#[inline(always)]
+pub fn merkle_leaf(schedule: &Pubkey, wallet: &Pubkey, allocation: u64) -> [u8; 32] {
+    hashv(&[MERKLE_LEAF_PREFIX, schedule, wallet, &allocation.to_le_bytes()])
+}
+
+/// Inner node, children are sorted so proofs don't need to carry left/right flags.
+#[inline(always)]
+pub fn merkle_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[MERKLE_NODE_PREFIX, a, b])
+    } else {
+        hashv(&[MERKLE_NODE_PREFIX, b, a])
+    }
+}
+
+/// `proof` is the concatenated 32-byte sibling hashes from the leaf up to the root.
+pub fn verify_merkle_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[u8]) -> bool {
+    if !proof.len().is_multiple_of(MERKLE_NODE_LEN) {
+        return false;
+    }
+    let computed = proof
+        .chunks_exact(MERKLE_NODE_LEN)
+        .fold(leaf, |node, sibling| {
+            merkle_node(&node, sibling.try_into().unwrap())
+        });
+    computed == *root
+}
+
+/// Builds the root and per-leaf proofs off-chain, an odd node is carried up unchanged.
+#[cfg(not(target_os = "solana"))]
+pub struct MerkleTree {
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+#[cfg(not(target_os = "solana"))]
+impl MerkleTree {
+    pub fn new(leaves: Vec<[u8; 32]>) -> Self {
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => merkle_node(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first().copied())
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, index: usize) -> Vec<u8> {
+        let mut proof = Vec::new();
+        let mut index = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.extend_from_slice(sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
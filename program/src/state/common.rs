@@ -1,4 +1,49 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::PinocchioError;
+
 pub trait Discriminator {
     const LEN: usize;
     const DISCRIMINATOR: u8;
-}
\ No newline at end of file
+}
+
+/// Accounts whose layout version is stored right after the discriminator.
+pub trait Versioned: Discriminator {
+    /// Layout written by this build, `LEN` is its fixed length.
+    const VERSION: u8;
+    /// Fixed length of every older layout, version 1 first.
+    const LEGACY_LEN: &'static [usize];
+    /// Length of one entry of the table that follows the fixed fields.
+    const ENTRY_LEN: usize;
+
+    #[inline(always)]
+    fn fixed_len(version: u8) -> usize {
+        if version == Self::VERSION {
+            Self::LEN
+        } else {
+            Self::LEGACY_LEN[version as usize - 1]
+        }
+    }
+
+    /// Layout version of `data`. Version 1 predates the version byte, so only its length tells it apart.
+    fn version_of(data: &[u8]) -> Result<u8, ProgramError> {
+        let fits = |version: u8| {
+            let fixed_len = Self::fixed_len(version);
+            data.len() >= fixed_len && (data.len() - fixed_len).is_multiple_of(Self::ENTRY_LEN)
+        };
+        match data.get(1) {
+            Some(&version) if (2..=Self::VERSION).contains(&version) && fits(version) => Ok(version),
+            _ if fits(1) => Ok(1),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    /// Older layouts are recognized but only `MigrateAccount` touches them, everything else asks for the upgrade.
+    #[inline(always)]
+    fn check_version(data: &[u8]) -> Result<(), ProgramError> {
+        if Self::version_of(data)? != Self::VERSION {
+            return Err(PinocchioError::AccountOutdated.into());
+        }
+        Ok(())
+    }
+}
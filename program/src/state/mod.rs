@@ -1,7 +1,9 @@
 pub mod vested_participant;
 pub mod schedule;
 pub mod common;
+pub mod vesting_curve;
 
 pub use vested_participant::*;
 pub use schedule::*;
-pub use common::*;
\ No newline at end of file
+pub use common::*;
+pub use vesting_curve::*;
\ No newline at end of file
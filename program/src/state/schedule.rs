@@ -1,6 +1,8 @@
-use crate::Discriminator;
+use crate::{
+    Discriminator, PinocchioError, TimeBase, Tranche, Versioned, VestedParticipant, VestingCurve,
+    VestingMode,
+};
 use core::mem::size_of;
-use std::ops::{Div, Mul};
 use pinocchio::{
     account_info::{AccountInfo, Ref, RefMut},
     program_error::ProgramError,
@@ -8,11 +10,35 @@ use pinocchio::{
     sysvars::{clock::Clock, Sysvar},
 };
 
+// Never use float in on-chain logic, use BPS with integers instead
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// mints a schedule can pay out besides its primary mint, every claim touches all of them so keep it small
+pub const MAX_BASKET_MINTS: usize = 3;
+
+/// What a schedule is created with, `Schedule::set_inner` starts everything else empty.
+pub struct ScheduleParams {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub seed: u64,
+    pub start: i64,
+    pub cliff_duration: i64,
+    pub step_duration: i64,
+    pub total_duration: i64,
+    pub bump: u8,
+    pub vesting_mode: VestingMode,
+    pub creator: Pubkey,
+    pub funder: Pubkey,
+    pub vault: Pubkey,
+    pub time_base: TimeBase,
+}
+
 // it is good practice to save the bump on the account state when using PDAs, this way we can verify the seeds and bump when loading the account in a more performant way
 #[repr(C, packed)]
 pub struct Schedule {
     // the discriminator is usually stored in the first byte of the account data
     pub discriminator: u8, //1
+    pub version: u8,       //1, layout version, see Versioned
     pub mint: Pubkey,      //32
     pub authority: Pubkey, //32
     pub seed: [u8; 8],       //8
@@ -21,19 +47,50 @@ pub struct Schedule {
     pub step_duration: i64,  //8
     pub total_duration: i64, //8
     pub bump: u8,
+    pub pending_authority: Pubkey, //32, all zeroes when no transfer is proposed
+    pub participant_count: u64,    //8, open participant accounts
+    pub outstanding_amount: u64,   //8, allocated but not yet claimed or clawed back
+    pub vesting_mode: u8,          //1, VestingMode
+    pub merkle_root: [u8; 32],     //32, zeroed unless recipients claim with a merkle proof
+    pub merkle_total: u64,         //8, funded through SetMerkleRoot for merkle leaves nobody has claimed yet
+    pub paused: u8,                //1, blocks claims and new participants, vesting keeps accruing
+    pub basket_mint_count: u8,     //1, used entries of basket_mints
+    pub basket_mints: [Pubkey; MAX_BASKET_MINTS], //32 each, extra mints vesting on the same timeline, each with its own vault
+    pub creator: Pubkey, //32, namespaces the pda, stays put when the authority rotates, zeroed on legacy schedules
+    pub funder: Pubkey,  //32, the treasury, pays allocations and owns where refunds, clawbacks and dust go
+    pub pauser: Pubkey,  //32, may pause besides the authority, all zeroes when there is none
+    pub vault: Pubkey,   //32, the schedule's ata of the primary mint, comparing it is far cheaper than deriving it again
+    pub time_base: u8,   //1, TimeBase of start, durations and tranche offsets
+    pub basket_vaults: [Pubkey; MAX_BASKET_MINTS], //32 each, the schedule's ata of each basket mint, in basket_mints order
+    pub merkle_claim_deadline: i64, //8, no leaf can be claimed after it and the unclaimed ones go back to the funder
 }
 
 impl Discriminator for Schedule {
     const DISCRIMINATOR: u8 = 0;
-    const LEN: usize = 2 * size_of::<u8>() + 2 * size_of::<Pubkey>() + 5 * size_of::<i64>();
+    const LEN: usize = 7 * size_of::<u8>() + (7 + 2 * MAX_BASKET_MINTS) * size_of::<Pubkey>() + size_of::<[u8; 32]>() + 6 * size_of::<i64>() + 3 * size_of::<u64>();
+}
+
+impl Versioned for Schedule {
+    const VERSION: u8 = 2;
+    // version 1 is the original layout without a version byte, it ended at the bump
+    const LEGACY_LEN: &'static [usize] = &[2 * size_of::<u8>() + 2 * size_of::<Pubkey>() + 5 * size_of::<i64>()];
+    const ENTRY_LEN: usize = Tranche::LEN;
 }
 
 impl Schedule {
+    /// Account size for a schedule followed by `tranche_count` tranches, 0 outside tranche mode.
     #[inline(always)]
-    pub fn load(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
-        if account_info.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+    pub fn space(tranche_count: usize) -> usize {
+        Self::LEN + tranche_count * Tranche::LEN
+    }
+    // the account is variable-length, the fixed header is followed by the tranche table
+    #[inline(always)]
+    fn check_len(account_info: &AccountInfo) -> Result<(), ProgramError> {
+        Self::check_version(&account_info.try_borrow_data()?)
+    }
+    #[inline(always)]
+    pub fn load(account_info: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        Self::check_len(account_info)?;
         if account_info.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner);
         }
@@ -42,10 +99,8 @@ impl Schedule {
         }))
     }
     #[inline(always)]
-    pub fn load_mut(account_info: &AccountInfo) -> Result<RefMut<Self>, ProgramError> {
-        if account_info.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
+    pub fn load_mut(account_info: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        Self::check_len(account_info)?;
         if account_info.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner);
         }
@@ -55,10 +110,44 @@ impl Schedule {
         ))
     }
     #[inline(always)]
+    pub fn load_tranches(account_info: &AccountInfo) -> Result<Ref<'_, [Tranche]>, ProgramError> {
+        Self::check_len(account_info)?;
+        if account_info.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(Ref::map(account_info.try_borrow_data()?, |bytes| {
+            // length was checked above so this can't fail
+            Tranche::from_bytes(&bytes[Self::LEN..]).unwrap_or_default()
+        }))
+    }
+    #[inline(always)]
+    pub fn load_tranches_mut(account_info: &AccountInfo) -> Result<RefMut<'_, [Tranche]>, ProgramError> {
+        Self::check_len(account_info)?;
+        if account_info.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(RefMut::map(account_info.try_borrow_mut_data()?, |bytes| {
+            Tranche::from_bytes_mut(&mut bytes[Self::LEN..]).unwrap_or_default()
+        }))
+    }
+    /// Reads the account out of raw data, for off-chain readers that have bytes instead of an `AccountInfo`.
+    #[cfg(not(target_os = "solana"))]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.first() != Some(&Self::DISCRIMINATOR) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_version(bytes)?;
+        Ok(unsafe { &*(bytes.as_ptr() as *const Schedule) })
+    }
+    #[inline(always)]
     pub fn discriminator(&self) -> u8 {
         self.discriminator
     }
     #[inline(always)]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+    #[inline(always)]
     pub fn mint(&self) -> &Pubkey {
         &self.mint
     }
@@ -91,33 +180,144 @@ impl Schedule {
         self.bump
     }
     #[inline(always)]
-    pub fn is_cliff_completed(&self) -> bool {
-        Clock::get().unwrap().unix_timestamp > self.cliff_duration + self.start
+    pub fn pending_authority(&self) -> &Pubkey {
+        &self.pending_authority
+    }
+    #[inline(always)]
+    pub fn has_pending_authority(&self) -> bool {
+        self.pending_authority != Pubkey::default()
+    }
+    #[inline(always)]
+    pub fn participant_count(&self) -> u64 {
+        self.participant_count
+    }
+    #[inline(always)]
+    pub fn outstanding_amount(&self) -> u64 {
+        self.outstanding_amount
+    }
+    #[inline(always)]
+    pub fn has_outstanding_allocations(&self) -> bool {
+        self.participant_count() != 0 || self.outstanding_amount() != 0
+    }
+    #[inline(always)]
+    pub fn merkle_root(&self) -> &[u8; 32] {
+        &self.merkle_root
+    }
+    #[inline(always)]
+    pub fn has_merkle_root(&self) -> bool {
+        self.merkle_root != [0u8; 32]
+    }
+    #[inline(always)]
+    pub fn merkle_total(&self) -> u64 {
+        self.merkle_total
+    }
+    #[inline(always)]
+    pub fn merkle_claim_deadline(&self) -> i64 {
+        self.merkle_claim_deadline
+    }
+    #[inline(always)]
+    pub fn is_paused(&self) -> bool {
+        self.paused != 0
+    }
+    #[inline(always)]
+    pub fn basket_mint_count(&self) -> usize {
+        self.basket_mint_count as usize
+    }
+    #[inline(always)]
+    pub fn basket_mints(&self) -> &[Pubkey] {
+        &self.basket_mints[..self.basket_mint_count()]
+    }
+    #[inline(always)]
+    pub fn basket_vaults(&self) -> &[Pubkey] {
+        &self.basket_vaults[..self.basket_mint_count()]
+    }
+    #[inline(always)]
+    pub fn creator(&self) -> &Pubkey {
+        &self.creator
+    }
+    #[inline(always)]
+    pub fn funder(&self) -> &Pubkey {
+        &self.funder
+    }
+    #[inline(always)]
+    pub fn pauser(&self) -> &Pubkey {
+        &self.pauser
+    }
+    #[inline(always)]
+    pub fn vault(&self) -> &Pubkey {
+        &self.vault
+    }
+    #[inline(always)]
+    pub fn has_pauser(&self) -> bool {
+        self.pauser != Pubkey::default()
     }
     #[inline(always)]
-    pub fn steps_passed_percentage(&self, bps_denominator: u64) -> i64 {       
-        // Never use float in on-chain logic, use BPS with integers instead
-        if !self.is_cliff_completed() {
-            return 0;
+    pub fn can_pause(&self, signer: &Pubkey) -> bool {
+        self.authority() == signer || (self.has_pauser() && self.pauser() == signer)
+    }
+    #[inline(always)]
+    pub fn vesting_mode(&self) -> VestingMode {
+        // only ever written from a validated VestingMode
+        VestingMode::try_from(self.vesting_mode).unwrap_or(VestingMode::Step)
+    }
+    #[inline(always)]
+    pub fn time_base(&self) -> TimeBase {
+        // only ever written from a validated TimeBase
+        TimeBase::try_from(self.time_base).unwrap_or(TimeBase::UnixTimestamp)
+    }
+    /// The clock in the schedule's time base, everything the curve is evaluated at or compared with.
+    #[inline(always)]
+    pub fn now(&self) -> Result<i64, ProgramError> {
+        Ok(self.time_base().now(&Clock::get()?))
+    }
+    #[inline(always)]
+    pub fn vesting_curve<'a>(&self, tranches: &'a [Tranche]) -> VestingCurve<'a> {
+        VestingCurve {
+            mode: self.vesting_mode(),
+            start: self.start(),
+            cliff_duration: self.cliff_duration(),
+            step_duration: self.step_duration(),
+            total_duration: self.total_duration(),
+            tranches,
         }
-        
-        let now = Clock::get().unwrap().unix_timestamp;
-        let end = self.start() + self.total_duration();
-        if now >= end {
-            return 1.mul(bps_denominator) as i64;       
+    }
+    /// The schedule's durations evaluated from the participant's own start when AddParticipant gave them one.
+    #[inline(always)]
+    pub fn participant_curve<'a>(&self, tranches: &'a [Tranche], participant: &VestedParticipant) -> VestingCurve<'a> {
+        let curve = self.vesting_curve(tranches);
+        if participant.has_own_start() {
+            return curve.starting_at(participant.start());
         }
-        
-        // Cliff = 1 period, remaining vesting periods after cliff
-        let vesting_duration = self.total_duration() - self.cliff_duration();
-        let steps_after_cliff = vesting_duration / self.step_duration();
-        let total_periods = 1 + steps_after_cliff; // cliff + steps
-        
-        let elapsed_after_cliff = now - self.start() - self.cliff_duration();
-        let periods_after_cliff = elapsed_after_cliff / self.step_duration();
-
-        (1 + periods_after_cliff)
-            .mul(bps_denominator as i64)
-            .div(total_periods) // 1 for cliff + periods passed
+        curve
+    }
+    /// What the participant has vested at `now`, their curve plus any acceleration, never more than everything.
+    #[inline(always)]
+    pub fn participant_vested_percentage_at(
+        &self,
+        tranches: &[Tranche],
+        participant: &VestedParticipant,
+        now: i64,
+        bps_denominator: u64,
+    ) -> i64 {
+        let accelerated = (participant.accelerated_bps() as u64 * bps_denominator / BPS_DENOMINATOR) as i64;
+        let vested = self.participant_curve(tranches, participant).vested_percentage_at(now, bps_denominator);
+        (vested + accelerated).min(bps_denominator as i64)
+    }
+    #[inline(always)]
+    pub fn is_cliff_completed(&self, tranches: &[Tranche]) -> Result<bool, ProgramError> {
+        Ok(self.is_cliff_completed_at(tranches, self.now()?))
+    }
+    #[inline(always)]
+    pub fn is_cliff_completed_at(&self, tranches: &[Tranche], timestamp: i64) -> bool {
+        self.vesting_curve(tranches).is_cliff_completed_at(timestamp)
+    }
+    #[inline(always)]
+    pub fn vested_percentage(&self, tranches: &[Tranche], bps_denominator: u64) -> Result<i64, ProgramError> {
+        Ok(self.vested_percentage_at(tranches, self.now()?, bps_denominator))
+    }
+    #[inline(always)]
+    pub fn vested_percentage_at(&self, tranches: &[Tranche], now: i64, bps_denominator: u64) -> i64 {
+        self.vesting_curve(tranches).vested_percentage_at(now, bps_denominator)
     }
     
     #[inline(always)]
@@ -125,6 +325,10 @@ impl Schedule {
         self.discriminator = discriminator;
     }
     #[inline(always)]
+    pub fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    #[inline(always)]
     pub fn set_mint(&mut self, mint: Pubkey) {
         self.mint = mint;
     }
@@ -157,26 +361,123 @@ impl Schedule {
         self.bump = bump;
     }
     #[inline(always)]
-    pub fn set_inner(
-        &mut self,
-        mint: Pubkey,
-        authority: Pubkey,
-        seed: u64,
-        start: i64,
-        cliff_duration: i64,
-        step_duration: i64,
-        total_duration: i64,
-        bump: u8,
-    ) -> Result<(), ProgramError> {
+    pub fn set_pending_authority(&mut self, pending_authority: Pubkey) {
+        self.pending_authority = pending_authority;
+    }
+    #[inline(always)]
+    pub fn set_participant_count(&mut self, participant_count: u64) {
+        self.participant_count = participant_count;
+    }
+    #[inline(always)]
+    pub fn set_outstanding_amount(&mut self, outstanding_amount: u64) {
+        self.outstanding_amount = outstanding_amount;
+    }
+    #[inline(always)]
+    pub fn add_participant(&mut self, allocated_amount: u64) -> Result<(), ProgramError> {
+        self.set_participant_count(self.participant_count().checked_add(1).ok_or(ProgramError::ArithmeticOverflow)?);
+        self.set_outstanding_amount(self.outstanding_amount().checked_add(allocated_amount).ok_or(ProgramError::ArithmeticOverflow)?);
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn remove_participant(&mut self) -> Result<(), ProgramError> {
+        self.set_participant_count(self.participant_count().checked_sub(1).ok_or(ProgramError::ArithmeticOverflow)?);
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn reserve_outstanding_amount(&mut self, amount: u64) -> Result<(), ProgramError> {
+        self.set_outstanding_amount(self.outstanding_amount().checked_add(amount).ok_or(ProgramError::ArithmeticOverflow)?);
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn release_outstanding_amount(&mut self, amount: u64) -> Result<(), ProgramError> {
+        self.set_outstanding_amount(self.outstanding_amount().checked_sub(amount).ok_or(ProgramError::ArithmeticOverflow)?);
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_vesting_mode(&mut self, vesting_mode: VestingMode) {
+        self.vesting_mode = vesting_mode as u8;
+    }
+    #[inline(always)]
+    pub fn set_merkle_root(&mut self, merkle_root: [u8; 32]) {
+        self.merkle_root = merkle_root;
+    }
+    #[inline(always)]
+    pub fn set_merkle_total(&mut self, merkle_total: u64) {
+        self.merkle_total = merkle_total;
+    }
+    #[inline(always)]
+    pub fn set_merkle_claim_deadline(&mut self, merkle_claim_deadline: i64) {
+        self.merkle_claim_deadline = merkle_claim_deadline;
+    }
+    #[inline(always)]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused as u8;
+    }
+    #[inline(always)]
+    pub fn set_creator(&mut self, creator: Pubkey) {
+        self.creator = creator;
+    }
+    #[inline(always)]
+    pub fn set_funder(&mut self, funder: Pubkey) {
+        self.funder = funder;
+    }
+    #[inline(always)]
+    pub fn set_pauser(&mut self, pauser: Pubkey) {
+        self.pauser = pauser;
+    }
+    #[inline(always)]
+    pub fn set_vault(&mut self, vault: Pubkey) {
+        self.vault = vault;
+    }
+    #[inline(always)]
+    pub fn set_basket_vault(&mut self, index: usize, vault: Pubkey) {
+        self.basket_vaults[index] = vault;
+    }
+    #[inline(always)]
+    pub fn set_time_base(&mut self, time_base: TimeBase) {
+        self.time_base = time_base as u8;
+    }
+    #[inline(always)]
+    pub fn add_basket_mint(&mut self, mint: Pubkey, vault: Pubkey) -> Result<(), ProgramError> {
+        let index = self.basket_mint_count();
+        if index == MAX_BASKET_MINTS {
+            return Err(PinocchioError::BasketFull.into());
+        }
+        self.basket_mints[index] = mint;
+        self.basket_vaults[index] = vault;
+        self.basket_mint_count += 1;
+        Ok(())
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, params: ScheduleParams) -> Result<(), ProgramError> {
         self.set_discriminator(Schedule::DISCRIMINATOR);
-        self.set_mint(mint);
-        self.set_authority(authority);
-        self.set_seed(seed);
-        self.set_start(start);
-        self.set_cliff_duration(cliff_duration);
-        self.set_step_duration(step_duration);
-        self.set_total_duration(total_duration);
-        self.set_bump(bump);
+        self.set_version(Schedule::VERSION);
+        self.set_mint(params.mint);
+        self.set_authority(params.authority);
+        self.set_seed(params.seed);
+        self.set_start(params.start);
+        self.set_cliff_duration(params.cliff_duration);
+        self.set_step_duration(params.step_duration);
+        self.set_total_duration(params.total_duration);
+        self.set_bump(params.bump);
+        self.set_vesting_mode(params.vesting_mode);
+        self.set_creator(params.creator);
+        self.set_funder(params.funder);
+        self.set_vault(params.vault);
+        self.set_time_base(params.time_base);
+        // no transfer proposed, no participants, no merkle recipients, not paused and no pauser yet
+        self.set_pending_authority(Pubkey::default());
+        self.set_participant_count(0);
+        self.set_outstanding_amount(0);
+        self.set_merkle_root([0u8; 32]);
+        self.set_merkle_total(0);
+        self.set_merkle_claim_deadline(0);
+        self.set_paused(false);
+        self.set_pauser(Pubkey::default());
+        // basket mints are only ever added afterwards with AddBasketMint
+        self.basket_mint_count = 0;
+        self.basket_mints = [Pubkey::default(); MAX_BASKET_MINTS];
+        self.basket_vaults = [Pubkey::default(); MAX_BASKET_MINTS];
 
         Ok(())
     }
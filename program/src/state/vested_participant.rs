@@ -1,27 +1,112 @@
 use core::mem::size_of;
 use pinocchio::{account_info::{AccountInfo, Ref, RefMut}, program_error::ProgramError, pubkey::Pubkey};
-use crate::Discriminator;
+use crate::{Discriminator, Versioned};
 
 #[repr(C, packed)]
 pub struct VestedParticipant {
     pub discriminator: u8,       //1
+    pub version: u8,             //1, layout version, see Versioned
     pub schedule: Pubkey,       //32
     pub participant: Pubkey,    //32
     pub allocated_amount: u64,  //8
     pub claimed_amount: u64,    //8
+    pub terminated_at: i64,     //8, in the schedule's time base, 0 until revoked
+    pub rent_payer: Pubkey,     //32, refunded when the account is closed
+    pub from_merkle_leaf: u8,   //1, created by a merkle proof claim, never closed since it marks the leaf as used
+    pub bump: u8,               //1, so the pda is verified with create_program_address instead of a bump search
+    pub start: i64,             //8, own vesting start on the schedule's durations, 0 follows the schedule's start
+    pub accelerated_bps: u16,   //2, vested on top of the curve by Accelerate, capped at BPS_DENOMINATOR
+}
+
+/// Every field `VestedParticipant::set_inner` writes, named so call sites don't depend on argument order.
+#[derive(Default)]
+pub struct VestedParticipantParams {
+    pub schedule: Pubkey,
+    pub wallet: Pubkey,
+    pub allocated_amount: u64,
+    pub claimed_amount: u64,
+    pub terminated_at: i64,
+    pub rent_payer: Pubkey,
+    pub from_merkle_leaf: bool,
+    pub bump: u8,
+    pub start: i64,
+    pub accelerated_bps: u16,
+}
+
+/// Allocation of one basket mint, in the same order as `Schedule::basket_mints`.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BasketLeg {
+    pub allocated_amount: u64, //8
+    pub claimed_amount: u64,   //8
+}
+
+impl BasketLeg {
+    pub const LEN: usize = 2 * size_of::<u64>();
+
+    #[inline(always)]
+    pub fn allocated_amount(&self) -> u64 { self.allocated_amount }
+    #[inline(always)]
+    pub fn claimed_amount(&self) -> u64 { self.claimed_amount }
+    #[inline(always)]
+    pub fn is_claim_finalized(&self) -> bool { self.claimed_amount == self.allocated_amount }
+    #[inline(always)]
+    pub fn set_allocated_amount(&mut self, allocated_amount: u64) {
+        self.allocated_amount = allocated_amount;
+    }
+    #[inline(always)]
+    pub fn set_claimed_amount(&mut self, claimed_amount: u64) {
+        self.claimed_amount = claimed_amount;
+    }
+
+    #[inline(always)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&[BasketLeg], ProgramError> {
+        if !bytes.len().is_multiple_of(Self::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe {
+            core::slice::from_raw_parts(bytes.as_ptr() as *const BasketLeg, bytes.len() / Self::LEN)
+        })
+    }
+
+    #[inline(always)]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut [BasketLeg], ProgramError> {
+        if !bytes.len().is_multiple_of(Self::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe {
+            core::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut BasketLeg, bytes.len() / Self::LEN)
+        })
+    }
 }
 
 impl Discriminator for VestedParticipant {
-    const LEN: usize = size_of::<u8>() + 2 * size_of::<Pubkey>() + 2 * size_of::<u64>();
+    const LEN: usize =
+        4 * size_of::<u8>() + 3 * size_of::<Pubkey>() + 2 * size_of::<u64>() + 2 * size_of::<i64>() + size_of::<u16>();
     const DISCRIMINATOR: u8 = 1;
 }
 
+impl Versioned for VestedParticipant {
+    const VERSION: u8 = 2;
+    // version 1 is the original layout without a version byte, it ended at the claimed amount
+    const LEGACY_LEN: &'static [usize] = &[size_of::<u8>() + 2 * size_of::<Pubkey>() + 2 * size_of::<u64>()];
+    const ENTRY_LEN: usize = BasketLeg::LEN;
+}
+
 impl VestedParticipant {
+    /// Account size for a participant of a schedule with `basket_mint_count` basket mints.
     #[inline(always)]
-    pub fn load(account_info: &AccountInfo) -> Result<Ref<Self>, ProgramError> {
-        if account_info.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData)
-        }
+    pub fn space(basket_mint_count: usize) -> usize {
+        Self::LEN + basket_mint_count * BasketLeg::LEN
+    }
+    // the fixed fields are followed by one basket leg per basket mint of the schedule
+    #[inline(always)]
+    fn check_len(account_info: &AccountInfo) -> Result<(), ProgramError> {
+        Self::check_version(&account_info.try_borrow_data()?)
+    }
+    #[inline(always)]
+    pub fn load(account_info: &AccountInfo) -> Result<Ref<'_, Self>, ProgramError> {
+        Self::check_len(account_info)?;
         if account_info.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner)
         }
@@ -30,10 +115,8 @@ impl VestedParticipant {
         }))
     }
     #[inline(always)]
-    pub fn load_mut(account_info: &AccountInfo) -> Result<RefMut<Self>, ProgramError> {
-        if account_info.data_len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData)
-        }
+    pub fn load_mut(account_info: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        Self::check_len(account_info)?;
         if account_info.owner() != &crate::ID {
             return Err(ProgramError::InvalidAccountOwner)
         }
@@ -42,6 +125,36 @@ impl VestedParticipant {
         }))
     }
     #[inline(always)]
+    pub fn load_basket_legs(account_info: &AccountInfo) -> Result<Ref<'_, [BasketLeg]>, ProgramError> {
+        Self::check_len(account_info)?;
+        if account_info.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner)
+        }
+        Ok(Ref::map(account_info.try_borrow_data()?, |bytes| {
+            // length was checked above so this can't fail
+            BasketLeg::from_bytes(&bytes[Self::LEN..]).unwrap_or_default()
+        }))
+    }
+    #[inline(always)]
+    pub fn load_basket_legs_mut(account_info: &AccountInfo) -> Result<RefMut<'_, [BasketLeg]>, ProgramError> {
+        Self::check_len(account_info)?;
+        if account_info.owner() != &crate::ID {
+            return Err(ProgramError::InvalidAccountOwner)
+        }
+        Ok(RefMut::map(account_info.try_borrow_mut_data()?, |bytes| {
+            BasketLeg::from_bytes_mut(&mut bytes[Self::LEN..]).unwrap_or_default()
+        }))
+    }
+    /// Reads the account out of raw data, for off-chain readers that have bytes instead of an `AccountInfo`.
+    #[cfg(not(target_os = "solana"))]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.first() != Some(&Self::DISCRIMINATOR) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Self::check_version(bytes)?;
+        Ok(unsafe { &*(bytes.as_ptr() as *const VestedParticipant) })
+    }
+    #[inline(always)]
     pub fn schedule(&self) -> &Pubkey { &self.schedule }
     #[inline(always)]
     pub fn participant(&self) -> &Pubkey { &self.participant }
@@ -50,10 +163,28 @@ impl VestedParticipant {
     #[inline(always)]
     pub fn claimed_amount(&self) -> u64 { self.claimed_amount }
     #[inline(always)]
+    pub fn terminated_at(&self) -> i64 { self.terminated_at }
+    #[inline(always)]
+    pub fn start(&self) -> i64 { self.start }
+    #[inline(always)]
+    pub fn has_own_start(&self) -> bool { self.start != 0 }
+    #[inline(always)]
+    pub fn accelerated_bps(&self) -> u16 { self.accelerated_bps }
+    #[inline(always)]
+    pub fn is_accelerated(&self) -> bool { self.accelerated_bps != 0 }
+    #[inline(always)]
+    pub fn rent_payer(&self) -> &Pubkey { &self.rent_payer }
+    #[inline(always)]
     pub fn discriminator(&self) -> u8 { self.discriminator }
     #[inline(always)]
     pub fn is_claim_finalized(&self) -> bool { self.claimed_amount == self.allocated_amount }
     #[inline(always)]
+    pub fn is_terminated(&self) -> bool { self.terminated_at != 0 }
+    #[inline(always)]
+    pub fn is_from_merkle_leaf(&self) -> bool { self.from_merkle_leaf != 0 }
+    #[inline(always)]
+    pub fn bump(&self) -> u8 { self.bump }
+    #[inline(always)]
     pub fn set_schedule(&mut self, schedule: Pubkey) {
         self.schedule = schedule;
     }
@@ -70,23 +201,51 @@ impl VestedParticipant {
         self.claimed_amount = claimed_amount;
     }
     #[inline(always)]
+    pub fn set_terminated_at(&mut self, terminated_at: i64) {
+        self.terminated_at = terminated_at;
+    }
+    #[inline(always)]
+    pub fn set_rent_payer(&mut self, rent_payer: Pubkey) {
+        self.rent_payer = rent_payer;
+    }
+    #[inline(always)]
+    pub fn set_from_merkle_leaf(&mut self, from_merkle_leaf: bool) {
+        self.from_merkle_leaf = from_merkle_leaf as u8;
+    }
+    #[inline(always)]
+    pub fn set_bump(&mut self, bump: u8) {
+        self.bump = bump;
+    }
+    #[inline(always)]
+    pub fn set_start(&mut self, start: i64) {
+        self.start = start;
+    }
+    #[inline(always)]
+    pub fn set_accelerated_bps(&mut self, accelerated_bps: u16) {
+        self.accelerated_bps = accelerated_bps;
+    }
+    #[inline(always)]
     pub fn set_disctiminator(&mut self, discriminator: u8) {
         self.discriminator = discriminator;
     }
     #[inline(always)]
-    pub fn set_inner(
-        &mut self, 
-        schedule_mint: Pubkey,
-        wallet: Pubkey,
-        allocated_amount: u64,
-        claimed_amount: u64,
-
-    ) -> Result<(), ProgramError> {
-        self.set_schedule(schedule_mint);
-        self.set_wallet(wallet);
-        self.set_allocated_amount(allocated_amount);
-        self.set_claimed_amount(claimed_amount);
+    pub fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+    #[inline(always)]
+    pub fn set_inner(&mut self, params: VestedParticipantParams) -> Result<(), ProgramError> {
+        self.set_schedule(params.schedule);
+        self.set_wallet(params.wallet);
+        self.set_allocated_amount(params.allocated_amount);
+        self.set_claimed_amount(params.claimed_amount);
+        self.set_terminated_at(params.terminated_at);
+        self.set_rent_payer(params.rent_payer);
+        self.set_from_merkle_leaf(params.from_merkle_leaf);
+        self.set_bump(params.bump);
+        self.set_start(params.start);
+        self.set_accelerated_bps(params.accelerated_bps);
         self.set_disctiminator(VestedParticipant::DISCRIMINATOR);
+        self.set_version(VestedParticipant::VERSION);
 
         Ok(())
     }
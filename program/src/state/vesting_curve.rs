@@ -0,0 +1,251 @@
+use core::mem::size_of;
+use std::ops::{Div, Mul};
+
+use pinocchio::{program_error::ProgramError, sysvars::clock::Clock};
+
+use crate::{PinocchioError, BPS_DENOMINATOR};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VestingMode {
+    // cliff counts as one period, then equal unlocks every step_duration
+    Step = 0,
+    // nothing at cliff end, then unlocks every second until start + total_duration
+    Linear = 1,
+    // unlocks follow the tranche table stored after the schedule
+    Tranche = 2,
+}
+
+impl TryFrom<u8> for VestingMode {
+    type Error = PinocchioError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Step),
+            1 => Ok(Self::Linear),
+            2 => Ok(Self::Tranche),
+            _ => Err(PinocchioError::VestingModeInvalid),
+        }
+    }
+}
+
+/// Unit of a schedule's start, durations and tranche offsets, and of every time read against them.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TimeBase {
+    // Clock::unix_timestamp, seconds
+    UnixTimestamp = 0,
+    // Clock::slot, for emissions specified in slots
+    Slot = 1,
+    // Clock::epoch, e.g. unlock at epoch N
+    Epoch = 2,
+}
+
+impl TryFrom<u8> for TimeBase {
+    type Error = PinocchioError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::UnixTimestamp),
+            1 => Ok(Self::Slot),
+            2 => Ok(Self::Epoch),
+            _ => Err(PinocchioError::TimeBaseInvalid),
+        }
+    }
+}
+
+impl TimeBase {
+    /// `clock` in this unit, off-chain callers pass the clock they observed.
+    #[inline(always)]
+    pub fn now(self, clock: &Clock) -> i64 {
+        match self {
+            Self::UnixTimestamp => clock.unix_timestamp,
+            Self::Slot => clock.slot as i64,
+            Self::Epoch => clock.epoch as i64,
+        }
+    }
+}
+
+/// One row of a milestone table: from `start + offset` on, `cumulative_bps` of the allocation is unlocked.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Tranche {
+    pub offset: i64,         //8, after start, in the schedule's time base
+    pub cumulative_bps: u16, //2
+}
+
+impl Tranche {
+    pub const LEN: usize = size_of::<i64>() + size_of::<u16>();
+
+    #[inline(always)]
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+    #[inline(always)]
+    pub fn cumulative_bps(&self) -> u16 {
+        self.cumulative_bps
+    }
+
+    /// Reinterprets raw bytes as a tranche table, packed so any alignment is fine.
+    #[inline(always)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<&[Tranche], ProgramError> {
+        if !bytes.len().is_multiple_of(Self::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe {
+            core::slice::from_raw_parts(bytes.as_ptr() as *const Tranche, bytes.len() / Self::LEN)
+        })
+    }
+
+    #[inline(always)]
+    pub fn from_bytes_mut(bytes: &mut [u8]) -> Result<&mut [Tranche], ProgramError> {
+        if !bytes.len().is_multiple_of(Self::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe {
+            core::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut Tranche, bytes.len() / Self::LEN)
+        })
+    }
+
+    /// Raw bytes of a tranche table, the inverse of `from_bytes`.
+    #[cfg(not(target_os = "solana"))]
+    pub fn as_bytes(tranches: &[Tranche]) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(tranches.as_ptr() as *const u8, core::mem::size_of_val(tranches)) }
+    }
+
+    /// A table must be non-empty, strictly increasing in both offset and unlocked share, and end at 100%.
+    pub fn validate(tranches: &[Tranche]) -> Result<(), PinocchioError> {
+        let Some(last) = tranches.last() else {
+            return Err(PinocchioError::TrancheTableInvalid);
+        };
+        if tranches[0].offset() < 0 || last.cumulative_bps() as u64 != BPS_DENOMINATOR {
+            return Err(PinocchioError::TrancheTableInvalid);
+        }
+        for pair in tranches.windows(2) {
+            if pair[1].offset() <= pair[0].offset()
+                || pair[1].cumulative_bps() <= pair[0].cumulative_bps()
+            {
+                return Err(PinocchioError::TrancheTableInvalid);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pure vesting curve evaluation, shared by the program, the indexer and any off-chain client so
+/// they can never disagree on what is vested at a given time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct VestingCurve<'a> {
+    pub mode: VestingMode,
+    pub start: i64,
+    pub cliff_duration: i64,
+    pub step_duration: i64,
+    pub total_duration: i64,
+    pub tranches: &'a [Tranche], //only read in tranche mode
+}
+
+impl VestingCurve<'_> {
+    #[inline(always)]
+    pub fn is_cliff_completed_at(&self, now: i64) -> bool {
+        match self.mode {
+            // the first tranche unlocks at its timestamp, not a second after
+            VestingMode::Tranche => self
+                .tranches
+                .first()
+                .is_some_and(|tranche| now >= self.start + tranche.offset()),
+            _ => now > self.cliff_duration + self.start,
+        }
+    }
+
+    #[inline(always)]
+    pub fn vested_percentage_at(&self, now: i64, bps_denominator: u64) -> i64 {
+        // Never use float in on-chain logic, use BPS with integers instead
+        if !self.is_cliff_completed_at(now) {
+            return 0;
+        }
+
+        let end = self.start + self.total_duration;
+        if now >= end {
+            return 1.mul(bps_denominator) as i64;
+        }
+
+        let elapsed_after_cliff = now - self.start - self.cliff_duration;
+        let vesting_duration = self.total_duration - self.cliff_duration;
+
+        match self.mode {
+            VestingMode::Step => {
+                // Cliff = 1 period, remaining vesting periods after cliff
+                let steps_after_cliff = vesting_duration / self.step_duration;
+                let total_periods = 1 + steps_after_cliff; // cliff + steps
+
+                let periods_after_cliff = elapsed_after_cliff / self.step_duration;
+
+                (1 + periods_after_cliff)
+                    .mul(bps_denominator as i64)
+                    .div(total_periods) // 1 for cliff + periods passed
+            }
+            VestingMode::Linear => {
+                // i128 so that long durations times the denominator can't overflow
+                (elapsed_after_cliff as i128)
+                    .mul(bps_denominator as i128)
+                    .div(vesting_duration as i128) as i64
+            }
+            VestingMode::Tranche => {
+                // last tranche whose timestamp has passed, the table is sorted by offset
+                let unlocked_bps = self
+                    .tranches
+                    .iter()
+                    .take_while(|tranche| now >= self.start + tranche.offset())
+                    .last()
+                    .map_or(0, |tranche| tranche.cumulative_bps() as u64);
+
+                unlocked_bps.mul(bps_denominator).div(BPS_DENOMINATOR) as i64
+            }
+        }
+    }
+
+    /// The same curve for a participant whose vesting starts at `start` instead.
+    #[inline(always)]
+    pub fn starting_at(self, start: i64) -> Self {
+        Self { start, ..self }
+    }
+
+    /// Timestamp at which the whole allocation is unlocked, a tranche table ends at `total_duration` too.
+    #[inline(always)]
+    pub fn fully_vested_at(&self) -> i64 {
+        self.start + self.total_duration
+    }
+
+    /// First timestamp after `now` at which the vested percentage grows, `None` once fully vested.
+    #[inline(always)]
+    pub fn next_unlock_at(&self, now: i64, bps_denominator: u64) -> Option<i64> {
+        let end = self.fully_vested_at();
+        if now >= end {
+            return None;
+        }
+
+        let cliff_end = self.start + self.cliff_duration;
+        let next_unlock = match self.mode {
+            // the cliff period unlocks a second after the cliff, then one every step_duration
+            VestingMode::Step if now <= cliff_end => cliff_end + 1,
+            VestingMode::Step => {
+                let periods_after_cliff = (now - cliff_end) / self.step_duration;
+                cliff_end + (periods_after_cliff + 1) * self.step_duration
+            }
+            // first second at which the integer percentage ticks over, not just the next second
+            VestingMode::Linear => {
+                let vesting_duration = (self.total_duration - self.cliff_duration) as i128;
+                let next_percentage = self.vested_percentage_at(now, bps_denominator) as i128 + 1;
+                // rounded up, both sides are non-negative here
+                let elapsed_after_cliff = ((next_percentage * vesting_duration + bps_denominator as i128 - 1)
+                    / bps_denominator as i128) as i64;
+                (cliff_end + elapsed_after_cliff).max(cliff_end + 1)
+            }
+            VestingMode::Tranche => self
+                .tranches
+                .iter()
+                .map(|tranche| self.start + tranche.offset())
+                .find(|unlock_at| *unlock_at > now)?,
+        };
+
+        Some(next_unlock.min(end))
+    }
+}
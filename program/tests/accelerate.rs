@@ -0,0 +1,598 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    Accelerate, AccelerateAccounts, AccelerateInstructionData, AddParticipant,
+    AddParticipantAccounts, AddParticipantInstructionData, AdjustAllocation,
+    AdjustAllocationAccounts, AdjustAllocationInstructionData, Claim, ClaimAccounts, Initialize,
+    InitializeAccounts, InitializeInstructionData, PinocchioError, Revoke, RevokeAccounts, TimeBase,
+    TransferBeneficiary, TransferBeneficiaryAccounts, VestedParticipant, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_revoke_ix(
+    authority: &Pubkey,
+    vested_participant: &Pubkey,
+    clawback_account: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Revoke::DISCRIMINATOR,
+        &RevokeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("vested_participant", *vested_participant),
+            ("clawback_account", *clawback_account),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_accelerate_ix(
+    authority: &Pubkey,
+    vested_participant: &Pubkey,
+    schedule: &Pubkey,
+    accelerated_bps: u16,
+) -> Instruction {
+    client::instruction(
+        *Accelerate::DISCRIMINATOR,
+        &AccelerateAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("vested_participant", *vested_participant),
+            ("schedule", *schedule),
+        ],
+        &AccelerateInstructionData { accelerated_bps }.to_bytes(),
+    )
+}
+
+fn build_adjust_allocation_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    client::instruction(
+        *AdjustAllocation::DISCRIMINATOR,
+        &AdjustAllocationAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &AdjustAllocationInstructionData { token_allocation_amount: allocation }.to_bytes(),
+    )
+}
+
+fn build_transfer_beneficiary_ix(
+    signer: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    new_wallet: &Pubkey,
+    new_vested_participant: &Pubkey,
+    schedule: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *TransferBeneficiary::DISCRIMINATOR,
+        &TransferBeneficiaryAccounts::ACCOUNTS,
+        &[
+            ("signer", *signer),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("new_wallet", *new_wallet),
+            ("new_vested_participant", *new_vested_participant),
+            ("schedule", *schedule),
+            ("rent_payer", *rent_payer),
+            ("system_program", SYSTEM_PROGRAM_ID),
+        ],
+        &[],
+    )
+}
+
+fn assert_custom_error(result: litesvm::types::TransactionResult, error: PinocchioError) {
+    let err = result.expect_err("Transaction should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(error as u32)));
+}
+
+fn get_accelerated_bps(svm: &LiteSVM, vested_participant: &Pubkey) -> u16 {
+    let account = svm.get_account(vested_participant).unwrap();
+    VestedParticipant::from_bytes(&account.data).unwrap().accelerated_bps()
+}
+
+/// Setup schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    // Fund authority ATA
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    // Add participant
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+#[test]
+fn test_single_trigger_vests_before_cliff() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 200_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    // Change of control: a quarter vests right away, long before the cliff at 1100
+    let ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, 2_500);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Accelerate should succeed: {:?}", result.err());
+    assert_eq!(get_accelerated_bps(&svm, &vested_participant_pda), 2_500);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim of the accelerated share should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 250_000_000);
+
+    // Clock: 1200 -> 60% on the curve plus the accelerated 25%
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    svm.expire_blockhash();
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 850_000_000);
+}
+
+#[test]
+fn test_double_trigger_accelerate_then_revoke_keeps_everything() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 200_001;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    // Clock: 1200 -> 60% vested, termination without cause after a change of control vests the rest
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let accelerate_ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, 10_000);
+    let revoke_ix = build_revoke_ix(
+        &authority.pubkey(), &vested_participant_pda, &authority_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[accelerate_ix, revoke_ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Accelerate and Revoke should succeed: {:?}", result.err());
+
+    assert_eq!(get_token_balance(&svm, &authority_ata), 0, "Nothing is left to claw back");
+    assert_eq!(get_token_balance(&svm, &vault), allocation);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim after revoke should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), allocation);
+
+    // A revoked participant can't be accelerated any further
+    svm.expire_blockhash();
+    let ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, 1_000);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::ParticipantTerminated);
+}
+
+#[test]
+fn test_accelerations_add_up_to_everything() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 200_002;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, _, _, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    for expected_bps in [6_000, 10_000] {
+        let ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, 6_000);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        assert!(result.is_ok(), "Accelerate should succeed: {:?}", result.err());
+        assert_eq!(get_accelerated_bps(&svm, &vested_participant_pda), expected_bps);
+        svm.expire_blockhash();
+    }
+
+    let ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, 1_000);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::ParticipantFullyAccelerated);
+}
+
+#[test]
+fn test_accelerate_invalid_bps_or_authority_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    let attacker = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&attacker.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 200_003;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, _, _, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    for accelerated_bps in [0, 10_001] {
+        let ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, accelerated_bps);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+        );
+        assert!(svm.send_transaction(tx).is_err(), "{accelerated_bps} bps should be rejected");
+    }
+
+    let ix = build_accelerate_ix(&attacker.pubkey(), &vested_participant_pda, &schedule, 10_000);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&attacker.pubkey()), &[&attacker], svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Only the schedule authority may accelerate");
+    assert_eq!(get_accelerated_bps(&svm, &vested_participant_pda), 0);
+}
+
+#[test]
+fn test_acceleration_follows_transferred_grant() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    let new_wallet = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&new_wallet.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 200_004;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    let ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, 4_000);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Accelerate should succeed: {:?}", result.err());
+
+    let (new_vested_participant_pda, _) = get_participant_pda(&new_wallet.pubkey(), &schedule);
+    let ix = build_transfer_beneficiary_ix(
+        &participant.pubkey(), &participant.pubkey(), &vested_participant_pda,
+        &new_wallet.pubkey(), &new_vested_participant_pda, &schedule, &authority.pubkey(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant, &new_wallet], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "TransferBeneficiary should succeed: {:?}", result.err());
+    assert_eq!(get_accelerated_bps(&svm, &new_vested_participant_pda), 4_000);
+
+    // Still before the cliff, the new wallet claims the accelerated share
+    let new_wallet_ata = get_ata(&new_wallet.pubkey(), &mint);
+    let ix = build_claim_ix(
+        &new_wallet.pubkey(), &new_vested_participant_pda, &new_wallet_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&new_wallet.pubkey()), &[&new_wallet], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim by the new wallet should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &new_wallet_ata), 400_000_000);
+}
+
+#[test]
+fn test_adjust_allocation_below_claimed_accelerated_share_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 200_005;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    let ix = build_accelerate_ix(&authority.pubkey(), &vested_participant_pda, &schedule, 5_000);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Accelerate should succeed: {:?}", result.err());
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 500_000_000);
+
+    // 600 at 50% would only vest 300 of the 500 already claimed
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 600_000_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::AllocationBelowClaimed);
+
+    // A top up keeps the claimed share covered
+    MintTo::new(&mut svm, &authority, &mint, &authority_ata, 200_000_000).send().unwrap();
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 1_200_000_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "AdjustAllocation should succeed: {:?}", result.err());
+}
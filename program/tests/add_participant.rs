@@ -1,7 +1,12 @@
 use litesvm::LiteSVM;
 use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Initialize,
+    InitializeAccounts, InitializeInstructionData, TimeBase, VestingMode,
+};
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::Instruction,
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
@@ -11,10 +16,6 @@ use solana_sdk::{
 
 const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
 
-const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
-    0xde, 0x0c, 0x2a, 0xd8, 0xf6, 0xeb, 0x0d, 0x5a, 0x94, 0x92, 0x02, 0x79, 0x06, 0xfa, 0xcc, 0x62,
-    0x60, 0xbb, 0x41, 0xca, 0xcd, 0xdd, 0x62, 0x68, 0x67, 0xb5, 0xe6, 0x8a, 0xfc, 0x26, 0xe0, 0x35,
-]);
 
 fn setup_svm() -> LiteSVM {
     let mut svm = LiteSVM::new()
@@ -27,8 +28,11 @@ fn setup_svm() -> LiteSVM {
     svm
 }
 
-fn get_schedule_pda(seed: u64) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"schedule", &seed.to_le_bytes()], &PROGRAM_ID)
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
 }
 
 fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
@@ -54,27 +58,32 @@ fn build_initialize_ix(
     seed: u64,
     bump: u8,
 ) -> Instruction {
-    let mut data = vec![0u8]; // Initialize discriminator
-    data.extend_from_slice(&start_timestamp.to_le_bytes());
-    data.extend_from_slice(&cliff_duration.to_le_bytes());
-    data.extend_from_slice(&step_duration.to_le_bytes());
-    data.extend_from_slice(&total_duration.to_le_bytes());
-    data.extend_from_slice(&seed.to_le_bytes());
-    data.push(bump);
-
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*authority, true),
-            AccountMeta::new(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
         ],
-        data,
-    }
+        &data.to_bytes(),
+    )
 }
 
 fn build_add_participant_ix(
@@ -87,24 +96,25 @@ fn build_add_participant_ix(
     mint: &Pubkey,
     allocation: u64,
 ) -> Instruction {
-    let mut data = vec![1u8]; // AddParticipant discriminator
-    data.extend_from_slice(&allocation.to_le_bytes());
-
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*authority, true),
-            AccountMeta::new(*authority_ata, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(*participant_wallet, false),
-            AccountMeta::new(*vested_participant_pda, false),
-            AccountMeta::new_readonly(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
         ],
-        data,
-    }
+        &data.to_bytes(),
+    )
 }
 
 /// Helper to initialize a schedule and return (schedule_pda, vault_ata, mint)
@@ -114,7 +124,7 @@ fn setup_schedule(svm: &mut LiteSVM, authority: &Keypair, seed: u64) -> (Pubkey,
         .send()
         .unwrap();
 
-    let (schedule, bump) = get_schedule_pda(seed);
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
     let vault = get_ata(&schedule, &mint);
 
     let ix = build_initialize_ix(
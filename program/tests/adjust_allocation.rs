@@ -0,0 +1,334 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, AdjustAllocation,
+    AdjustAllocationAccounts, AdjustAllocationInstructionData, Initialize, InitializeAccounts,
+    InitializeInstructionData, PinocchioError, TimeBase, VestedParticipant, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+
+fn build_adjust_allocation_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    client::instruction(
+        *AdjustAllocation::DISCRIMINATOR,
+        &AdjustAllocationAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &AdjustAllocationInstructionData { token_allocation_amount: allocation }.to_bytes(),
+    )
+}
+
+fn get_allocated_amount(svm: &LiteSVM, vested_participant_pda: &Pubkey) -> u64 {
+    let account = svm.get_account(vested_participant_pda).unwrap();
+    VestedParticipant::from_bytes(&account.data).unwrap().allocated_amount()
+}
+
+fn assert_custom_error(result: litesvm::types::TransactionResult, error: PinocchioError) {
+    let err = result.expect_err("Transaction should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(error as u32)));
+}
+
+/// Setup schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    // Fund authority ATA
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    // Add participant
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+#[test]
+fn test_adjust_allocation_top_up() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 120_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    // refresh grant of 500 on top of the original 1000
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    MintTo::new(&mut svm, &authority, &mint, &authority_ata, 500_000_000).send().unwrap();
+
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 1_500_000_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "AdjustAllocation should succeed: {:?}", result.err());
+
+    assert_eq!(get_allocated_amount(&svm, &vested_participant_pda), 1_500_000_000);
+    assert_eq!(get_token_balance(&svm, &vault), 1_500_000_000);
+    assert_eq!(get_token_balance(&svm, &authority_ata), 0);
+}
+
+#[test]
+fn test_adjust_allocation_reduce_refunds_authority() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 120_001;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 400_000_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "AdjustAllocation should succeed: {:?}", result.err());
+
+    assert_eq!(get_allocated_amount(&svm, &vested_participant_pda), 400_000_000);
+    assert_eq!(get_token_balance(&svm, &vault), 400_000_000);
+    assert_eq!(get_token_balance(&svm, &authority_ata), 600_000_000);
+}
+
+#[test]
+fn test_adjust_allocation_after_cliff_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 120_002;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 400_000_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::CannotAddParticipantAfterCliff);
+}
+
+#[test]
+fn test_adjust_allocation_non_authority_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 120_003;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    // the participant tries to double their own grant
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+    MintTo::new(&mut svm, &authority, &mint, &participant_ata, allocation).send().unwrap();
+
+    let ix = build_adjust_allocation_ix(
+        &participant.pubkey(), &participant_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 2 * allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Only the authority can adjust an allocation");
+}
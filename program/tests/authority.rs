@@ -0,0 +1,272 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateMint, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AcceptAuthority, AcceptAuthorityAccounts, CancelAuthorityTransfer,
+    CancelAuthorityTransferAccounts, Initialize, InitializeAccounts, InitializeInstructionData,
+    ProposeAuthority, ProposeAuthorityAccounts, Schedule, TimeBase, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::Transaction,
+};
+
+// System program ID: 11111111111111111111111111111111
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_vault_ata(schedule: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(schedule, mint)
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn get_schedule_authorities(svm: &LiteSVM, schedule: &Pubkey) -> (Pubkey, Pubkey) {
+    let account = svm.get_account(schedule).expect("Schedule not found");
+    let schedule = Schedule::from_bytes(account.data()).unwrap();
+    (
+        Pubkey::new_from_array(*schedule.authority()),
+        Pubkey::new_from_array(*schedule.pending_authority()),
+    )
+}
+
+fn build_propose_authority_ix(authority: &Pubkey, schedule: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    client::instruction(
+        *ProposeAuthority::DISCRIMINATOR,
+        &ProposeAuthorityAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("new_authority", *new_authority),
+        ],
+        &[],
+    )
+}
+
+fn build_accept_authority_ix(pending_authority: &Pubkey, schedule: &Pubkey) -> Instruction {
+    client::instruction(
+        *AcceptAuthority::DISCRIMINATOR,
+        &AcceptAuthorityAccounts::ACCOUNTS,
+        &[
+            ("pending_authority", *pending_authority),
+            ("schedule", *schedule),
+        ],
+        &[],
+    )
+}
+
+fn build_cancel_authority_transfer_ix(authority: &Pubkey, schedule: &Pubkey) -> Instruction {
+    client::instruction(
+        *CancelAuthorityTransfer::DISCRIMINATOR,
+        &CancelAuthorityTransferAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+        ],
+        &[],
+    )
+}
+
+/// Helper to initialize a schedule and return its PDA
+fn setup_schedule(svm: &mut LiteSVM, authority: &Keypair, seed: u64) -> Pubkey {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_vault_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        2000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize should succeed");
+
+    schedule
+}
+
+#[test]
+fn test_authority_transfer_success() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let new_authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&new_authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1000, ..Default::default() });
+
+    let schedule = setup_schedule(&mut svm, &authority, 500);
+
+    let ix = build_propose_authority_ix(&authority.pubkey(), &schedule, &new_authority.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "ProposeAuthority should succeed: {:?}", result.err());
+
+    // Proposal alone does not change the authority
+    let (current, pending) = get_schedule_authorities(&svm, &schedule);
+    assert_eq!(current, authority.pubkey());
+    assert_eq!(pending, new_authority.pubkey());
+
+    let ix = build_accept_authority_ix(&new_authority.pubkey(), &schedule);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&new_authority.pubkey()), &[&new_authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "AcceptAuthority should succeed: {:?}", result.err());
+
+    let (current, pending) = get_schedule_authorities(&svm, &schedule);
+    assert_eq!(current, new_authority.pubkey());
+    assert_eq!(pending, Pubkey::default());
+}
+
+#[test]
+fn test_propose_authority_wrong_authority_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let attacker = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&attacker.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1000, ..Default::default() });
+
+    let schedule = setup_schedule(&mut svm, &authority, 501);
+
+    let ix = build_propose_authority_ix(&attacker.pubkey(), &schedule, &attacker.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&attacker.pubkey()), &[&attacker], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Only the current authority can propose");
+}
+
+#[test]
+fn test_accept_authority_wrong_signer_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let new_authority = Keypair::new();
+    let attacker = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&attacker.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1000, ..Default::default() });
+
+    let schedule = setup_schedule(&mut svm, &authority, 502);
+
+    let ix = build_propose_authority_ix(&authority.pubkey(), &schedule, &new_authority.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("ProposeAuthority should succeed");
+
+    let ix = build_accept_authority_ix(&attacker.pubkey(), &schedule);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&attacker.pubkey()), &[&attacker], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Only the pending authority can accept");
+}
+
+#[test]
+fn test_cancel_authority_transfer() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let new_authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&new_authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1000, ..Default::default() });
+
+    let schedule = setup_schedule(&mut svm, &authority, 503);
+
+    let ix = build_propose_authority_ix(&authority.pubkey(), &schedule, &new_authority.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("ProposeAuthority should succeed");
+
+    let ix = build_cancel_authority_transfer_ix(&authority.pubkey(), &schedule);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "CancelAuthorityTransfer should succeed: {:?}", result.err());
+
+    // Accepting a cancelled proposal fails
+    let ix = build_accept_authority_ix(&new_authority.pubkey(), &schedule);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&new_authority.pubkey()), &[&new_authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Accepting a cancelled transfer should fail");
+
+    let (current, pending) = get_schedule_authorities(&svm, &schedule);
+    assert_eq!(current, authority.pubkey());
+    assert_eq!(pending, Pubkey::default());
+}
@@ -0,0 +1,583 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddBasketAllocation, AddBasketAllocationAccounts, AddBasketAllocationInstructionData,
+    AddBasketMint, AddBasketMintAccounts, AddParticipant, AddParticipantAccounts,
+    AddParticipantInstructionData, BasketLeg, Claim, ClaimAccounts, Discriminator, Initialize,
+    InitializeAccounts, InitializeInstructionData, PinocchioError, Revoke, RevokeAccounts, Schedule,
+    TimeBase, VestedParticipant, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_revoke_ix(
+    authority: &Pubkey,
+    vested_participant: &Pubkey,
+    clawback_account: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Revoke::DISCRIMINATOR,
+        &RevokeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("vested_participant", *vested_participant),
+            ("clawback_account", *clawback_account),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_add_basket_mint_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    basket_mint: &Pubkey,
+    basket_vault: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *AddBasketMint::DISCRIMINATOR,
+        &AddBasketMintAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *basket_mint),
+            ("vault", *basket_vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_add_basket_allocation_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    basket_vault: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    basket_mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    client::instruction(
+        *AddBasketAllocation::DISCRIMINATOR,
+        &AddBasketAllocationAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *basket_vault),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *basket_mint),
+            ("token_program", spl_token::ID),
+        ],
+        &AddBasketAllocationInstructionData { token_allocation_amount: allocation }.to_bytes(),
+    )
+}
+
+/// Appends one (mint, vault, token_account, token_program) basket leg group.
+fn push_basket_leg(ix: &mut Instruction, basket_mint: &Pubkey, basket_vault: &Pubkey, token_account: &Pubkey) {
+    ix.accounts.push(AccountMeta::new_readonly(*basket_mint, false));
+    ix.accounts.push(AccountMeta::new(*basket_vault, false));
+    ix.accounts.push(AccountMeta::new(*token_account, false));
+    ix.accounts.push(AccountMeta::new_readonly(spl_token::ID, false));
+}
+
+fn assert_custom_error(result: litesvm::types::TransactionResult, error: PinocchioError) {
+    let err = result.expect_err("Transaction should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(error as u32)));
+}
+
+/// Setup schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    // Fund authority ATA
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    // Add participant
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+/// Setup schedule with one basket mint + participant holding both,
+/// returns (schedule, vault, mint, basket_mint, basket_vault, vested_participant_pda)
+fn setup_basket_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+    basket_allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let basket_mint = CreateMint::new(svm, authority).decimals(6).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+    let basket_vault = get_ata(&schedule, &basket_mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    let ix = build_add_basket_mint_ix(&authority.pubkey(), &schedule, &basket_mint, &basket_vault);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddBasketMint failed");
+
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+    assert_eq!(schedule_state.basket_mints(), &[basket_mint.to_bytes()]);
+    assert_eq!(schedule_state.basket_vaults(), &[basket_vault.to_bytes()]);
+
+    // Fund authority ATAs
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+    let authority_basket_ata = get_ata(&authority.pubkey(), &basket_mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &basket_mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &basket_mint, &authority_basket_ata, basket_allocation).send().unwrap();
+
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    let ix = build_add_basket_allocation_ix(
+        &authority.pubkey(), &authority_basket_ata, &basket_vault,
+        &vested_participant_pda, &schedule, &basket_mint, basket_allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddBasketAllocation failed");
+
+    (schedule, vault, mint, basket_mint, basket_vault, vested_participant_pda)
+}
+
+#[test]
+fn test_basket_claim_pays_every_mint() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 130_000;
+    let (schedule, vault, mint, basket_mint, basket_vault, vested_participant_pda) =
+        setup_basket_vesting(&mut svm, &authority, &participant, seed, 1_000_000_000, 2_000_000);
+
+    // the fixed fields are followed by one basket leg per basket mint
+    let participant_account = svm.get_account(&vested_participant_pda).unwrap();
+    assert_eq!(participant_account.data.len(), VestedParticipant::space(1));
+    let legs = BasketLeg::from_bytes(&participant_account.data[VestedParticipant::LEN..]).unwrap();
+    assert_eq!(legs[0].allocated_amount(), 2_000_000);
+    assert_eq!(get_token_balance(&svm, &basket_vault), 2_000_000);
+
+    // 3/5 vested at 1200, on both mints
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    let participant_basket_ata = get_ata(&participant.pubkey(), &basket_mint);
+    let mut ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    push_basket_leg(&mut ix, &basket_mint, &basket_vault, &participant_basket_ata);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Basket claim should succeed: {:?}", result.err());
+
+    assert_eq!(get_token_balance(&svm, &participant_ata), 600_000_000);
+    assert_eq!(get_token_balance(&svm, &participant_basket_ata), 1_200_000);
+
+    let participant_account = svm.get_account(&vested_participant_pda).unwrap();
+    let legs = BasketLeg::from_bytes(&participant_account.data[VestedParticipant::LEN..]).unwrap();
+    assert_eq!(legs[0].claimed_amount(), 1_200_000);
+}
+
+#[test]
+fn test_basket_claim_pays_leg_after_primary_is_finalized() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    // a primary allocation of 2 floors to 1 at both 3/5 and 4/5, the basket leg doesn't
+    let seed: u64 = 130_004;
+    let (schedule, vault, mint, basket_mint, basket_vault, vested_participant_pda) =
+        setup_basket_vesting(&mut svm, &authority, &participant, seed, 2, 2_000_000);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    let participant_basket_ata = get_ata(&participant.pubkey(), &basket_mint);
+    let build_basket_claim_ix = || {
+        let mut ix = build_claim_ix(
+            &participant.pubkey(), &vested_participant_pda, &participant_ata,
+            &vault, &schedule, &mint,
+        );
+        push_basket_leg(&mut ix, &basket_mint, &basket_vault, &participant_basket_ata);
+        ix
+    };
+
+    // 3/5 vested at 1200
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let tx = Transaction::new_signed_with_payer(
+        &[build_basket_claim_ix()], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Basket claim failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), 1);
+    assert_eq!(get_token_balance(&svm, &participant_basket_ata), 1_200_000);
+
+    // revoked at 4/5, the primary mint is cut down to the 1 already claimed, the leg keeps 1_600_000
+    svm.set_sysvar(&Clock { unix_timestamp: 1250, ..Default::default() });
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let authority_basket_ata = get_ata(&authority.pubkey(), &basket_mint);
+    let mut ix = build_revoke_ix(
+        &authority.pubkey(), &vested_participant_pda, &authority_ata, &vault, &schedule, &mint,
+    );
+    push_basket_leg(&mut ix, &basket_mint, &basket_vault, &authority_basket_ata);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Revoke failed");
+
+    let participant_account = svm.get_account(&vested_participant_pda).unwrap();
+    let participant_state = VestedParticipant::from_bytes(&participant_account.data).unwrap();
+    assert_eq!(participant_state.allocated_amount(), 1);
+    assert_eq!(participant_state.claimed_amount(), 1);
+
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[build_basket_claim_ix()], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim of the leftover leg should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 1);
+    assert_eq!(get_token_balance(&svm, &participant_basket_ata), 1_600_000);
+
+    // every mint is paid out now
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[build_basket_claim_ix()], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::CannotDoubleClaim);
+}
+
+#[test]
+fn test_basket_claim_without_leg_accounts_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 130_001;
+    let (schedule, vault, mint, _, _, vested_participant_pda) =
+        setup_basket_vesting(&mut svm, &authority, &participant, seed, 1_000_000_000, 2_000_000);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    // a claim has to pay every mint of the basket at once
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Claim without the basket leg accounts should fail");
+}
+
+#[test]
+fn test_basket_claim_with_other_vault_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 130_005;
+    let (schedule, vault, mint, basket_mint, _, vested_participant_pda) =
+        setup_basket_vesting(&mut svm, &authority, &participant, seed, 1_000_000_000, 2_000_000);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    // legs are compared against the vault AddBasketMint stored, any other account of the mint is refused
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    let participant_basket_ata = get_ata(&participant.pubkey(), &basket_mint);
+    let authority_basket_ata = get_ata(&authority.pubkey(), &basket_mint);
+    let mut ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    push_basket_leg(&mut ix, &basket_mint, &authority_basket_ata, &participant_basket_ata);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::InvalidAddress);
+}
+
+#[test]
+fn test_add_basket_mint_after_participants_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 130_002;
+    let (schedule, _, _, _) =
+        setup_vesting(&mut svm, &authority, &participant, seed, 1_000_000_000);
+
+    let basket_mint = CreateMint::new(&mut svm, &authority).decimals(6).send().unwrap();
+    let basket_vault = get_ata(&schedule, &basket_mint);
+    let ix = build_add_basket_mint_ix(&authority.pubkey(), &schedule, &basket_mint, &basket_vault);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::BasketLocked);
+}
+
+#[test]
+fn test_add_primary_mint_to_basket_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 130_003;
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    let ix = build_add_basket_mint_ix(&authority.pubkey(), &schedule, &mint, &vault);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::BasketMintInvalid);
+}
@@ -0,0 +1,386 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    BatchAddParticipant, BatchAddParticipantAccounts, BatchAddParticipantInstructionData,
+    Initialize, InitializeAccounts, InitializeInstructionData, Schedule, TimeBase,
+    VestedParticipant, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+// BatchEntryError codes are offset so they can't collide with PinocchioError
+const BATCH_ERROR_OFFSET: u32 = 1_000;
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_batch_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    participants: &[(Pubkey, Pubkey, u64)], // (wallet, vested_participant_pda, allocation)
+) -> Instruction {
+    let amounts: Vec<u64> = participants.iter().map(|(_, _, allocation)| *allocation).collect();
+    let mut ix = client::instruction(
+        *BatchAddParticipant::DISCRIMINATOR,
+        &BatchAddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &BatchAddParticipantInstructionData::pack(&amounts),
+    );
+    for (wallet, vested_participant_pda, _) in participants {
+        ix.accounts.push(AccountMeta::new_readonly(*wallet, false));
+        ix.accounts.push(AccountMeta::new(*vested_participant_pda, false));
+    }
+    ix
+}
+
+fn fund_authority(svm: &mut LiteSVM, authority: &Keypair, mint: &Pubkey, amount: u64) -> Pubkey {
+    let authority_ata = get_ata(&authority.pubkey(), mint);
+    CreateAssociatedTokenAccount::new(svm, authority, mint)
+        .owner(&authority.pubkey())
+        .send()
+        .unwrap();
+    MintTo::new(svm, authority, mint, &authority_ata, amount)
+        .send()
+        .unwrap();
+    authority_ata
+}
+
+fn new_participants(schedule: &Pubkey, allocations: &[u64]) -> Vec<(Pubkey, Pubkey, u64)> {
+    allocations
+        .iter()
+        .map(|allocation| {
+            let wallet = Pubkey::new_unique();
+            let (vested_participant_pda, _) = get_participant_pda(&wallet, schedule);
+            (wallet, vested_participant_pda, *allocation)
+        })
+        .collect()
+}
+
+fn assert_batch_entry_error(result: litesvm::types::TransactionResult, index: u32) {
+    let err = result.expect_err("Batch should fail").err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(BATCH_ERROR_OFFSET + index))
+    );
+}
+
+fn setup_schedule(svm: &mut LiteSVM, authority: &Keypair, seed: u64) -> (Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority)
+        .decimals(9)
+        .send()
+        .unwrap();
+
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(),
+        &schedule,
+        &mint,
+        &vault,
+        2000,  // start
+        100,   // cliff
+        50,    // step
+        300,   // total
+        seed,
+        bump,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx).expect("Initialize should succeed");
+
+    (schedule, vault, mint)
+}
+
+#[test]
+fn test_batch_add_participants_success() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1000,
+        ..Default::default()
+    });
+
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 70_000);
+    let authority_ata = fund_authority(&mut svm, &authority, &mint, 1_000_000_000);
+
+    let allocations: Vec<u64> = (1..=10).map(|i| i * 1_000_000).collect();
+    let participants = new_participants(&schedule, &allocations);
+
+    let ix = build_batch_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &participants,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "BatchAddParticipant should succeed: {:?}", result.err());
+
+    // the summed allocation arrives in a single transfer
+    let total: u64 = allocations.iter().sum();
+    assert_eq!(get_token_balance(&svm, &vault), total);
+    assert_eq!(get_token_balance(&svm, &authority_ata), 1_000_000_000 - total);
+
+    for (wallet, vested_participant_pda, allocation) in &participants {
+        let account = svm.get_account(vested_participant_pda).unwrap();
+        assert_eq!(account.owner, PROGRAM_ID);
+        let participant_state = VestedParticipant::from_bytes(&account.data).unwrap();
+        assert_eq!(participant_state.schedule(), schedule.as_array());
+        assert_eq!(participant_state.participant(), wallet.as_array());
+        assert_eq!(participant_state.allocated_amount(), *allocation);
+    }
+
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+    assert_eq!(schedule_state.participant_count(), 10);
+    assert_eq!(schedule_state.outstanding_amount(), total);
+}
+
+#[test]
+fn test_batch_wrong_pda_reports_index() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1000,
+        ..Default::default()
+    });
+
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 70_001);
+    let authority_ata = fund_authority(&mut svm, &authority, &mint, 1_000_000_000);
+
+    let mut participants = new_participants(&schedule, &[1_000, 2_000, 3_000, 4_000]);
+    // entry 2 points at another wallet's pda
+    participants[2].1 = participants[3].1;
+
+    let ix = build_batch_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &participants,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_batch_entry_error(svm.send_transaction(tx), 2);
+
+    // atomic, nothing from the earlier entries was kept
+    assert!(svm.get_account(&participants[0].1).is_none());
+    assert_eq!(get_token_balance(&svm, &authority_ata), 1_000_000_000);
+}
+
+#[test]
+fn test_batch_zero_allocation_reports_index() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1000,
+        ..Default::default()
+    });
+
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 70_002);
+    let authority_ata = fund_authority(&mut svm, &authority, &mint, 1_000_000_000);
+
+    let participants = new_participants(&schedule, &[1_000, 0, 3_000]);
+
+    let ix = build_batch_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &participants,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_batch_entry_error(svm.send_transaction(tx), 1);
+}
+
+#[test]
+fn test_batch_duplicate_wallet_reports_index() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1000,
+        ..Default::default()
+    });
+
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 70_003);
+    let authority_ata = fund_authority(&mut svm, &authority, &mint, 1_000_000_000);
+
+    let mut participants = new_participants(&schedule, &[1_000, 2_000, 3_000]);
+    participants[2].0 = participants[0].0;
+    participants[2].1 = participants[0].1;
+
+    let ix = build_batch_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &participants,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_batch_entry_error(svm.send_transaction(tx), 2);
+}
+
+#[test]
+fn test_batch_insufficient_funds_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1000,
+        ..Default::default()
+    });
+
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 70_004);
+    let authority_ata = fund_authority(&mut svm, &authority, &mint, 5_000);
+
+    let participants = new_participants(&schedule, &[2_000, 2_000, 2_000]);
+
+    let ix = build_batch_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &participants,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Batch larger than the balance should fail");
+}
+
+#[test]
+fn test_batch_after_cliff_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1000,
+        ..Default::default()
+    });
+
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 70_005);
+    let authority_ata = fund_authority(&mut svm, &authority, &mint, 1_000_000_000);
+
+    // start=2000, cliff=100 -> cliff ends at 2100
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 2200,
+        ..Default::default()
+    });
+
+    let participants = new_participants(&schedule, &[1_000, 2_000]);
+
+    let ix = build_batch_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &participants,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Batch after cliff should fail");
+}
@@ -1,21 +1,23 @@
 use litesvm::LiteSVM;
 use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    ClaimInstructionData, Initialize, InitializeAccounts, InitializeInstructionData, PinocchioError,
+    TimeBase, VestingMode,
+};
 use solana_sdk::{
     account::ReadableAccount,
-    instruction::{AccountMeta, Instruction},
+    instruction::{Instruction, InstructionError},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     sysvar::clock::Clock,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 
 const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
 
-const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
-    0xde, 0x0c, 0x2a, 0xd8, 0xf6, 0xeb, 0x0d, 0x5a, 0x94, 0x92, 0x02, 0x79, 0x06, 0xfa, 0xcc, 0x62,
-    0x60, 0xbb, 0x41, 0xca, 0xcd, 0xdd, 0x62, 0x68, 0x67, 0xb5, 0xe6, 0x8a, 0xfc, 0x26, 0xe0, 0x35,
-]);
 
 fn setup_svm() -> LiteSVM {
     let mut svm = LiteSVM::new()
@@ -28,8 +30,11 @@ fn setup_svm() -> LiteSVM {
     svm
 }
 
-fn get_schedule_pda(seed: u64) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"schedule", &seed.to_le_bytes()], &PROGRAM_ID)
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
 }
 
 fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
@@ -62,27 +67,32 @@ fn build_initialize_ix(
     seed: u64,
     bump: u8,
 ) -> Instruction {
-    let mut data = vec![0u8];
-    data.extend_from_slice(&start_timestamp.to_le_bytes());
-    data.extend_from_slice(&cliff_duration.to_le_bytes());
-    data.extend_from_slice(&step_duration.to_le_bytes());
-    data.extend_from_slice(&total_duration.to_le_bytes());
-    data.extend_from_slice(&seed.to_le_bytes());
-    data.push(bump);
-
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*authority, true),
-            AccountMeta::new(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
         ],
-        data,
-    }
+        &data.to_bytes(),
+    )
 }
 
 fn build_add_participant_ix(
@@ -95,24 +105,25 @@ fn build_add_participant_ix(
     mint: &Pubkey,
     allocation: u64,
 ) -> Instruction {
-    let mut data = vec![1u8];
-    data.extend_from_slice(&allocation.to_le_bytes());
-
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*authority, true),
-            AccountMeta::new(*authority_ata, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(*participant_wallet, false),
-            AccountMeta::new(*vested_participant_pda, false),
-            AccountMeta::new_readonly(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
         ],
-        data,
-    }
+        &data.to_bytes(),
+    )
 }
 
 fn build_claim_ix(
@@ -123,21 +134,42 @@ fn build_claim_ix(
     schedule: &Pubkey,
     mint: &Pubkey,
 ) -> Instruction {
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*participant_wallet, true),
-            AccountMeta::new(*vested_participant, false),
-            AccountMeta::new(*participant_ata, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
         ],
-        data: vec![2u8],
+        &[],
+    )
+}
+
+fn build_claim_amount_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut ix = build_claim_ix(participant_wallet, vested_participant, participant_ata, vault, schedule, mint);
+    ix.data = ClaimInstructionData {
+        close_when_finalized: false,
+        amount: Some(amount),
+        merkle_allocation: None,
+        merkle_proof: &[],
     }
+    .to_bytes();
+    ix
 }
 
 /// Setup schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
@@ -149,7 +181,7 @@ fn setup_vesting(
     allocation: u64,
 ) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
     let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
-    let (schedule, bump) = get_schedule_pda(seed);
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
     let vault = get_ata(&schedule, &mint);
 
     // Initialize: start=1000, cliff=100, step=50, total=300
@@ -478,3 +510,103 @@ fn test_claim_after_vesting_complete() {
     let balance = get_token_balance(&svm, &participant_ata);
     assert_eq!(balance, 1_000_000_000, "Should receive 100% of allocation");
 }
+
+#[test]
+fn test_claim_partial_amount() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 8000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    // Clock: 1200 -> 60% vested, 600_000_000 available
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_claim_amount_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint, 250_000_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Partial claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 250_000_000);
+
+    // The rest of the vested amount is still available
+    let ix = build_claim_amount_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint, 350_000_000,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Second partial claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 600_000_000);
+
+    // A claim without an amount still takes everything available
+    svm.set_sysvar(&Clock { unix_timestamp: 1400, ..Default::default() });
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Claim should succeed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), allocation);
+}
+
+#[test]
+fn test_claim_amount_exceeds_available_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 9000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_claim_amount_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint, 600_000_001,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let err = svm.send_transaction(tx).expect_err("Claiming more than is vested should fail").err;
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(0, InstructionError::Custom(PinocchioError::ClaimAmountUnavailable as u32))
+    );
+    assert_eq!(get_token_balance(&svm, &participant_ata), 0);
+}
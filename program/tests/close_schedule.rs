@@ -0,0 +1,434 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    ClaimInstructionData, CloseParticipant, CloseParticipantAccounts, CloseSchedule,
+    CloseScheduleAccounts, Initialize, InitializeAccounts, InitializeInstructionData,
+    TimeBase, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::Transaction,
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_claim_and_close_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    let mut ix = build_claim_ix(participant_wallet, vested_participant, participant_ata, vault, schedule, mint);
+    ix.data = ClaimInstructionData {
+        close_when_finalized: true,
+        amount: None,
+        merkle_allocation: None,
+        merkle_proof: &[],
+    }
+    .to_bytes();
+    ix.accounts.push(AccountMeta::new(*rent_payer, false));
+    ix
+}
+
+fn build_close_participant_ix(vested_participant: &Pubkey, schedule: &Pubkey, rent_payer: &Pubkey) -> Instruction {
+    client::instruction(
+        *CloseParticipant::DISCRIMINATOR,
+        &CloseParticipantAccounts::ACCOUNTS,
+        &[
+            ("vested_participant", *vested_participant),
+            ("schedule", *schedule),
+            ("rent_payer", *rent_payer),
+        ],
+        &[],
+    )
+}
+
+/// Setup schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    // Fund authority ATA
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    // Add participant
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+fn build_close_schedule_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *CloseSchedule::DISCRIMINATOR,
+        &CloseScheduleAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder_token_account", *authority_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &[],
+    )
+}
+
+/// Claims the whole allocation after vesting ends, closing the participant account on the way
+fn claim_everything_and_close(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    schedule: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    vested_participant_pda: &Pubkey,
+) {
+    svm.set_sysvar(&Clock { unix_timestamp: 1400, ..Default::default() });
+
+    let participant_ata = get_ata(&participant.pubkey(), mint);
+    CreateAssociatedTokenAccount::new(svm, participant, mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_claim_and_close_ix(
+        &participant.pubkey(), vested_participant_pda, &participant_ata,
+        vault, schedule, mint, &authority.pubkey(),
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Claim should succeed");
+}
+
+#[test]
+fn test_close_schedule_success_sweeps_dust() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 40_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    claim_everything_and_close(
+        &mut svm, &authority, &participant, &schedule, &vault, &mint, &vested_participant_pda,
+    );
+
+    // Someone sends stray tokens to the vault
+    MintTo::new(&mut svm, &authority, &mint, &vault, 42).send().unwrap();
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_close_schedule_ix(&authority.pubkey(), &authority_ata, &vault, &schedule, &mint);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "CloseSchedule should succeed: {:?}", result.err());
+
+    assert_eq!(get_token_balance(&svm, &authority_ata), 42, "Dust should be swept to the authority");
+    assert!(svm.get_account(&vault).map_or(true, |a| a.lamports == 0), "Vault should be closed");
+    assert!(svm.get_account(&schedule).map_or(true, |a| a.lamports == 0), "Schedule should be closed");
+}
+
+#[test]
+fn test_close_schedule_with_outstanding_allocation_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 41_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, _) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_close_schedule_ix(&authority.pubkey(), &authority_ata, &vault, &schedule, &mint);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Closing a schedule with unclaimed allocations should fail");
+}
+
+#[test]
+fn test_close_schedule_requires_participants_closed() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 42_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1400, ..Default::default() });
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    // Claim without closing the participant account
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Claim should succeed");
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_close_schedule_ix(&authority.pubkey(), &authority_ata, &vault, &schedule, &mint);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Open participant accounts should block closing the schedule");
+
+    let ix = build_close_participant_ix(&vested_participant_pda, &schedule, &authority.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("CloseParticipant should succeed");
+
+    svm.expire_blockhash();
+    let ix = build_close_schedule_ix(&authority.pubkey(), &authority_ata, &vault, &schedule, &mint);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "CloseSchedule should succeed once participants are closed: {:?}", result.err());
+}
+
+#[test]
+fn test_close_schedule_wrong_authority_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    let attacker = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&attacker.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 43_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    claim_everything_and_close(
+        &mut svm, &authority, &participant, &schedule, &vault, &mint, &vested_participant_pda,
+    );
+
+    let attacker_ata = get_ata(&attacker.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &attacker, &mint)
+        .owner(&attacker.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_close_schedule_ix(&attacker.pubkey(), &attacker_ata, &vault, &schedule, &mint);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&attacker.pubkey()), &[&attacker], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Only the schedule authority can close it");
+}
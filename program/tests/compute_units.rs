@@ -0,0 +1,507 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddBasketAllocation, AddBasketAllocationAccounts, AddBasketAllocationInstructionData,
+    AddBasketMint, AddBasketMintAccounts, AddParticipant, AddParticipantAccounts,
+    AddParticipantInstructionData, Claim, ClaimAccounts, ClaimInstructionData, CrankClaim,
+    Initialize, InitializeAccounts, InitializeInstructionData, merkle_leaf, MerkleTree, Pause,
+    PauseAccounts, SetMerkleRoot, SetMerkleRootAccounts, SetMerkleRootInstructionData, TimeBase,
+    VestingMode,
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::Transaction,
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_merkle_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+    proof: &[u8],
+) -> Instruction {
+    let mut ix = build_claim_ix(participant_wallet, vested_participant, participant_ata, vault, schedule, mint);
+    ix.data = ClaimInstructionData {
+        close_when_finalized: false,
+        amount: None,
+        merkle_allocation: Some(allocation),
+        merkle_proof: proof,
+    }
+    .to_bytes();
+    ix
+}
+
+fn build_crank_claim_ix(
+    keeper: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *CrankClaim::DISCRIMINATOR,
+        &CrankClaim::ACCOUNTS,
+        &[
+            ("keeper", *keeper),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_set_merkle_root_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    merkle_root: &[u8; 32],
+    merkle_total: u64,
+    merkle_claim_deadline: i64,
+) -> Instruction {
+    let data = SetMerkleRootInstructionData { merkle_root: *merkle_root, merkle_total, merkle_claim_deadline };
+
+    client::instruction(
+        *SetMerkleRoot::DISCRIMINATOR,
+        &SetMerkleRootAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_basket_mint_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    basket_mint: &Pubkey,
+    basket_vault: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *AddBasketMint::DISCRIMINATOR,
+        &AddBasketMintAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *basket_mint),
+            ("vault", *basket_vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_add_basket_allocation_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    basket_vault: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    basket_mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    client::instruction(
+        *AddBasketAllocation::DISCRIMINATOR,
+        &AddBasketAllocationAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *basket_vault),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *basket_mint),
+            ("token_program", spl_token::ID),
+        ],
+        &AddBasketAllocationInstructionData { token_allocation_amount: allocation }.to_bytes(),
+    )
+}
+
+fn build_pause_ix(authority: &Pubkey, schedule: &Pubkey) -> Instruction {
+    client::instruction(
+        *Pause::DISCRIMINATOR,
+        &PauseAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+        ],
+        &[],
+    )
+}
+
+/// Ceilings sit a little above what each instruction costs today, so a change that brings back a bump search or an extra CPI fails here
+const INITIALIZE_CU_CEILING: u64 = 60_000;
+const ADD_PARTICIPANT_CU_CEILING: u64 = 30_000;
+const CLAIM_CU_CEILING: u64 = 20_000;
+const PAUSE_CU_CEILING: u64 = 5_000;
+const BASKET_CLAIM_CU_CEILING: u64 = 30_000;
+const MERKLE_CLAIM_CU_CEILING: u64 = 40_000;
+const CRANK_CLAIM_CU_CEILING: u64 = 25_000;
+
+fn send(svm: &mut LiteSVM, ix: Instruction, signer: &Keypair) -> u64 {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&signer.pubkey()), &[signer], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Transaction failed").compute_units_consumed
+}
+
+/// Schedule with start=1000, cliff=100, step=50, total=300, returns (schedule, vault, mint)
+fn setup_schedule(svm: &mut LiteSVM, authority: &Keypair, seed: u64) -> (Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    send(svm, ix, authority);
+    (schedule, vault, mint)
+}
+
+fn create_funded_ata(svm: &mut LiteSVM, owner: &Keypair, mint: &Pubkey, amount: u64) -> Pubkey {
+    let ata = get_ata(&owner.pubkey(), mint);
+    CreateAssociatedTokenAccount::new(svm, owner, mint)
+        .owner(&owner.pubkey()).send().unwrap();
+    if amount > 0 {
+        MintTo::new(svm, owner, mint, &ata, amount).send().unwrap();
+    }
+    ata
+}
+
+#[test]
+fn test_compute_units_stay_under_ceilings() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 170_000;
+    let allocation: u64 = 1_000_000_000;
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let initialize_cu = send(&mut svm, ix, &authority);
+    assert!(initialize_cu <= INITIALIZE_CU_CEILING, "Initialize used {initialize_cu} CU");
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(&mut svm, &authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let add_participant_cu = send(&mut svm, ix, &authority);
+    assert!(add_participant_cu <= ADD_PARTICIPANT_CU_CEILING, "AddParticipant used {add_participant_cu} CU");
+
+    // The participant ata exists already so Claim is measured without its creation
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let claim_cu = send(&mut svm, ix, &participant);
+    assert!(claim_cu <= CLAIM_CU_CEILING, "Claim used {claim_cu} CU");
+
+    let ix = build_pause_ix(&authority.pubkey(), &schedule);
+    let pause_cu = send(&mut svm, ix, &authority);
+    assert!(pause_cu <= PAUSE_CU_CEILING, "Pause used {pause_cu} CU");
+}
+
+#[test]
+fn test_basket_claim_stays_under_ceiling() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let allocation: u64 = 1_000_000_000;
+    let basket_allocation: u64 = 2_000_000;
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 170_001);
+    let basket_mint = CreateMint::new(&mut svm, &authority).decimals(6).send().unwrap();
+    let basket_vault = get_ata(&schedule, &basket_mint);
+    let ix = build_add_basket_mint_ix(&authority.pubkey(), &schedule, &basket_mint, &basket_vault);
+    send(&mut svm, ix, &authority);
+
+    let authority_ata = create_funded_ata(&mut svm, &authority, &mint, allocation);
+    let authority_basket_ata = create_funded_ata(&mut svm, &authority, &basket_mint, basket_allocation);
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    send(&mut svm, ix, &authority);
+    let ix = build_add_basket_allocation_ix(
+        &authority.pubkey(), &authority_basket_ata, &basket_vault,
+        &vested_participant_pda, &schedule, &basket_mint, basket_allocation,
+    );
+    send(&mut svm, ix, &authority);
+
+    // Both atas exist already so only the two transfers and the leg checks are measured
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let participant_ata = create_funded_ata(&mut svm, &participant, &mint, 0);
+    let participant_basket_ata = create_funded_ata(&mut svm, &participant, &basket_mint, 0);
+
+    let mut ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    ix.accounts.push(AccountMeta::new_readonly(basket_mint, false));
+    ix.accounts.push(AccountMeta::new(basket_vault, false));
+    ix.accounts.push(AccountMeta::new(participant_basket_ata, false));
+    let basket_claim_cu = send(&mut svm, ix, &participant);
+    assert!(basket_claim_cu <= BASKET_CLAIM_CU_CEILING, "Basket claim used {basket_claim_cu} CU");
+}
+
+#[test]
+fn test_merkle_claim_stays_under_ceiling() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 170_002);
+    let authority_ata = create_funded_ata(&mut svm, &authority, &mint, 1_000_000_000);
+
+    let recipients: Vec<(Keypair, u64)> = (1..=4).map(|i| (Keypair::new(), i * 100_000)).collect();
+    let tree = MerkleTree::new(
+        recipients
+            .iter()
+            .map(|(wallet, allocation)| merkle_leaf(&schedule.to_bytes(), &wallet.pubkey().to_bytes(), *allocation))
+            .collect(),
+    );
+    let total: u64 = recipients.iter().map(|(_, allocation)| allocation).sum();
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &tree.root(), total, 2000,
+    );
+    send(&mut svm, ix, &authority);
+
+    // The first claim creates the participant record, which is the costly path
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let (recipient, allocation) = &recipients[2];
+    svm.airdrop(&recipient.pubkey(), 1_000_000_000).unwrap();
+    let recipient_ata = create_funded_ata(&mut svm, recipient, &mint, 0);
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &schedule);
+
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(), &vested_participant_pda, &recipient_ata,
+        &vault, &schedule, &mint, *allocation, &tree.proof(2),
+    );
+    let merkle_claim_cu = send(&mut svm, ix, recipient);
+    assert!(merkle_claim_cu <= MERKLE_CLAIM_CU_CEILING, "Merkle claim used {merkle_claim_cu} CU");
+}
+
+#[test]
+fn test_crank_claim_stays_under_ceiling() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    let keeper = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&keeper.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint) = setup_schedule(&mut svm, &authority, 170_003);
+    let authority_ata = create_funded_ata(&mut svm, &authority, &mint, allocation);
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    send(&mut svm, ix, &authority);
+
+    // The participant ata exists already so CrankClaim is measured without its creation
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let participant_ata = create_funded_ata(&mut svm, &participant, &mint, 0);
+
+    let ix = build_crank_claim_ix(
+        &keeper.pubkey(), &participant.pubkey(), &vested_participant_pda,
+        &participant_ata, &vault, &schedule, &mint,
+    );
+    let crank_claim_cu = send(&mut svm, ix, &keeper);
+    assert!(crank_claim_cu <= CRANK_CLAIM_CU_CEILING, "CrankClaim used {crank_claim_cu} CU");
+}
@@ -0,0 +1,42 @@
+use multi_token_vesting::{
+    idl::{idl, IDL_PATH},
+    PinocchioError,
+};
+use solana_sdk::pubkey::Pubkey;
+
+const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0xde, 0x0c, 0x2a, 0xd8, 0xf6, 0xeb, 0x0d, 0x5a, 0x94, 0x92, 0x02, 0x79, 0x06, 0xfa, 0xcc, 0x62,
+    0x60, 0xbb, 0x41, 0xca, 0xcd, 0xdd, 0x62, 0x68, 0x67, 0xb5, 0xe6, 0x8a, 0xfc, 0x26, 0xe0, 0x35,
+]);
+
+#[test]
+fn test_idl_is_up_to_date() {
+    // Building the IDL checks every layout against the structs
+    let generated = serde_json::to_string_pretty(&idl()).unwrap() + "\n";
+
+    // Regenerate with: UPDATE_IDL=1 cargo test --test idl
+    if std::env::var_os("UPDATE_IDL").is_some() {
+        std::fs::create_dir_all("idl").unwrap();
+        std::fs::write(IDL_PATH, &generated).unwrap();
+    }
+
+    let committed = std::fs::read_to_string(IDL_PATH).expect("IDL missing, run with UPDATE_IDL=1");
+    assert!(committed == generated, "{IDL_PATH} is stale, run with UPDATE_IDL=1");
+}
+
+#[test]
+fn test_idl_program_and_errors() {
+    let idl = idl();
+    let program = &idl["program"];
+
+    assert_eq!(program["publicKey"], PROGRAM_ID.to_string());
+
+    // Every custom error code the program can return is listed under its code
+    let errors = program["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), PinocchioError::ALL.len());
+    for (code, (error, listed)) in PinocchioError::ALL.iter().zip(errors).enumerate() {
+        assert_eq!(error.clone() as usize, code, "PinocchioError::ALL is out of code order");
+        assert_eq!(listed["code"], code);
+        assert_eq!(listed["message"], error.to_string());
+    }
+}
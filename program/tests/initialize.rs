@@ -1,8 +1,12 @@
 use litesvm::LiteSVM;
 use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, spl_token};
-use multi_token_vesting::{Discriminator, Schedule};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    Discriminator, Initialize, InitializeAccounts, InitializeInstructionData, Schedule, TimeBase,
+    Versioned, VestingMode,
+};
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::Instruction,
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
@@ -13,10 +17,6 @@ use solana_sdk::{
 // System program ID: 11111111111111111111111111111111
 const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
 
-const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
-    0xde, 0x0c, 0x2a, 0xd8, 0xf6, 0xeb, 0x0d, 0x5a, 0x94, 0x92, 0x02, 0x79, 0x06, 0xfa, 0xcc, 0x62,
-    0x60, 0xbb, 0x41, 0xca, 0xcd, 0xdd, 0x62, 0x68, 0x67, 0xb5, 0xe6, 0x8a, 0xfc, 0x26, 0xe0, 0x35,
-]);
 
 fn setup_svm() -> LiteSVM {
     let mut svm = LiteSVM::new()
@@ -29,8 +29,11 @@ fn setup_svm() -> LiteSVM {
     svm
 }
 
-fn get_schedule_pda(seed: u64) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"schedule", &seed.to_le_bytes()], &PROGRAM_ID)
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
 }
 
 fn get_vault_ata(schedule: &Pubkey, mint: &Pubkey) -> Pubkey {
@@ -49,27 +52,32 @@ fn build_initialize_ix(
     seed: u64,
     bump: u8,
 ) -> Instruction {
-    let mut data = vec![0u8]; // discriminator for Initialize
-    data.extend_from_slice(&start_timestamp.to_le_bytes());
-    data.extend_from_slice(&cliff_duration.to_le_bytes());
-    data.extend_from_slice(&step_duration.to_le_bytes());
-    data.extend_from_slice(&total_duration.to_le_bytes());
-    data.extend_from_slice(&seed.to_le_bytes());
-    data.push(bump);
-
-    Instruction {
-        program_id: PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(*authority, true),
-            AccountMeta::new(*schedule, false),
-            AccountMeta::new_readonly(*mint, false),
-            AccountMeta::new(*vault, false),
-            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
-            AccountMeta::new_readonly(spl_token::ID, false),
-            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
         ],
-        data,
-    }
+        &data.to_bytes(),
+    )
 }
 
 #[test]
@@ -87,7 +95,7 @@ fn test_initialize_success() {
 
     // Derive PDAs
     let seed: u64 = 1;
-    let (schedule, bump) = get_schedule_pda(seed);
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
     let vault = get_vault_ata(&schedule, &mint);
 
     // Set clock to known time
@@ -129,7 +137,14 @@ fn test_initialize_success() {
     // Verify schedule account exists and has correct size
     let schedule_account = svm.get_account(&schedule).unwrap();
     assert_eq!(schedule_account.owner, PROGRAM_ID);
-    assert_eq!(schedule_account.data.len(), 106); // Schedule::LEN
+    assert_eq!(schedule_account.data.len(), Schedule::LEN);
+    let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+    assert_eq!(schedule_state.version(), Schedule::VERSION);
+    assert_eq!(schedule_state.creator(), authority.pubkey().as_array());
+    assert_eq!(schedule_state.funder(), authority.pubkey().as_array());
+    assert!(!schedule_state.has_pauser());
+    assert_eq!(schedule_state.vault(), vault.as_array());
+    assert_eq!(schedule_state.time_base(), TimeBase::UnixTimestamp);
 }
 
 #[test]
@@ -145,7 +160,7 @@ fn test_initialize_start_in_past_fails() {
         .unwrap();
 
     let seed: u64 = 2;
-    let (schedule, bump) = get_schedule_pda(seed);
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
     let vault = get_vault_ata(&schedule, &mint);
 
     // Current time = 2000
@@ -191,7 +206,7 @@ fn test_initialize_uneven_steps_fails() {
         .unwrap();
 
     let seed: u64 = 3;
-    let (schedule, bump) = get_schedule_pda(seed);
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
     let vault = get_vault_ata(&schedule, &mint);
 
     svm.set_sysvar(&Clock {
@@ -238,7 +253,7 @@ fn test_initialize_duplicate_seed_fails() {
         .unwrap();
 
     let seed: u64 = 4;
-    let (schedule, bump) = get_schedule_pda(seed);
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
     let vault = get_vault_ata(&schedule, &mint);
 
     svm.set_sysvar(&Clock {
@@ -284,3 +299,79 @@ fn test_initialize_duplicate_seed_fails() {
     let result2 = svm.send_transaction(tx2);
     assert!(result2.is_err(), "Duplicate seed should fail");
 }
+
+#[test]
+fn test_initialize_seed_squatting_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    let squatter = Keypair::new();
+    svm.airdrop(&squatter.pubkey(), 10_000_000_000).unwrap();
+
+    let mint = CreateMint::new(&mut svm, &authority)
+        .decimals(9)
+        .send()
+        .unwrap();
+
+    let seed: u64 = 5;
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_vault_ata(&schedule, &mint);
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1000,
+        ..Default::default()
+    });
+
+    // Squatter front-runs with the address the authority's tooling computed
+    let squat_ix = build_initialize_ix(
+        &squatter.pubkey(),
+        &schedule,
+        &mint,
+        &vault,
+        2000, 100, 50, 300, seed, bump,
+    );
+    let squat_tx = Transaction::new_signed_with_payer(
+        &[squat_ix],
+        Some(&squatter.pubkey()),
+        &[&squatter],
+        svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(squat_tx).is_err(), "Squatter can't take the authority's address");
+
+    // The same seed only lands in the squatter's own namespace
+    let (squatter_schedule, squatter_bump) = get_schedule_pda(&squatter.pubkey(), &mint, seed);
+    let squatter_vault = get_vault_ata(&squatter_schedule, &mint);
+    let ix = build_initialize_ix(
+        &squatter.pubkey(),
+        &squatter_schedule,
+        &mint,
+        &squatter_vault,
+        2000, 100, 50, 300, seed, squatter_bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&squatter.pubkey()),
+        &[&squatter],
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Squatter's own schedule should succeed: {:?}", result.err());
+
+    // The authority's schedule is still free
+    let ix = build_initialize_ix(
+        &authority.pubkey(),
+        &schedule,
+        &mint,
+        &vault,
+        2000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[&authority],
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Authority's schedule should succeed: {:?}", result.err());
+}
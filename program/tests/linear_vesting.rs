@@ -0,0 +1,389 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    Discriminator, Initialize, InitializeAccounts, InitializeInstructionData, Schedule, TimeBase,
+    VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::Transaction,
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+    vesting_mode: VestingMode,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+const STEP: VestingMode = VestingMode::Step;
+const LINEAR: VestingMode = VestingMode::Linear;
+
+/// Setup linear schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_linear_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, no step, total=1100 -> streams 1000s after cliff
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 0, 1100, seed, bump, LINEAR,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+fn claim_at(
+    svm: &mut LiteSVM,
+    participant: &Keypair,
+    participant_ata: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    unix_timestamp: i64,
+) -> Result<(), String> {
+    svm.set_sysvar(&Clock { unix_timestamp, ..Default::default() });
+    svm.expire_blockhash();
+
+    let ix = build_claim_ix(
+        &participant.pubkey(), vested_participant_pda, participant_ata,
+        vault, schedule, mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| format!("{:?}", e.err))
+}
+
+#[test]
+fn test_linear_initialize_stores_mode() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let (schedule, _, _, _) =
+        setup_linear_vesting(&mut svm, &authority, &participant, 50_000, 1_000_000);
+
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    assert_eq!(schedule_account.data.len(), Schedule::LEN);
+    assert_eq!(Schedule::from_bytes(&schedule_account.data).unwrap().vesting_mode(), LINEAR);
+}
+
+#[test]
+fn test_linear_streams_per_second() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let allocation: u64 = 1_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_linear_vesting(&mut svm, &authority, &participant, 50_001, allocation);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    // cliff ends at 1100, nothing is unlocked at the cliff itself in linear mode
+    let result = claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1100,
+    );
+    assert!(result.is_err(), "Claim at cliff end should fail");
+
+    // 1 second after cliff: 1/1000 of the allocation
+    claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1101,
+    ).expect("Claim 1s after cliff failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), 1_000);
+
+    // 250 seconds after cliff: 25%
+    claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1350,
+    ).expect("Claim at 25% failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), 250_000);
+
+    // 777 seconds after cliff: 77.7%
+    claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1877,
+    ).expect("Claim at 77.7% failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), 777_000);
+
+    // fully vested at start + total_duration
+    claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 2100,
+    ).expect("Final claim failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), allocation);
+    assert_eq!(get_token_balance(&svm, &vault), 0);
+}
+
+#[test]
+fn test_linear_with_step_duration_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, 50_002);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 1100, 50_002, bump, LINEAR,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Linear mode with a step should fail");
+}
+
+#[test]
+fn test_linear_cliff_after_end_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, 50_003);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 2000, 0, 1100, 50_003, bump, LINEAR,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Cliff past the end should fail");
+}
+
+#[test]
+fn test_unknown_vesting_mode_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, 50_004);
+    let vault = get_ata(&schedule, &mint);
+
+    let mut ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, 50_004, bump, STEP,
+    );
+    // no VestingMode encodes 3, the mode is the second to last byte of the fixed data
+    ix.data[InitializeInstructionData::LEN - 1] = 3;
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Unknown vesting mode should fail");
+}
+
+#[test]
+fn test_step_mode_still_unlocks_in_steps() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, 50_005);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, 50_005, bump, STEP,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    assert_eq!(Schedule::from_bytes(&schedule_account.data).unwrap().vesting_mode(), STEP);
+}
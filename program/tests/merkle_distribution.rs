@@ -0,0 +1,1148 @@
+use litesvm::LiteSVM;
+use litesvm_token::{spl_token, CreateAssociatedTokenAccount, CreateMint, MintTo};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    Claim, ClaimAccounts, ClaimInstructionData, CloseParticipant, CloseParticipantAccounts,
+    CloseSchedule, CloseScheduleAccounts, Initialize, InitializeAccounts, InitializeInstructionData,
+    merkle_leaf, MerkleTree, PinocchioError, Schedule, SetMerkleRoot, SetMerkleRootAccounts,
+    SetMerkleRootInstructionData, TimeBase, TransferBeneficiary, TransferBeneficiaryAccounts,
+    VestedParticipant, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+// well after everything vests at 1300
+const MERKLE_CLAIM_DEADLINE: i64 = 2000;
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new().with_sigverify(false).with_builtins();
+    svm.add_program_from_file(PROGRAM_ID, "target/deploy/multi_token_vesting.so")
+        .expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_merkle_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+    proof: &[u8],
+) -> Instruction {
+    let mut ix = build_claim_ix(
+        participant_wallet,
+        vested_participant,
+        participant_ata,
+        vault,
+        schedule,
+        mint,
+    );
+    ix.data = ClaimInstructionData {
+        close_when_finalized: false,
+        amount: None,
+        merkle_allocation: Some(allocation),
+        merkle_proof: proof,
+    }
+    .to_bytes();
+    ix
+}
+
+fn build_set_merkle_root_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    merkle_root: &[u8; 32],
+    merkle_total: u64,
+    merkle_claim_deadline: i64,
+) -> Instruction {
+    let data = SetMerkleRootInstructionData { merkle_root: *merkle_root, merkle_total, merkle_claim_deadline };
+
+    client::instruction(
+        *SetMerkleRoot::DISCRIMINATOR,
+        &SetMerkleRootAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_close_participant_ix(
+    vested_participant: &Pubkey,
+    schedule: &Pubkey,
+    rent_payer: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *CloseParticipant::DISCRIMINATOR,
+        &CloseParticipantAccounts::ACCOUNTS,
+        &[
+            ("vested_participant", *vested_participant),
+            ("schedule", *schedule),
+            ("rent_payer", *rent_payer),
+        ],
+        &[],
+    )
+}
+
+fn build_close_schedule_ix(
+    authority: &Pubkey,
+    funder_token_account: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *CloseSchedule::DISCRIMINATOR,
+        &CloseScheduleAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder_token_account", *funder_token_account),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_transfer_beneficiary_ix(
+    signer: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    new_wallet: &Pubkey,
+    new_vested_participant: &Pubkey,
+    schedule: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *TransferBeneficiary::DISCRIMINATOR,
+        &TransferBeneficiaryAccounts::ACCOUNTS,
+        &[
+            ("signer", *signer),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("new_wallet", *new_wallet),
+            ("new_vested_participant", *new_vested_participant),
+            ("schedule", *schedule),
+            ("rent_payer", *participant_wallet),
+            ("system_program", SYSTEM_PROGRAM_ID),
+        ],
+        &[],
+    )
+}
+
+struct MerkleSetup {
+    schedule: Pubkey,
+    vault: Pubkey,
+    mint: Pubkey,
+    authority_ata: Pubkey,
+    recipients: Vec<(Keypair, u64)>,
+    tree: MerkleTree,
+}
+
+fn build_tree(schedule: &Pubkey, recipients: &[(Keypair, u64)]) -> MerkleTree {
+    MerkleTree::new(
+        recipients
+            .iter()
+            .map(|(wallet, allocation)| merkle_leaf(&schedule.to_bytes(), &wallet.pubkey().to_bytes(), *allocation))
+            .collect(),
+    )
+}
+
+fn send(svm: &mut LiteSVM, ix: Instruction, payer: &Keypair) -> Result<(), String> {
+    svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+        .map(|_| ())
+        .map_err(|e| format!("{:?}", e.err))
+}
+
+/// What `send` returns for a failed instruction
+fn custom_error(error: PinocchioError) -> Result<(), String> {
+    Err(format!("{:?}", TransactionError::InstructionError(0, InstructionError::Custom(error as u32))))
+}
+
+/// Schedule with start=1000, cliff=100, step=50, total=300 funded for 5 merkle recipients
+fn setup_merkle(svm: &mut LiteSVM, authority: &Keypair, seed: u64) -> MerkleSetup {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(),
+        &schedule,
+        &mint,
+        &vault,
+        1000,
+        100,
+        50,
+        300,
+        seed,
+        bump,
+    );
+    send(svm, ix, authority).expect("Initialize failed");
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey())
+        .send()
+        .unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, 1_000_000_000)
+        .send()
+        .unwrap();
+
+    let recipients: Vec<(Keypair, u64)> = (1..=5).map(|i| (Keypair::new(), i * 100_000)).collect();
+    for (wallet, _) in &recipients {
+        svm.airdrop(&wallet.pubkey(), 1_000_000_000).unwrap();
+    }
+    let tree = build_tree(&schedule, &recipients);
+    let total: u64 = recipients.iter().map(|(_, allocation)| allocation).sum();
+
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &authority_ata,
+        &vault,
+        &schedule,
+        &mint,
+        &tree.root(),
+        total,
+        MERKLE_CLAIM_DEADLINE,
+    );
+    send(svm, ix, authority).expect("SetMerkleRoot failed");
+
+    MerkleSetup {
+        schedule,
+        vault,
+        mint,
+        authority_ata,
+        recipients,
+        tree,
+    }
+}
+
+fn create_ata(svm: &mut LiteSVM, owner: &Keypair, mint: &Pubkey) -> Pubkey {
+    CreateAssociatedTokenAccount::new(svm, owner, mint)
+        .owner(&owner.pubkey())
+        .send()
+        .unwrap();
+    get_ata(&owner.pubkey(), mint)
+}
+
+#[test]
+fn test_set_merkle_root_funds_vault() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_000);
+
+    assert_eq!(get_token_balance(&svm, &setup.vault), 1_500_000);
+    assert_eq!(
+        get_token_balance(&svm, &setup.authority_ata),
+        1_000_000_000 - 1_500_000
+    );
+
+    let schedule_account = svm.get_account(&setup.schedule).unwrap();
+    let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+    assert_eq!(schedule_state.participant_count(), 0);
+    assert_eq!(schedule_state.outstanding_amount(), 1_500_000);
+    assert_eq!(schedule_state.merkle_root(), &setup.tree.root());
+    assert_eq!(schedule_state.merkle_total(), 1_500_000);
+}
+
+#[test]
+fn test_merkle_claim_creates_participant() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_001);
+    let (recipient, allocation) = &setup.recipients[2];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+
+    // start=1000, cliff=100 -> cliff_end=1100, 1200 is 3 of 5 periods
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1200,
+        ..Default::default()
+    });
+
+    let lamports_before = svm.get_account(&recipient.pubkey()).unwrap().lamports;
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(2),
+    );
+    send(&mut svm, ix, recipient).expect("Merkle claim failed");
+
+    assert_eq!(get_token_balance(&svm, &recipient_ata), allocation * 3 / 5);
+
+    // the claimer paid for its own record
+    let account = svm.get_account(&vested_participant_pda).unwrap();
+    assert_eq!(account.owner, PROGRAM_ID);
+    assert!(
+        svm.get_account(&recipient.pubkey()).unwrap().lamports
+            <= lamports_before - account.lamports
+    );
+    let participant_state = VestedParticipant::from_bytes(&account.data).unwrap();
+    assert_eq!(participant_state.rent_payer(), recipient.pubkey().as_array());
+    assert!(participant_state.is_from_merkle_leaf());
+
+    // later claims work without the proof
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1300,
+        ..Default::default()
+    });
+    let ix = build_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+    );
+    send(&mut svm, ix, recipient).expect("Follow-up claim failed");
+    assert_eq!(get_token_balance(&svm, &recipient_ata), *allocation);
+
+    // outstanding only drops by what was claimed
+    let schedule_account = svm.get_account(&setup.schedule).unwrap();
+    assert_eq!(
+        Schedule::from_bytes(&schedule_account.data).unwrap().outstanding_amount(),
+        1_500_000 - allocation
+    );
+}
+
+#[test]
+fn test_merkle_claim_wrong_allocation_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_002);
+    let (recipient, allocation) = &setup.recipients[0];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1300,
+        ..Default::default()
+    });
+
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        allocation * 2,
+        &setup.tree.proof(0),
+    );
+    assert!(
+        send(&mut svm, ix, recipient).is_err(),
+        "Inflated allocation should fail"
+    );
+
+    // another recipient's proof doesn't work either
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(1),
+    );
+    assert!(
+        send(&mut svm, ix, recipient).is_err(),
+        "Wrong proof should fail"
+    );
+    assert!(svm.get_account(&vested_participant_pda).is_none());
+}
+
+#[test]
+fn test_merkle_claim_someone_elses_leaf_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_003);
+    let (_, allocation) = &setup.recipients[4];
+
+    let thief = Keypair::new();
+    svm.airdrop(&thief.pubkey(), 1_000_000_000).unwrap();
+    let (vested_participant_pda, _) = get_participant_pda(&thief.pubkey(), &setup.schedule);
+    let thief_ata = create_ata(&mut svm, &thief, &setup.mint);
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1300,
+        ..Default::default()
+    });
+
+    let ix = build_merkle_claim_ix(
+        &thief.pubkey(),
+        &vested_participant_pda,
+        &thief_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(4),
+    );
+    assert!(
+        send(&mut svm, ix, &thief).is_err(),
+        "Leaf of another wallet should fail"
+    );
+}
+
+#[test]
+fn test_update_merkle_root_before_cliff_refunds() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_004);
+
+    // drop the last recipient
+    let tree = build_tree(&setup.schedule, &setup.recipients[..4]);
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        &tree.root(),
+        1_000_000,
+        MERKLE_CLAIM_DEADLINE,
+    );
+    send(&mut svm, ix, &authority).expect("Root update failed");
+
+    assert_eq!(get_token_balance(&svm, &setup.vault), 1_000_000);
+    assert_eq!(
+        get_token_balance(&svm, &setup.authority_ata),
+        1_000_000_000 - 1_000_000
+    );
+
+    let schedule_account = svm.get_account(&setup.schedule).unwrap();
+    let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+    assert_eq!(schedule_state.outstanding_amount(), 1_000_000);
+    assert_eq!(schedule_state.merkle_root(), &tree.root());
+}
+
+#[test]
+fn test_update_merkle_root_after_cliff_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_005);
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1200,
+        ..Default::default()
+    });
+
+    let tree = build_tree(&setup.schedule, &setup.recipients[..4]);
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        &tree.root(),
+        1_000_000,
+        MERKLE_CLAIM_DEADLINE,
+    );
+    assert!(
+        send(&mut svm, ix, &authority).is_err(),
+        "Root update after cliff should fail"
+    );
+}
+
+#[test]
+fn test_merkle_participant_not_closable() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_006);
+    let (recipient, allocation) = &setup.recipients[1];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1300,
+        ..Default::default()
+    });
+
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(1),
+    );
+    send(&mut svm, ix, recipient).expect("Merkle claim failed");
+
+    // closing would let the same leaf create a fresh record and claim twice
+    let ix = build_close_participant_ix(
+        &vested_participant_pda,
+        &setup.schedule,
+        &recipient.pubkey(),
+    );
+    assert!(
+        send(&mut svm, ix, recipient).is_err(),
+        "Closing a merkle participant should fail"
+    );
+    assert!(svm.get_account(&vested_participant_pda).is_some());
+}
+
+#[test]
+fn test_transfer_merkle_participant_keeps_leaf_used() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let new_wallet = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&new_wallet.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_007);
+    let (recipient, allocation) = &setup.recipients[3];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let (new_vested_participant_pda, _) =
+        get_participant_pda(&new_wallet.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+
+    // 1200 is 3 of 5 periods
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1200,
+        ..Default::default()
+    });
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(3),
+    );
+    send(&mut svm, ix, recipient).expect("Merkle claim failed");
+    let claimed = allocation * 3 / 5;
+
+    let ix = build_transfer_beneficiary_ix(
+        &recipient.pubkey(),
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &new_wallet.pubkey(),
+        &new_vested_participant_pda,
+        &setup.schedule,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&recipient.pubkey()),
+        &[recipient, &new_wallet],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("TransferBeneficiary failed");
+
+    // the old record stays open as fully claimed, so the leaf can't create a fresh one
+    let account = svm.get_account(&vested_participant_pda).unwrap();
+    assert_eq!(
+        VestedParticipant::from_bytes(&account.data).unwrap().allocated_amount(),
+        claimed
+    );
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1400,
+        ..Default::default()
+    });
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(3),
+    );
+    assert!(
+        send(&mut svm, ix, recipient).is_err(),
+        "The old wallet should not claim the leaf again"
+    );
+
+    let new_wallet_ata = create_ata(&mut svm, &new_wallet, &setup.mint);
+    let ix = build_claim_ix(
+        &new_wallet.pubkey(),
+        &new_vested_participant_pda,
+        &new_wallet_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+    );
+    send(&mut svm, ix, &new_wallet).expect("Claim by the new wallet failed");
+    assert_eq!(get_token_balance(&svm, &new_wallet_ata), allocation - claimed);
+}
+
+#[test]
+fn test_transfer_to_wallet_with_unclaimed_leaf_gives_up_the_leaf() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_011);
+    let (recipient, allocation) = &setup.recipients[3];
+    // the new wallet has its own leaf it hasn't claimed yet
+    let (new_wallet, new_wallet_allocation) = &setup.recipients[2];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let (new_vested_participant_pda, _) =
+        get_participant_pda(&new_wallet.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+
+    // 1200 is 3 of 5 periods
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1200,
+        ..Default::default()
+    });
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(3),
+    );
+    send(&mut svm, ix, recipient).expect("Merkle claim failed");
+    let claimed = allocation * 3 / 5;
+
+    let ix = build_transfer_beneficiary_ix(
+        &recipient.pubkey(),
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &new_wallet.pubkey(),
+        &new_vested_participant_pda,
+        &setup.schedule,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&recipient.pubkey()),
+        &[recipient, new_wallet],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("TransferBeneficiary failed");
+
+    // the moved grant sits on the new wallet's pda, so its own proof only pays out the moved grant
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1400,
+        ..Default::default()
+    });
+    let new_wallet_ata = create_ata(&mut svm, new_wallet, &setup.mint);
+    let ix = build_merkle_claim_ix(
+        &new_wallet.pubkey(),
+        &new_vested_participant_pda,
+        &new_wallet_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *new_wallet_allocation,
+        &setup.tree.proof(2),
+    );
+    send(&mut svm, ix, new_wallet).expect("Claim by the new wallet failed");
+    assert_eq!(get_token_balance(&svm, &new_wallet_ata), allocation - claimed);
+
+    // the new wallet's leaf is still owed and can't be claimed anymore
+    let schedule_account = svm.get_account(&setup.schedule).unwrap();
+    assert_eq!(
+        Schedule::from_bytes(&schedule_account.data).unwrap().merkle_total(),
+        1_500_000 - allocation
+    );
+}
+
+#[test]
+fn test_remove_merkle_root_after_vesting_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_008);
+
+    // start=1000, total=300 -> everything vested at 1300
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1400,
+        ..Default::default()
+    });
+    let (recipient, allocation) = &setup.recipients[4];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(4),
+    );
+    send(&mut svm, ix, recipient).expect("Merkle claim failed");
+
+    // the claimed leaf left the merkle total
+    let schedule_account = svm.get_account(&setup.schedule).unwrap();
+    assert_eq!(
+        Schedule::from_bytes(&schedule_account.data).unwrap().merkle_total(),
+        1_500_000 - allocation
+    );
+
+    // the authority can't take the unclaimed leaves back from recipients who waited, not before the claim deadline
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        &[0u8; 32],
+        0,
+        0,
+    );
+    assert!(
+        send(&mut svm, ix, &authority).is_err(),
+        "Root removal after vesting should fail"
+    );
+    assert_eq!(get_token_balance(&svm, &setup.vault), 1_500_000 - allocation);
+
+    let (late_recipient, late_allocation) = &setup.recipients[0];
+    let (late_participant_pda, _) = get_participant_pda(&late_recipient.pubkey(), &setup.schedule);
+    let late_recipient_ata = create_ata(&mut svm, late_recipient, &setup.mint);
+    let ix = build_merkle_claim_ix(
+        &late_recipient.pubkey(),
+        &late_participant_pda,
+        &late_recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *late_allocation,
+        &setup.tree.proof(0),
+    );
+    send(&mut svm, ix, late_recipient).expect("Late merkle claim failed");
+    assert_eq!(get_token_balance(&svm, &late_recipient_ata), *late_allocation);
+}
+
+#[test]
+fn test_merkle_proof_of_another_schedule_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_009);
+
+    // a second schedule of the same mint funded under the very same root
+    let seed = 80_010;
+    let (other_schedule, bump) = get_schedule_pda(&authority.pubkey(), &setup.mint, seed);
+    let other_vault = get_ata(&other_schedule, &setup.mint);
+    let ix = build_initialize_ix(
+        &authority.pubkey(),
+        &other_schedule,
+        &setup.mint,
+        &other_vault,
+        1000,
+        100,
+        50,
+        300,
+        seed,
+        bump,
+    );
+    send(&mut svm, ix, &authority).expect("Initialize failed");
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &other_vault,
+        &other_schedule,
+        &setup.mint,
+        &setup.tree.root(),
+        1_500_000,
+        MERKLE_CLAIM_DEADLINE,
+    );
+    send(&mut svm, ix, &authority).expect("SetMerkleRoot failed");
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1300,
+        ..Default::default()
+    });
+
+    // the leaf commits to the first schedule, so its proof doesn't verify on the second
+    let (recipient, allocation) = &setup.recipients[4];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &other_schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &other_vault,
+        &other_schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(4),
+    );
+    assert!(
+        send(&mut svm, ix, recipient).is_err(),
+        "Proof made for another schedule should fail"
+    );
+    assert!(svm.get_account(&vested_participant_pda).is_none());
+}
+
+#[test]
+fn test_merkle_claim_to_prefunded_participant_address() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_011);
+    let (recipient, allocation) = &setup.recipients[1];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+
+    // the record's address is public, anyone can send it lamports before the first claim
+    svm.airdrop(&vested_participant_pda, 1_000_000).unwrap();
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1200,
+        ..Default::default()
+    });
+
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(1),
+    );
+    send(&mut svm, ix, recipient).expect("Merkle claim to a prefunded address failed");
+
+    assert_eq!(get_token_balance(&svm, &recipient_ata), allocation * 3 / 5);
+    let account = svm.get_account(&vested_participant_pda).unwrap();
+    assert_eq!(account.owner, PROGRAM_ID);
+    assert!(account.lamports >= svm.minimum_balance_for_rent_exemption(account.data.len()));
+    let participant_state = VestedParticipant::from_bytes(&account.data).unwrap();
+    assert_eq!(participant_state.allocated_amount(), *allocation);
+    assert!(participant_state.is_from_merkle_leaf());
+}
+
+#[test]
+fn test_merkle_claim_deadline_before_vesting_end_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_012);
+
+    // everything vests at 1300, recipients get at least until then
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        &setup.tree.root(),
+        1_500_000,
+        1299,
+    );
+    assert_eq!(
+        send(&mut svm, ix, &authority),
+        custom_error(PinocchioError::MerkleClaimDeadlineInvalid)
+    );
+}
+
+#[test]
+fn test_unclaimed_leaves_swept_after_deadline_and_schedule_closes() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 500,
+        ..Default::default()
+    });
+
+    let setup = setup_merkle(&mut svm, &authority, 80_013);
+
+    // one recipient claims everything once vested, the others never show up
+    svm.set_sysvar(&Clock {
+        unix_timestamp: 1400,
+        ..Default::default()
+    });
+    let (recipient, allocation) = &setup.recipients[4];
+    let (vested_participant_pda, _) = get_participant_pda(&recipient.pubkey(), &setup.schedule);
+    let recipient_ata = create_ata(&mut svm, recipient, &setup.mint);
+    let ix = build_merkle_claim_ix(
+        &recipient.pubkey(),
+        &vested_participant_pda,
+        &recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *allocation,
+        &setup.tree.proof(4),
+    );
+    send(&mut svm, ix, recipient).expect("Merkle claim failed");
+    assert_eq!(get_token_balance(&svm, &recipient_ata), *allocation);
+
+    svm.set_sysvar(&Clock {
+        unix_timestamp: MERKLE_CLAIM_DEADLINE + 1,
+        ..Default::default()
+    });
+
+    // past the deadline no leaf can be claimed anymore
+    let (late_recipient, late_allocation) = &setup.recipients[0];
+    let (late_participant_pda, _) = get_participant_pda(&late_recipient.pubkey(), &setup.schedule);
+    let late_recipient_ata = create_ata(&mut svm, late_recipient, &setup.mint);
+    let ix = build_merkle_claim_ix(
+        &late_recipient.pubkey(),
+        &late_participant_pda,
+        &late_recipient_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        *late_allocation,
+        &setup.tree.proof(0),
+    );
+    assert_eq!(send(&mut svm, ix, late_recipient), custom_error(PinocchioError::MerkleClaimExpired));
+
+    // a new root stays locked, clearing it sweeps the unclaimed leaves back to the funder
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        &setup.tree.root(),
+        1_500_000 - allocation,
+        MERKLE_CLAIM_DEADLINE + 1000,
+    );
+    assert_eq!(send(&mut svm, ix, &authority), custom_error(PinocchioError::MerkleRootLocked));
+
+    let ix = build_set_merkle_root_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+        &[0u8; 32],
+        0,
+        0,
+    );
+    send(&mut svm, ix, &authority).expect("Sweeping the unclaimed leaves failed");
+    assert_eq!(get_token_balance(&svm, &setup.vault), 0);
+    assert_eq!(
+        get_token_balance(&svm, &setup.authority_ata),
+        1_000_000_000 - allocation
+    );
+
+    let schedule_account = svm.get_account(&setup.schedule).unwrap();
+    let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+    assert_eq!(schedule_state.merkle_total(), 0);
+    assert_eq!(schedule_state.outstanding_amount(), 0);
+
+    let ix = build_close_schedule_ix(
+        &authority.pubkey(),
+        &setup.authority_ata,
+        &setup.vault,
+        &setup.schedule,
+        &setup.mint,
+    );
+    send(&mut svm, ix, &authority).expect("Closing the partially claimed merkle schedule failed");
+    assert!(svm.get_account(&setup.schedule).is_none_or(|account| account.lamports == 0));
+}
@@ -0,0 +1,517 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    CloseParticipant, CloseParticipantAccounts, CloseSchedule, CloseScheduleAccounts, Discriminator,
+    Initialize, InitializeAccounts, InitializeInstructionData, MigrateAccount,
+    MigrateAccountAccounts, PinocchioError, Schedule, TimeBase, Versioned, VestedParticipant,
+    VestingMode,
+};
+use solana_sdk::{
+    account::{Account, ReadableAccount},
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+/// `extra_account` is only needed for version 1: the schedule's vault, or a participant's schedule
+fn build_migrate_account_ix(payer: &Pubkey, account: &Pubkey, extra_account: Option<&Pubkey>) -> Instruction {
+    let mut ix = client::instruction(
+        *MigrateAccount::DISCRIMINATOR,
+        &MigrateAccountAccounts::ACCOUNTS,
+        &[
+            ("payer", *payer),
+            ("account", *account),
+            ("system_program", SYSTEM_PROGRAM_ID),
+        ],
+        &[],
+    );
+    ix.accounts.extend(extra_account.map(|extra_account| AccountMeta::new(*extra_account, false)));
+    ix
+}
+
+fn build_close_participant_ix(vested_participant: &Pubkey, schedule: &Pubkey, rent_payer: &Pubkey) -> Instruction {
+    client::instruction(
+        *CloseParticipant::DISCRIMINATOR,
+        &CloseParticipantAccounts::ACCOUNTS,
+        &[
+            ("vested_participant", *vested_participant),
+            ("schedule", *schedule),
+            ("rent_payer", *rent_payer),
+        ],
+        &[],
+    )
+}
+
+fn build_close_schedule_ix(
+    authority: &Pubkey,
+    funder_token_account: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *CloseSchedule::DISCRIMINATOR,
+        &CloseScheduleAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder_token_account", *funder_token_account),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &[],
+    )
+}
+
+fn assert_custom_error(result: litesvm::types::TransactionResult, error: PinocchioError) {
+    let err = result.expect_err("Transaction should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(error as u32)));
+}
+
+/// Setup schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    // Fund authority ATA
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    // Add participant
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+/// Writes a schedule and participant the way the original program did, returns (schedule, vault, mint, vested_participant_pda)
+///
+/// The original program derived schedules from `["schedule", seed]` alone, took SPL Token mints only
+/// and stored neither a version byte nor anything after the bump.
+fn setup_original_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = Pubkey::find_program_address(&[b"schedule", &seed.to_le_bytes()], &PROGRAM_ID);
+
+    // discriminator, mint, authority, seed, start=1000, cliff=100, step=50, total=300, bump
+    let mut data = vec![0u8];
+    data.extend_from_slice(mint.as_ref());
+    data.extend_from_slice(authority.pubkey().as_ref());
+    data.extend_from_slice(&seed.to_le_bytes());
+    for value in [1000i64, 100, 50, 300] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.push(bump);
+    set_program_account(svm, &schedule, data);
+
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&schedule).send().unwrap();
+    let vault = get_ata(&schedule, &mint);
+    MintTo::new(svm, authority, &mint, &vault, allocation).send().unwrap();
+
+    // discriminator, schedule, participant, allocated_amount, claimed_amount
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let mut data = vec![1u8];
+    data.extend_from_slice(schedule.as_ref());
+    data.extend_from_slice(participant.pubkey().as_ref());
+    data.extend_from_slice(&allocation.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    set_program_account(svm, &vested_participant_pda, data);
+
+    // the authority funded the vault from its own ata
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+fn set_program_account(svm: &mut LiteSVM, address: &Pubkey, data: Vec<u8>) {
+    let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+    svm.set_account(
+        *address,
+        Account { lamports, data, owner: PROGRAM_ID, executable: false, rent_epoch: 0 },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_migrate_original_accounts() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    let keeper = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&keeper.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 180_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_original_vesting(&mut svm, &authority, &participant, seed, allocation);
+    assert_eq!(svm.get_account(&schedule).unwrap().data.len(), 106);
+    assert_eq!(svm.get_account(&vested_participant_pda).unwrap().data.len(), 81);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    // Nothing but MigrateAccount touches a version 1 account
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::AccountOutdated);
+
+    // The participant is counted on its schedule, which has to be migrated first
+    let ix = build_migrate_account_ix(&keeper.pubkey(), &vested_participant_pda, Some(&schedule));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&keeper.pubkey()), &[&keeper], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::AccountOutdated);
+
+    // The vault must be the schedule's own ata
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_migrate_account_ix(&keeper.pubkey(), &schedule, Some(&authority_ata));
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&keeper.pubkey()), &[&keeper], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::InvalidAddress);
+
+    // Tokens sent straight to the vault don't count as outstanding
+    MintTo::new(&mut svm, &authority, &mint, &vault, 1_000).send().unwrap();
+
+    // Anyone may migrate, the keeper pays for the extra bytes
+    for (account, extra_account) in [(schedule, vault), (vested_participant_pda, schedule)] {
+        svm.expire_blockhash();
+        let ix = build_migrate_account_ix(&keeper.pubkey(), &account, Some(&extra_account));
+        let tx = Transaction::new_signed_with_payer(
+            &[ix], Some(&keeper.pubkey()), &[&keeper], svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        assert!(result.is_ok(), "MigrateAccount should succeed: {:?}", result.err());
+
+        // the schedule starts with nothing outstanding, its participants add their unclaimed allocations
+        if account == schedule {
+            let schedule_account = svm.get_account(&schedule).unwrap();
+            let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+            assert_eq!(schedule_state.participant_count(), 0);
+            assert_eq!(schedule_state.outstanding_amount(), 0);
+        }
+    }
+
+    // Schedule: the original fields move up by the version byte
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    assert_eq!(schedule_account.data.len(), Schedule::LEN);
+    assert!(schedule_account.lamports >= svm.minimum_balance_for_rent_exemption(Schedule::LEN));
+    let schedule_state = Schedule::from_bytes(&schedule_account.data).unwrap();
+    assert_eq!(schedule_state.version(), Schedule::VERSION);
+    assert_eq!(schedule_state.mint(), mint.as_array());
+    assert_eq!(schedule_state.authority(), authority.pubkey().as_array());
+    assert_eq!(schedule_state.seed(), seed);
+    assert_eq!(schedule_state.start(), 1000);
+    assert_eq!(schedule_state.total_duration(), 300);
+    assert_eq!(schedule_state.participant_count(), 1);
+    assert_eq!(schedule_state.outstanding_amount(), allocation);
+    assert_eq!(schedule_state.vesting_mode(), VestingMode::Step);
+    // the creator isn't known and stays zeroed
+    assert_eq!(schedule_state.creator(), &[0u8; 32]);
+    assert_eq!(schedule_state.funder(), authority.pubkey().as_array());
+    assert_eq!(schedule_state.vault(), vault.as_array());
+    assert_eq!(schedule_state.time_base(), TimeBase::UnixTimestamp);
+
+    let (_, participant_bump) = get_participant_pda(&participant.pubkey(), &schedule);
+    let participant_account = svm.get_account(&vested_participant_pda).unwrap();
+    assert_eq!(participant_account.data.len(), VestedParticipant::LEN);
+    let participant_state = VestedParticipant::from_bytes(&participant_account.data).unwrap();
+    assert_eq!(participant_state.schedule(), schedule.as_array());
+    assert_eq!(participant_state.participant(), participant.pubkey().as_array());
+    assert_eq!(participant_state.allocated_amount(), allocation);
+    assert_eq!(participant_state.claimed_amount(), 0);
+    assert_eq!(participant_state.rent_payer(), authority.pubkey().as_array());
+    assert_eq!(participant_state.bump(), participant_bump);
+}
+
+#[test]
+fn test_migrate_current_account_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 180_003;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, _, _, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    for account in [schedule, vested_participant_pda] {
+        let ix = build_migrate_account_ix(&authority.pubkey(), &account, None);
+        let tx = Transaction::new_signed_with_payer(
+            &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+        );
+        assert_custom_error(svm.send_transaction(tx), PinocchioError::AccountUpToDate);
+        svm.expire_blockhash();
+    }
+}
+
+#[test]
+fn test_original_schedule_signs_with_original_seeds() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 180_004;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_original_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    for (account, extra_account) in [(schedule, vault), (vested_participant_pda, schedule)] {
+        let ix = build_migrate_account_ix(&authority.pubkey(), &account, Some(&extra_account));
+        let tx = Transaction::new_signed_with_payer(
+            &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).expect("MigrateAccount failed");
+    }
+    // The schedule isn't at the address the current seeds derive, only ["schedule", seed] can sign for it
+    assert_ne!(get_schedule_pda(&authority.pubkey(), &mint, seed).0, schedule);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim from a migrated schedule should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 600_000_000);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1400, ..Default::default() });
+    svm.expire_blockhash();
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Final claim failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), allocation);
+
+    // The original program made the authority pay the participant's rent, so it gets it back
+    let participant_rent = svm.get_account(&vested_participant_pda).unwrap().lamports;
+    let authority_balance = svm.get_balance(&authority.pubkey()).unwrap();
+    let ix = build_close_participant_ix(&vested_participant_pda, &schedule, &authority.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("CloseParticipant failed");
+    assert_eq!(svm.get_balance(&authority.pubkey()).unwrap(), authority_balance + participant_rent);
+
+    // Closing the vault is signed with the original seeds as well
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    let ix = build_close_schedule_ix(&authority.pubkey(), &authority_ata, &vault, &schedule, &mint);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "CloseSchedule of a migrated schedule should succeed: {:?}", result.err());
+    assert!(svm.get_account(&vault).map_or(true, |a| a.lamports == 0));
+    assert!(svm.get_account(&schedule).map_or(true, |a| a.lamports == 0));
+}
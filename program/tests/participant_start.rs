@@ -0,0 +1,324 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    Discriminator, Initialize, InitializeAccounts, InitializeInstructionData, PinocchioError,
+    TimeBase, VestedParticipant, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+    start: Option<i64>,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn assert_custom_error(result: litesvm::types::TransactionResult, error: PinocchioError) {
+    let err = result.expect_err("Transaction should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(error as u32)));
+}
+
+/// Setup schedule and fund the authority, returns (schedule, vault, mint, authority_ata)
+fn setup_schedule(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    seed: u64,
+    funding: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, funding).send().unwrap();
+
+    (schedule, vault, mint, authority_ata)
+}
+
+fn add_participant(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+    start: Option<i64>,
+) -> (Pubkey, litesvm::types::TransactionResult) {
+    let (vested_participant_pda, _) = get_participant_pda(participant, schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), authority_ata, vault,
+        participant, &vested_participant_pda, schedule, mint, allocation, start,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    (vested_participant_pda, svm.send_transaction(tx))
+}
+
+#[test]
+fn test_late_joiner_vests_from_own_start() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 190_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, authority_ata) = setup_schedule(&mut svm, &authority, seed, allocation);
+
+    // The schedule's cliff passed at 1100, the hire date of 1500 puts this participant's at 1600
+    svm.set_sysvar(&Clock { unix_timestamp: 1550, ..Default::default() });
+    let (vested_participant_pda, result) = add_participant(
+        &mut svm, &authority, &authority_ata, &vault,
+        &participant.pubkey(), &schedule, &mint, allocation, Some(1500),
+    );
+    assert!(result.is_ok(), "AddParticipant with own start should succeed: {:?}", result.err());
+
+    let participant_account = svm.get_account(&vested_participant_pda).unwrap();
+    assert_eq!(participant_account.data.len(), VestedParticipant::LEN);
+    assert_eq!(VestedParticipant::from_bytes(&participant_account.data).unwrap().start(), 1500);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    // Before the participant's own cliff
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::CannotClaimBeforeCliff);
+
+    // 200 seconds into their own schedule, as a participant on the schedule's start would be at 1200
+    svm.set_sysvar(&Clock { unix_timestamp: 1700, ..Default::default() });
+    svm.expire_blockhash();
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 600_000_000);
+}
+
+#[test]
+fn test_add_participant_after_own_cliff_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 190_001;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, authority_ata) = setup_schedule(&mut svm, &authority, seed, allocation);
+
+    svm.set_sysvar(&Clock { unix_timestamp: 1550, ..Default::default() });
+
+    // Without their own start the participant follows the schedule, whose cliff has passed
+    let (_, result) = add_participant(
+        &mut svm, &authority, &authority_ata, &vault,
+        &participant.pubkey(), &schedule, &mint, allocation, None,
+    );
+    assert_custom_error(result, PinocchioError::CannotAddParticipantAfterCliff);
+
+    // A start of 1400 puts the participant's own cliff at 1500, also passed
+    svm.expire_blockhash();
+    let (_, result) = add_participant(
+        &mut svm, &authority, &authority_ata, &vault,
+        &participant.pubkey(), &schedule, &mint, allocation, Some(1400),
+    );
+    assert_custom_error(result, PinocchioError::CannotAddParticipantAfterCliff);
+}
+
+#[test]
+fn test_add_participant_non_positive_start_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 190_002;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, authority_ata) = setup_schedule(&mut svm, &authority, seed, allocation);
+
+    for start in [0, -1] {
+        let (_, result) = add_participant(
+            &mut svm, &authority, &authority_ata, &vault,
+            &participant.pubkey(), &schedule, &mint, allocation, Some(start),
+        );
+        assert_custom_error(result, PinocchioError::StartTimeInvalid);
+        svm.expire_blockhash();
+    }
+}
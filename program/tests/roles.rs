@@ -0,0 +1,453 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, AdjustAllocation,
+    AdjustAllocationAccounts, AdjustAllocationInstructionData, Initialize, InitializeAccounts,
+    InitializeInstructionData, PauseAccounts, PinocchioError, Revoke, RevokeAccounts, Schedule,
+    SetFunder, SetFunderAccounts, SetPauser, SetPauserAccounts, TimeBase, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: 1000,
+        cliff_duration: 100,
+        step_duration: 50,
+        total_duration: 300,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    funder: &Pubkey,
+    funder_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *funder),
+            ("funder_ata", *funder_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_adjust_allocation_ix(
+    authority: &Pubkey,
+    funder: &Pubkey,
+    funder_signs: bool,
+    funder_ata: &Pubkey,
+    vault: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let mut ix = client::instruction(
+        *AdjustAllocation::DISCRIMINATOR,
+        &AdjustAllocationAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *funder),
+            ("funder_ata", *funder_ata),
+            ("vault", *vault),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &AdjustAllocationInstructionData { token_allocation_amount: allocation }.to_bytes(),
+    );
+    // the funder only signs when tokens are pulled from its ata
+    client::set_signer(&mut ix, funder, funder_signs);
+    ix
+}
+
+fn build_set_funder_ix(funder: &Pubkey, schedule: &Pubkey, new_funder: &Pubkey) -> Instruction {
+    client::instruction(
+        *SetFunder::DISCRIMINATOR,
+        &SetFunderAccounts::ACCOUNTS,
+        &[
+            ("funder", *funder),
+            ("schedule", *schedule),
+            ("new_funder", *new_funder),
+        ],
+        &[],
+    )
+}
+
+fn build_set_pauser_ix(authority: &Pubkey, schedule: &Pubkey, new_pauser: &Pubkey) -> Instruction {
+    client::instruction(
+        *SetPauser::DISCRIMINATOR,
+        &SetPauserAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("new_pauser", *new_pauser),
+        ],
+        &[],
+    )
+}
+
+// pause and unpause share their account list
+fn build_pause_ix(signer: &Pubkey, schedule: &Pubkey, discriminator: u8) -> Instruction {
+    client::instruction(
+        discriminator,
+        &PauseAccounts::ACCOUNTS,
+        &[("authority", *signer), ("schedule", *schedule)],
+        &[],
+    )
+}
+
+fn build_revoke_ix(
+    authority: &Pubkey,
+    vested_participant: &Pubkey,
+    clawback_account: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Revoke::DISCRIMINATOR,
+        &RevokeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("vested_participant", *vested_participant),
+            ("clawback_account", *clawback_account),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", spl_token::ID),
+        ],
+        &[],
+    )
+}
+
+fn send(svm: &mut LiteSVM, ix: Instruction, signers: &[&Keypair]) -> litesvm::types::TransactionResult {
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&signers[0].pubkey()), signers, svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+}
+
+fn assert_illegal_owner(result: litesvm::types::TransactionResult) {
+    let err = result.expect_err("Transaction should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::IllegalOwner));
+}
+
+/// Initializes a schedule and hands the funder role to `treasury`, returns (schedule, vault, mint, treasury_ata)
+fn setup_treasury_schedule(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    treasury: &Keypair,
+    seed: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(&authority.pubkey(), &schedule, &mint, &vault, seed, bump);
+    send(svm, ix, &[authority]).expect("Initialize failed");
+
+    // the creating authority funds by default
+    assert_eq!(Schedule::from_bytes(&svm.get_account(&schedule).unwrap().data).unwrap().funder(), authority.pubkey().as_array());
+
+    let ix = build_set_funder_ix(&authority.pubkey(), &schedule, &treasury.pubkey());
+    send(svm, ix, &[authority, treasury]).expect("SetFunder failed");
+    assert_eq!(Schedule::from_bytes(&svm.get_account(&schedule).unwrap().data).unwrap().funder(), treasury.pubkey().as_array());
+
+    let treasury_ata = get_ata(&treasury.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&treasury.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &treasury_ata, 5_000_000_000).send().unwrap();
+
+    (schedule, vault, mint, treasury_ata)
+}
+
+#[test]
+fn test_funder_pays_allocations_the_authority_manages() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let treasury = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&treasury.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 160_000;
+    let (schedule, vault, mint, treasury_ata) =
+        setup_treasury_schedule(&mut svm, &authority, &treasury, seed);
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+
+    // The authority alone can no longer fund from its own ata
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(&mut svm, &authority, &mint, &authority_ata, 1_000_000_000).send().unwrap();
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, 1_000_000_000,
+    );
+    assert_illegal_owner(send(&mut svm, ix, &[&authority]));
+
+    // The treasury can't add participants on its own either
+    let ix = build_add_participant_ix(
+        &treasury.pubkey(), &treasury.pubkey(), &treasury_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, 1_000_000_000,
+    );
+    assert_illegal_owner(send(&mut svm, ix, &[&treasury]));
+
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &treasury.pubkey(), &treasury_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, 1_000_000_000,
+    );
+    let result = send(&mut svm, ix, &[&authority, &treasury]);
+    assert!(result.is_ok(), "AddParticipant should succeed: {:?}", result.err());
+
+    assert_eq!(get_token_balance(&svm, &treasury_ata), 4_000_000_000);
+    assert_eq!(get_token_balance(&svm, &authority_ata), 1_000_000_000);
+    assert_eq!(get_token_balance(&svm, &vault), 1_000_000_000);
+
+    // A reduction refunds the treasury without its signature, a top up needs it
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &treasury.pubkey(), false, &treasury_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 600_000_000,
+    );
+    let result = send(&mut svm, ix, &[&authority]);
+    assert!(result.is_ok(), "Reduction should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &treasury_ata), 4_400_000_000);
+
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &treasury.pubkey(), false, &treasury_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 800_000_000,
+    );
+    assert!(send(&mut svm, ix, &[&authority]).is_err(), "Top up without the funder should fail");
+
+    let ix = build_adjust_allocation_ix(
+        &authority.pubkey(), &treasury.pubkey(), true, &treasury_ata, &vault,
+        &vested_participant_pda, &schedule, &mint, 800_000_000,
+    );
+    let result = send(&mut svm, ix, &[&authority, &treasury]);
+    assert!(result.is_ok(), "Top up should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &treasury_ata), 4_200_000_000);
+    assert_eq!(get_token_balance(&svm, &vault), 800_000_000);
+}
+
+#[test]
+fn test_only_funder_hands_on_funder_role() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let treasury = Keypair::new();
+    let new_treasury = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&treasury.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 160_001;
+    let (schedule, _, _, _) = setup_treasury_schedule(&mut svm, &authority, &treasury, seed);
+
+    // Refunds follow the funder, so the authority can't take the role back
+    let ix = build_set_funder_ix(&authority.pubkey(), &schedule, &authority.pubkey());
+    assert_illegal_owner(send(&mut svm, ix, &[&authority]));
+
+    // A mistyped funder could never sign a funding transfer, so the new funder has to sign too
+    let mut ix = build_set_funder_ix(&treasury.pubkey(), &schedule, &new_treasury.pubkey());
+    ix.accounts[2].is_signer = false;
+    let err = send(&mut svm, ix, &[&treasury]).expect_err("Unsigned new funder should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(PinocchioError::InvalidSigner as u32)));
+
+    let ix = build_set_funder_ix(&treasury.pubkey(), &schedule, &new_treasury.pubkey());
+    let result = send(&mut svm, ix, &[&treasury, &new_treasury]);
+    assert!(result.is_ok(), "SetFunder should succeed: {:?}", result.err());
+    assert_eq!(Schedule::from_bytes(&svm.get_account(&schedule).unwrap().data).unwrap().funder(), new_treasury.pubkey().as_array());
+}
+
+#[test]
+fn test_pauser_pauses_but_only_authority_unpauses() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let treasury = Keypair::new();
+    let pauser = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&treasury.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&pauser.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 160_002;
+    let (schedule, _, _, _) = setup_treasury_schedule(&mut svm, &authority, &treasury, seed);
+
+    // Without a pauser only the authority may pause
+    let ix = build_pause_ix(&pauser.pubkey(), &schedule, 13);
+    assert_illegal_owner(send(&mut svm, ix, &[&pauser]));
+
+    let ix = build_set_pauser_ix(&pauser.pubkey(), &schedule, &pauser.pubkey());
+    assert_illegal_owner(send(&mut svm, ix, &[&pauser]));
+
+    let ix = build_set_pauser_ix(&authority.pubkey(), &schedule, &pauser.pubkey());
+    let result = send(&mut svm, ix, &[&authority]);
+    assert!(result.is_ok(), "SetPauser should succeed: {:?}", result.err());
+    assert_eq!(Schedule::from_bytes(&svm.get_account(&schedule).unwrap().data).unwrap().pauser(), pauser.pubkey().as_array());
+
+    let ix = build_pause_ix(&pauser.pubkey(), &schedule, 13);
+    let result = send(&mut svm, ix, &[&pauser]);
+    assert!(result.is_ok(), "Pauser should pause: {:?}", result.err());
+    assert!(Schedule::from_bytes(&svm.get_account(&schedule).unwrap().data).unwrap().is_paused());
+
+    let ix = build_pause_ix(&pauser.pubkey(), &schedule, 14);
+    assert_illegal_owner(send(&mut svm, ix, &[&pauser]));
+
+    let ix = build_pause_ix(&authority.pubkey(), &schedule, 14);
+    let result = send(&mut svm, ix, &[&authority]);
+    assert!(result.is_ok(), "Authority should unpause: {:?}", result.err());
+    assert!(!Schedule::from_bytes(&svm.get_account(&schedule).unwrap().data).unwrap().is_paused());
+
+    // The system program's address removes the pauser again
+    let ix = build_set_pauser_ix(&authority.pubkey(), &schedule, &SYSTEM_PROGRAM_ID);
+    send(&mut svm, ix, &[&authority]).expect("SetPauser failed");
+    assert!(!Schedule::from_bytes(&svm.get_account(&schedule).unwrap().data).unwrap().has_pauser());
+
+    svm.expire_blockhash();
+    let ix = build_pause_ix(&pauser.pubkey(), &schedule, 13);
+    assert_illegal_owner(send(&mut svm, ix, &[&pauser]));
+}
+
+#[test]
+fn test_clawback_goes_to_the_funder() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let treasury = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&treasury.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 160_003;
+    let (schedule, vault, mint, treasury_ata) =
+        setup_treasury_schedule(&mut svm, &authority, &treasury, seed);
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &treasury.pubkey(), &treasury_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, 1_000_000_000,
+    );
+    let result = send(&mut svm, ix, &[&authority, &treasury]);
+    assert!(result.is_ok(), "AddParticipant should succeed: {:?}", result.err());
+
+    // The authority can't divert the treasury's tokens to its own account
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    let ix = build_revoke_ix(
+        &authority.pubkey(), &vested_participant_pda, &authority_ata, &vault, &schedule, &mint,
+    );
+    let err = send(&mut svm, ix, &[&authority]).expect_err("Clawback to the authority should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(PinocchioError::InvalidAddress as u32)));
+
+    let ix = build_revoke_ix(
+        &authority.pubkey(), &vested_participant_pda, &treasury_ata, &vault, &schedule, &mint,
+    );
+    let result = send(&mut svm, ix, &[&authority]);
+    assert!(result.is_ok(), "Revoke should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &treasury_ata), 5_000_000_000);
+    assert_eq!(get_token_balance(&svm, &authority_ata), 0);
+}
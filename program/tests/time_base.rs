@@ -0,0 +1,370 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    Initialize, InitializeAccounts, InitializeInstructionData, PinocchioError, Schedule, TimeBase,
+    VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::{Transaction, TransactionError},
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+    time_base: TimeBase,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn assert_custom_error(result: litesvm::types::TransactionResult, error: PinocchioError) {
+    let err = result.expect_err("Transaction should fail").err;
+    assert_eq!(err, TransactionError::InstructionError(0, InstructionError::Custom(error as u32)));
+}
+
+const UNIX_TIMESTAMP: TimeBase = TimeBase::UnixTimestamp;
+const SLOT: TimeBase = TimeBase::Slot;
+const EPOCH: TimeBase = TimeBase::Epoch;
+
+/// Setup a schedule in the given time base with one participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Pubkey,
+    seed: u64,
+    allocation: u64,
+    (start, cliff, step, total): (u64, u64, u64, u64),
+    time_base: TimeBase,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        start, cliff, step, total, seed, bump, time_base,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    let (vested_participant_pda, _) = get_participant_pda(participant, &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        participant, &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+fn send_claim(
+    svm: &mut LiteSVM,
+    participant: &Keypair,
+    vested_participant_pda: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> litesvm::types::TransactionResult {
+    let ix = build_claim_ix(
+        &participant.pubkey(), vested_participant_pda, participant_ata,
+        vault, schedule, mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    svm.expire_blockhash();
+    result
+}
+
+#[test]
+fn test_slot_schedule_vests_by_slot() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    // The unix timestamp is already past the whole schedule, only the slot counts
+    svm.set_sysvar(&Clock { slot: 500, unix_timestamp: 1_000_000, ..Default::default() });
+
+    let seed: u64 = 210_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) = setup_vesting(
+        &mut svm, &authority, &participant.pubkey(), seed, allocation,
+        (1000, 100, 50, 300), SLOT,
+    );
+
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    assert_eq!(Schedule::from_bytes(&schedule_account.data).unwrap().time_base(), SLOT);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    // Before the cliff at slot 1100
+    svm.set_sysvar(&Clock { slot: 1050, unix_timestamp: 2_000_000, ..Default::default() });
+    let result = send_claim(
+        &mut svm, &participant, &vested_participant_pda, &participant_ata, &vault, &schedule, &mint,
+    );
+    assert_custom_error(result, PinocchioError::CannotClaimBeforeCliff);
+
+    // Slot 1200 completes the cliff and two steps of 50, 60% vested
+    svm.set_sysvar(&Clock { slot: 1200, unix_timestamp: 2_000_000, ..Default::default() });
+    let result = send_claim(
+        &mut svm, &participant, &vested_participant_pda, &participant_ata, &vault, &schedule, &mint,
+    );
+    assert!(result.is_ok(), "Claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 600_000_000);
+}
+
+#[test]
+fn test_epoch_schedule_vests_by_epoch() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { epoch: 5, slot: 2_000, unix_timestamp: 1_000_000, ..Default::default() });
+
+    let seed: u64 = 210_001;
+    let allocation: u64 = 1_000_000_000;
+    // Start at epoch 10, a cliff of 2 epochs, then one step per epoch until epoch 14
+    let (schedule, vault, mint, vested_participant_pda) = setup_vesting(
+        &mut svm, &authority, &participant.pubkey(), seed, allocation,
+        (10, 2, 1, 4), EPOCH,
+    );
+
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    assert_eq!(Schedule::from_bytes(&schedule_account.data).unwrap().time_base(), EPOCH);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    // Epoch 13 completes the cliff and one step, two of three periods
+    svm.set_sysvar(&Clock { epoch: 13, slot: 5_000, unix_timestamp: 2_000_000, ..Default::default() });
+    let result = send_claim(
+        &mut svm, &participant, &vested_participant_pda, &participant_ata, &vault, &schedule, &mint,
+    );
+    assert!(result.is_ok(), "Claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), 666_600_000);
+
+    svm.set_sysvar(&Clock { epoch: 14, slot: 6_000, unix_timestamp: 2_000_000, ..Default::default() });
+    let result = send_claim(
+        &mut svm, &participant, &vested_participant_pda, &participant_ata, &vault, &schedule, &mint,
+    );
+    assert!(result.is_ok(), "Claim should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &participant_ata), allocation);
+}
+
+#[test]
+fn test_initialize_with_invalid_time_base_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 210_002;
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let mut ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump, UNIX_TIMESTAMP,
+    );
+    // no TimeBase encodes 3, the time base is the last byte of the fixed data
+    ix.data[InitializeInstructionData::LEN] = 3;
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert_custom_error(svm.send_transaction(tx), PinocchioError::TimeBaseInvalid);
+}
+
+#[test]
+fn test_initialize_start_is_checked_in_time_base() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    // Start 1000 is in the future as a unix timestamp but already past as a slot
+    svm.set_sysvar(&Clock { slot: 2_000, unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    for (seed, time_base) in [(210_003u64, SLOT), (210_004, UNIX_TIMESTAMP)] {
+        let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+        let vault = get_ata(&schedule, &mint);
+
+        let ix = build_initialize_ix(
+            &authority.pubkey(), &schedule, &mint, &vault,
+            1000, 100, 50, 300, seed, bump, time_base,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        if time_base == SLOT {
+            assert_custom_error(result, PinocchioError::StartTimeInvalid);
+        } else {
+            assert!(result.is_ok(), "Initialize should succeed: {:?}", result.err());
+        }
+    }
+}
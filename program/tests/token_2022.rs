@@ -0,0 +1,396 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, TransferChecked, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    CloseSchedule, CloseScheduleAccounts, Initialize, InitializeAccounts, InitializeInstructionData,
+    TimeBase, VestingMode,
+};
+use solana_sdk::{
+    account::{Account, ReadableAccount},
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::Transaction,
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+const TOKEN_2022_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token-2022 shares the base token account layout, balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+/// Token-2022 mint with a transfer fee of `fee_bps`, the authority mints and nobody can change or withdraw the fees
+fn create_transfer_fee_mint(svm: &mut LiteSVM, authority: &Keypair, fee_bps: u16) -> Pubkey {
+    let mint = Keypair::new().pubkey();
+
+    // base mint padded to the token account length, the account type byte and the transfer fee config extension
+    let data = vec![0u8; 165 + 1 + 4 + 108];
+    let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+    svm.set_account(
+        mint,
+        Account { lamports, data, owner: TOKEN_2022_PROGRAM_ID, executable: false, rent_epoch: 0 },
+    )
+    .unwrap();
+
+    // TransferFeeExtension::InitializeTransferFeeConfig without config or withdraw authority
+    let mut fee_config_data = vec![26u8, 0, 0, 0];
+    fee_config_data.extend_from_slice(&fee_bps.to_le_bytes());
+    fee_config_data.extend_from_slice(&u64::MAX.to_le_bytes());
+    let fee_config_ix = Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(mint, false)],
+        data: fee_config_data,
+    };
+
+    // InitializeMint2 with 6 decimals and no freeze authority
+    let mut mint_data = vec![20u8, 6];
+    mint_data.extend_from_slice(authority.pubkey().as_ref());
+    mint_data.push(0);
+    let mint_ix = Instruction {
+        program_id: TOKEN_2022_PROGRAM_ID,
+        accounts: vec![AccountMeta::new(mint, false)],
+        data: mint_data,
+    };
+
+    let tx = Transaction::new_signed_with_payer(
+        &[fee_config_ix, mint_ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    mint
+}
+
+/// Fees harvested to the mint, stored after the fee config and withdraw authorities in the extension
+fn get_mint_withheld_amount(svm: &LiteSVM, mint: &Pubkey) -> u64 {
+    let account = svm.get_account(mint).unwrap();
+    u64::from_le_bytes(account.data()[234..242].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    token_program: &Pubkey,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: 1000,
+        cliff_duration: 100,
+        step_duration: 50,
+        total_duration: 300,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", *token_program),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", *token_program),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", *token_program),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+#[test]
+fn test_token_2022_full_flow() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority)
+        .decimals(6)
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+
+    let seed: u64 = 20_000;
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint, &TOKEN_2022_PROGRAM_ID);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault, &TOKEN_2022_PROGRAM_ID, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Initialize with Token-2022 mint should succeed: {:?}", result.err());
+
+    let vault_account = svm.get_account(&vault).unwrap();
+    assert_eq!(vault_account.owner, TOKEN_2022_PROGRAM_ID, "Vault should be a Token-2022 account");
+
+    let allocation: u64 = 1_000_000_000;
+    let authority_ata = get_ata(&authority.pubkey(), &mint, &TOKEN_2022_PROGRAM_ID);
+    CreateAssociatedTokenAccount::new(&mut svm, &authority, &mint)
+        .owner(&authority.pubkey())
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+    MintTo::new(&mut svm, &authority, &mint, &authority_ata, allocation)
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint,
+        &TOKEN_2022_PROGRAM_ID, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "AddParticipant should succeed: {:?}", result.err());
+    assert_eq!(get_token_balance(&svm, &vault), allocation);
+
+    // Clock: 1200 -> 3/5 = 60% vested
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint, &TOKEN_2022_PROGRAM_ID);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint, &TOKEN_2022_PROGRAM_ID,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Claim should succeed: {:?}", result.err());
+
+    assert_eq!(get_token_balance(&svm, &participant_ata), 600_000_000, "Should receive 60% (3/5) of allocation");
+}
+
+fn build_close_schedule_ix(
+    authority: &Pubkey,
+    funder_token_account: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *CloseSchedule::DISCRIMINATOR,
+        &CloseScheduleAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder_token_account", *funder_token_account),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("token_program", *token_program),
+        ],
+        &[],
+    )
+}
+
+#[test]
+fn test_close_schedule_harvests_withheld_transfer_fees() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    // 1% transfer fee
+    let mint = create_transfer_fee_mint(&mut svm, &authority, 100);
+
+    let seed: u64 = 22_000;
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint, &TOKEN_2022_PROGRAM_ID);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault, &TOKEN_2022_PROGRAM_ID, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Initialize with a transfer fee mint should succeed: {:?}", result.err());
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint, &TOKEN_2022_PROGRAM_ID);
+    CreateAssociatedTokenAccount::new(&mut svm, &authority, &mint)
+        .owner(&authority.pubkey())
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+    MintTo::new(&mut svm, &authority, &mint, &authority_ata, 1_000_000)
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+
+    // Anything sent to the vault leaves 1% withheld on it, which token-2022 won't close over
+    TransferChecked::new(&mut svm, &authority, &mint, &vault, 1_000_000)
+        .source(&authority_ata)
+        .decimals(6)
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+    assert_eq!(get_token_balance(&svm, &vault), 990_000);
+
+    let ix = build_close_schedule_ix(
+        &authority.pubkey(), &authority_ata, &vault, &schedule, &mint, &TOKEN_2022_PROGRAM_ID,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "CloseSchedule should harvest the withheld fees and close: {:?}", result.err());
+
+    assert!(svm.get_account(&vault).is_none_or(|account| account.lamports == 0), "Vault should be closed");
+    assert!(svm.get_account(&schedule).is_none_or(|account| account.lamports == 0), "Schedule should be closed");
+    assert_eq!(get_mint_withheld_amount(&svm, &mint), 10_000, "The vault's withheld fees should be on the mint");
+    // the dust pays the fee on its way back to the funder
+    assert_eq!(get_token_balance(&svm, &authority_ata), 980_100);
+}
+
+#[test]
+fn test_token_program_mismatch_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority)
+        .decimals(6)
+        .token_program_id(&TOKEN_2022_PROGRAM_ID)
+        .send()
+        .unwrap();
+
+    let seed: u64 = 21_000;
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint, &spl_token::ID);
+
+    // Token-2022 mint but legacy token program passed in
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault, &spl_token::ID, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_err(), "Token program not owning the mint should fail");
+}
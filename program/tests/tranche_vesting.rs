@@ -0,0 +1,403 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    Discriminator, Initialize, InitializeAccounts, InitializeInstructionData, Schedule, TimeBase,
+    Tranche, VestingMode,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::Transaction,
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+    vesting_mode: VestingMode,
+    tranches: &[(i64, u16)],
+) -> Instruction {
+    let tranches: Vec<Tranche> = tranches
+        .iter()
+        .map(|&(offset, cumulative_bps)| Tranche { offset, cumulative_bps })
+        .collect();
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &tranches,
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+const STEP: VestingMode = VestingMode::Step;
+const TRANCHE: VestingMode = VestingMode::Tranche;
+
+// 10% at start, 25% after 100s, 50% after 200s, everything after 300s
+const TRANCHES: [(i64, u16); 4] = [(0, 1_000), (100, 2_500), (200, 5_000), (300, 10_000)];
+
+fn try_initialize(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    seed: u64,
+    cliff_duration: u64,
+    total_duration: u64,
+    vesting_mode: VestingMode,
+    tranches: &[(i64, u16)],
+) -> Result<(Pubkey, Pubkey, Pubkey), String> {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, cliff_duration, 0, total_duration, seed, bump, vesting_mode, tranches,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)
+        .map(|_| (schedule, vault, mint))
+        .map_err(|e| format!("{:?}", e.err))
+}
+
+/// Setup tranche schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_tranche_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let (schedule, vault, mint) =
+        try_initialize(svm, authority, seed, 0, 300, TRANCHE, &TRANCHES).expect("Initialize failed");
+
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+fn claim_at(
+    svm: &mut LiteSVM,
+    participant: &Keypair,
+    participant_ata: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    unix_timestamp: i64,
+) -> Result<(), String> {
+    svm.set_sysvar(&Clock { unix_timestamp, ..Default::default() });
+    svm.expire_blockhash();
+
+    let ix = build_claim_ix(
+        &participant.pubkey(), vested_participant_pda, participant_ata,
+        vault, schedule, mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| format!("{:?}", e.err))
+}
+
+#[test]
+fn test_tranche_initialize_stores_table() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let (schedule, _, _) =
+        try_initialize(&mut svm, &authority, 60_000, 0, 300, TRANCHE, &TRANCHES).unwrap();
+
+    let schedule_account = svm.get_account(&schedule).unwrap();
+    assert_eq!(schedule_account.data.len(), Schedule::space(4));
+    assert_eq!(Schedule::from_bytes(&schedule_account.data).unwrap().vesting_mode(), TRANCHE);
+
+    // the table follows the fixed fields
+    let tranches = Tranche::from_bytes(&schedule_account.data[Schedule::LEN..]).unwrap();
+    assert_eq!(tranches[1].offset(), 100);
+    assert_eq!(tranches[1].cumulative_bps(), 2_500);
+}
+
+#[test]
+fn test_tranche_claims_follow_table() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let allocation: u64 = 1_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_tranche_vesting(&mut svm, &authority, &participant, 60_001, allocation);
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+
+    // nothing before the first tranche
+    let result = claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 999,
+    );
+    assert!(result.is_err(), "Claim before the first tranche should fail");
+
+    // first tranche unlocks exactly at its timestamp
+    claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1000,
+    ).expect("Claim at first tranche failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), 100_000);
+
+    // between tranches the previous one still applies
+    claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1150,
+    ).expect("Claim at second tranche failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), 250_000);
+
+    let result = claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1199,
+    );
+    assert!(result.is_err(), "Nothing new should be claimable before the third tranche");
+
+    claim_at(
+        &mut svm, &participant, &participant_ata, &vested_participant_pda,
+        &vault, &schedule, &mint, 1300,
+    ).expect("Final claim failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), allocation);
+    assert_eq!(get_token_balance(&svm, &vault), 0);
+}
+
+#[test]
+fn test_tranche_not_ending_at_full_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let result = try_initialize(
+        &mut svm, &authority, 60_002, 0, 300, TRANCHE,
+        &[(0, 1_000), (300, 9_999)],
+    );
+    assert!(result.is_err(), "Table not ending at 100% should fail");
+}
+
+#[test]
+fn test_tranche_non_monotonic_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let result = try_initialize(
+        &mut svm, &authority, 60_003, 0, 300, TRANCHE,
+        &[(0, 5_000), (100, 2_500), (300, 10_000)],
+    );
+    assert!(result.is_err(), "Decreasing unlock share should fail");
+
+    let result = try_initialize(
+        &mut svm, &authority, 60_004, 0, 300, TRANCHE,
+        &[(0, 1_000), (200, 2_500), (200, 5_000), (300, 10_000)],
+    );
+    assert!(result.is_err(), "Duplicate offsets should fail");
+}
+
+#[test]
+fn test_tranche_empty_table_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let result = try_initialize(&mut svm, &authority, 60_005, 0, 300, TRANCHE, &[]);
+    assert!(result.is_err(), "Tranche mode without a table should fail");
+}
+
+#[test]
+fn test_tranche_durations_must_mirror_table() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let result = try_initialize(&mut svm, &authority, 60_006, 0, 400, TRANCHE, &TRANCHES);
+    assert!(result.is_err(), "Total duration different from the last offset should fail");
+
+    let result = try_initialize(&mut svm, &authority, 60_007, 50, 300, TRANCHE, &TRANCHES);
+    assert!(result.is_err(), "Cliff different from the first offset should fail");
+}
+
+#[test]
+fn test_step_mode_with_table_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let mint = CreateMint::new(&mut svm, &authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, 60_008);
+    let vault = get_ata(&schedule, &mint);
+
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, 60_008, bump, STEP, &TRANCHES,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert!(svm.send_transaction(tx).is_err(), "Tranches outside tranche mode should fail");
+}
@@ -0,0 +1,373 @@
+use litesvm::LiteSVM;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo, spl_token};
+use multi_token_vesting::{
+    client::{self, PROGRAM_ID},
+    AddParticipant, AddParticipantAccounts, AddParticipantInstructionData, Claim, ClaimAccounts,
+    GetVestingStatus, GetVestingStatusAccounts, Initialize, InitializeAccounts,
+    InitializeInstructionData, Pause, PauseAccounts, TimeBase, VestingMode, VestingStatus,
+};
+use solana_sdk::{
+    account::ReadableAccount,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar::clock::Clock,
+    transaction::Transaction,
+};
+
+const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0u8; 32]);
+
+
+fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new()
+        .with_sigverify(false)
+        .with_builtins();
+    svm.add_program_from_file(
+        PROGRAM_ID,
+        "target/deploy/multi_token_vesting.so",
+    ).expect("Failed to load program");
+    svm
+}
+
+fn get_schedule_pda(authority: &Pubkey, mint: &Pubkey, seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"schedule", authority.as_ref(), mint.as_ref(), &seed.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_participant_pda(participant: &Pubkey, schedule: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"participant", participant.as_ref(), schedule.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+fn get_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    spl_associated_token_account::get_associated_token_address(owner, mint)
+}
+
+fn get_token_balance(svm: &LiteSVM, ata: &Pubkey) -> u64 {
+    let account = svm.get_account(ata).expect("ATA not found");
+    let data = account.data();
+    // Token account balance is at bytes 64-72
+    u64::from_le_bytes(data[64..72].try_into().unwrap())
+}
+
+fn build_initialize_ix(
+    authority: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    vault: &Pubkey,
+    start_timestamp: u64,
+    cliff_duration: u64,
+    step_duration: u64,
+    total_duration: u64,
+    seed: u64,
+    bump: u8,
+) -> Instruction {
+    let data = InitializeInstructionData {
+        start_timestamp: start_timestamp as i64,
+        cliff_duration: cliff_duration as i64,
+        step_duration: step_duration as i64,
+        total_duration: total_duration as i64,
+        seed,
+        bump,
+        vesting_mode: VestingMode::Step,
+        time_base: TimeBase::UnixTimestamp,
+        tranches: &[],
+    };
+
+    client::instruction(
+        *Initialize::DISCRIMINATOR,
+        &InitializeAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("vault", *vault),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_add_participant_ix(
+    authority: &Pubkey,
+    authority_ata: &Pubkey,
+    vault: &Pubkey,
+    participant_wallet: &Pubkey,
+    vested_participant_pda: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+    allocation: u64,
+) -> Instruction {
+    let data = AddParticipantInstructionData { token_allocation_amount: allocation, start: None };
+
+    client::instruction(
+        *AddParticipant::DISCRIMINATOR,
+        &AddParticipantAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("funder", *authority),
+            ("funder_ata", *authority_ata),
+            ("vault", *vault),
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant_pda),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+        ],
+        &data.to_bytes(),
+    )
+}
+
+fn build_claim_ix(
+    participant_wallet: &Pubkey,
+    vested_participant: &Pubkey,
+    participant_ata: &Pubkey,
+    vault: &Pubkey,
+    schedule: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    client::instruction(
+        *Claim::DISCRIMINATOR,
+        &ClaimAccounts::ACCOUNTS,
+        &[
+            ("participant_wallet", *participant_wallet),
+            ("vested_participant", *vested_participant),
+            ("participant_ata", *participant_ata),
+            ("vault", *vault),
+            ("schedule", *schedule),
+            ("mint", *mint),
+            ("system_program", SYSTEM_PROGRAM_ID),
+            ("token_program", spl_token::ID),
+            ("associated_token_program", spl_associated_token_account::ID),
+        ],
+        &[],
+    )
+}
+
+fn build_get_vesting_status_ix(schedule: &Pubkey, vested_participant: &Pubkey) -> Instruction {
+    client::instruction(
+        *GetVestingStatus::DISCRIMINATOR,
+        &GetVestingStatusAccounts::ACCOUNTS,
+        &[
+            ("schedule", *schedule),
+            ("vested_participant", *vested_participant),
+        ],
+        &[],
+    )
+}
+
+fn build_pause_ix(authority: &Pubkey, schedule: &Pubkey) -> Instruction {
+    client::instruction(
+        *Pause::DISCRIMINATOR,
+        &PauseAccounts::ACCOUNTS,
+        &[
+            ("authority", *authority),
+            ("schedule", *schedule),
+        ],
+        &[],
+    )
+}
+
+/// Simulates GetVestingStatus like a frontend would over RPC and decodes the return data
+fn simulate_vesting_status(
+    svm: &LiteSVM,
+    payer: &Keypair,
+    schedule: &Pubkey,
+    vested_participant: &Pubkey,
+) -> VestingStatus {
+    let ix = build_get_vesting_status_ix(schedule, vested_participant);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&payer.pubkey()), &[payer], svm.latest_blockhash(),
+    );
+    let simulated = svm.simulate_transaction(tx).expect("GetVestingStatus failed");
+    assert_eq!(simulated.meta.return_data.program_id, PROGRAM_ID);
+    VestingStatus::from_bytes(&simulated.meta.return_data.data).unwrap()
+}
+
+/// Setup schedule + participant, returns (schedule, vault, mint, vested_participant_pda)
+fn setup_vesting(
+    svm: &mut LiteSVM,
+    authority: &Keypair,
+    participant: &Keypair,
+    seed: u64,
+    allocation: u64,
+) -> (Pubkey, Pubkey, Pubkey, Pubkey) {
+    let mint = CreateMint::new(svm, authority).decimals(9).send().unwrap();
+    let (schedule, bump) = get_schedule_pda(&authority.pubkey(), &mint, seed);
+    let vault = get_ata(&schedule, &mint);
+
+    // Initialize: start=1000, cliff=100, step=50, total=300
+    let ix = build_initialize_ix(
+        &authority.pubkey(), &schedule, &mint, &vault,
+        1000, 100, 50, 300, seed, bump,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Initialize failed");
+
+    // Fund authority ATA
+    let authority_ata = get_ata(&authority.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(svm, authority, &mint)
+        .owner(&authority.pubkey()).send().unwrap();
+    MintTo::new(svm, authority, &mint, &authority_ata, allocation).send().unwrap();
+
+    // Add participant
+    let (vested_participant_pda, _) = get_participant_pda(&participant.pubkey(), &schedule);
+    let ix = build_add_participant_ix(
+        &authority.pubkey(), &authority_ata, &vault,
+        &participant.pubkey(), &vested_participant_pda, &schedule, &mint, allocation,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("AddParticipant failed");
+
+    (schedule, vault, mint, vested_participant_pda)
+}
+
+#[test]
+fn test_status_before_cliff() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 140_000;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, _, _, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    let schedule_data = svm.get_account(&schedule).unwrap().data;
+    let status = simulate_vesting_status(&svm, &authority, &schedule, &vested_participant_pda);
+
+    // Cliff ends at 1100, the first period unlocks a second later, fully vested at start + total
+    assert_eq!(status, VestingStatus {
+        vested_amount: 0,
+        claimed_amount: 0,
+        claimable_amount: 0,
+        next_unlock_at: 1101,
+        fully_vested_at: 1300,
+    });
+
+    // Read-only, nothing was written
+    assert_eq!(svm.get_account(&schedule).unwrap().data, schedule_data);
+}
+
+#[test]
+fn test_status_tracks_claims() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+    svm.airdrop(&participant.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 140_001;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, vault, mint, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    // 3/5 vested at 1200, the next step unlocks at 1250
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let status = simulate_vesting_status(&svm, &participant, &schedule, &vested_participant_pda);
+    assert_eq!(status, VestingStatus {
+        vested_amount: 600_000_000,
+        claimed_amount: 0,
+        claimable_amount: 600_000_000,
+        next_unlock_at: 1250,
+        fully_vested_at: 1300,
+    });
+
+    let participant_ata = get_ata(&participant.pubkey(), &mint);
+    CreateAssociatedTokenAccount::new(&mut svm, &participant, &mint)
+        .owner(&participant.pubkey())
+        .send()
+        .unwrap();
+    let ix = build_claim_ix(
+        &participant.pubkey(), &vested_participant_pda, &participant_ata,
+        &vault, &schedule, &mint,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&participant.pubkey()), &[&participant], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Claim failed");
+    assert_eq!(get_token_balance(&svm, &participant_ata), 600_000_000);
+
+    svm.expire_blockhash();
+    let status = simulate_vesting_status(&svm, &participant, &schedule, &vested_participant_pda);
+    assert_eq!(status.claimed_amount, 600_000_000);
+    assert_eq!(status.claimable_amount, 0);
+
+    // Fully vested, nothing left to unlock
+    svm.set_sysvar(&Clock { unix_timestamp: 1300, ..Default::default() });
+    let status = simulate_vesting_status(&svm, &participant, &schedule, &vested_participant_pda);
+    assert_eq!(status.vested_amount, allocation);
+    assert_eq!(status.claimable_amount, 400_000_000);
+    assert_eq!(status.next_unlock_at, 0);
+}
+
+#[test]
+fn test_status_paused_has_nothing_claimable() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 140_002;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, _, _, vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+
+    let ix = build_pause_ix(&authority.pubkey(), &schedule);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Pause failed");
+
+    // Vesting keeps accruing while paused, but a claim would fail
+    svm.set_sysvar(&Clock { unix_timestamp: 1200, ..Default::default() });
+    let status = simulate_vesting_status(&svm, &authority, &schedule, &vested_participant_pda);
+    assert_eq!(status.vested_amount, 600_000_000);
+    assert_eq!(status.claimable_amount, 0);
+}
+
+#[test]
+fn test_status_participant_of_other_schedule_fails() {
+    let mut svm = setup_svm();
+
+    let authority = Keypair::new();
+    let participant = Keypair::new();
+    svm.airdrop(&authority.pubkey(), 10_000_000_000).unwrap();
+
+    svm.set_sysvar(&Clock { unix_timestamp: 500, ..Default::default() });
+
+    let seed: u64 = 140_003;
+    let allocation: u64 = 1_000_000_000;
+    let (schedule, _, _, _) =
+        setup_vesting(&mut svm, &authority, &participant, seed, allocation);
+    let (_, _, _, other_vested_participant_pda) =
+        setup_vesting(&mut svm, &authority, &participant, seed + 1, allocation);
+
+    let ix = build_get_vesting_status_ix(&schedule, &other_vested_participant_pda);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix], Some(&authority.pubkey()), &[&authority], svm.latest_blockhash(),
+    );
+    assert!(svm.simulate_transaction(tx).is_err(), "Participant must belong to the schedule");
+}